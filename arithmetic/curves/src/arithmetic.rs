@@ -22,6 +22,29 @@ pub trait CurveAffineExt: pasta_curves::arithmetic::CurveAffine {
         let coordinates = self.coordinates().unwrap();
         (*coordinates.x(), *coordinates.y())
     }
+
+    /// Sums `points` in affine coordinates by adding them pairwise, level by level,
+    /// with a single field inversion per level through [`Self::batch_add`]
+    fn batch_sum(points: &[Self]) -> Self {
+        let mut level = points.to_vec();
+        while level.len() > 1 {
+            let odd = if level.len() % 2 == 1 {
+                level.pop()
+            } else {
+                None
+            };
+
+            // Pairs are read from the front of the buffer, sums are written behind them
+            let num_points = level.len();
+            let output_indices: Vec<u32> = (num_points as u32..).take(num_points / 2).collect();
+            level.resize(num_points + num_points / 2, Self::identity());
+            Self::batch_add::<true, false>(&mut level, &output_indices, num_points, 0, &[], &[]);
+
+            level.drain(..num_points);
+            level.extend(odd);
+        }
+        level.pop().unwrap_or_else(Self::identity)
+    }
 }
 
 pub(crate) fn sqrt_tonelli_shanks<F: ff::PrimeField, S: AsRef<[u64]>>(
@@ -98,6 +121,30 @@ pub(crate) const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
     (lo, hi + carry as u64)
 }
 
+/// Parses a decimal or `0x`-prefixed hexadecimal string into little-endian
+/// 256-bit limbs, returning `None` on an invalid digit or overflow.
+pub(crate) fn parse_u256_vartime(s: &str) -> Option<[u64; 4]> {
+    let (digits, radix) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => (hex, 16),
+        None => (s, 10),
+    };
+    if digits.is_empty() {
+        return None;
+    }
+
+    let mut limbs = [0u64; 4];
+    for c in digits.chars() {
+        let mut carry = c.to_digit(radix)? as u64;
+        for limb in limbs.iter_mut() {
+            (*limb, carry) = mac(0, *limb, radix as u64, carry);
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(limbs)
+}
+
 /// Compute a * b, returning the result.
 #[inline(always)]
 pub(crate) fn mul_512(a: [u64; 4], b: [u64; 4]) -> [u64; 8] {
@@ -3,6 +3,53 @@ use std::collections::HashMap;
 
 use crate::pairing::{Engine, MultiMillerLoop};
 
+/// Prepared forms of G2 points keyed by their repr, so that points recurring
+/// across many verifications (e.g. SRS or static table points) are prepared once
+pub struct G2PreparedCache<E: MultiMillerLoop> {
+    prepared: HashMap<Vec<u8>, E::G2Prepared>,
+    /// number of lookups served from the cache
+    hits: usize,
+}
+
+impl<E: MultiMillerLoop> Default for G2PreparedCache<E> {
+    fn default() -> Self {
+        Self {
+            prepared: HashMap::default(),
+            hits: 0,
+        }
+    }
+}
+
+impl<E: MultiMillerLoop> G2PreparedCache<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached prepared points
+    pub fn len(&self) -> usize {
+        self.prepared.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prepared.is_empty()
+    }
+
+    /// Number of lookups that reused an already prepared point
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    fn get_or_prepare(&mut self, g2_repr: &[u8], g2: &E::G2) -> E::G2Prepared {
+        if let Some(prepared) = self.prepared.get(g2_repr) {
+            self.hits += 1;
+            return prepared.clone();
+        }
+        let prepared: E::G2Prepared = g2.to_affine().into();
+        self.prepared.insert(g2_repr.to_vec(), prepared.clone());
+        prepared
+    }
+}
+
 /// Dynamically batches tuples of points and returns output compatible with MultiMillerLoop
 pub struct PairingBatcher<E: MultiMillerLoop> {
     /// Mapping of g2 repr to overcome trait bounds
@@ -59,6 +106,33 @@ impl<E: MultiMillerLoop> PairingBatcher<E> {
         self.update_mapping(&g2_reprs, &g1_points, &g2_points);
     }
 
+    /// Adds a pairing equation that is already weighted by the caller, e.g. with
+    /// powers of a verifier challenge. Its G1 side is scaled by `weight` and no
+    /// running challenge is consumed
+    pub fn add_pairing_with_scalar(
+        &mut self,
+        pairs: &[(E::G1Affine, E::G2Affine)],
+        weight: E::Scalar,
+    ) {
+        let g2_reprs: Vec<_> = pairs
+            .iter()
+            .map(|&(_, g2)| g2.to_bytes().as_ref().to_vec())
+            .collect();
+        let g2_points: Vec<E::G2> = pairs.iter().map(|&(_, g2)| g2.into()).collect();
+        let g1_points: Vec<E::G1> = pairs.iter().map(|&(g1, _)| g1 * weight).collect();
+
+        self.update_mapping(&g2_reprs, &g1_points, &g2_points);
+    }
+
+    /// Folds all pairing equations accumulated in `other` into this batcher,
+    /// scaling their G1 side by `scalar`
+    pub fn merge(&mut self, other: Self, scalar: E::Scalar) {
+        for (g2_repr, g1) in other.g2_to_g1.into_iter() {
+            let g2 = other.g2_to_g2[&g2_repr];
+            self.update_mapping(&[g2_repr], &[g1 * scalar], &[g2]);
+        }
+    }
+
     fn update_mapping(&mut self, g2_reprs: &[Vec<u8>], g1_points: &[E::G1], g2_points: &[E::G2]) {
         assert_eq!(g1_points.len(), g2_reprs.len());
         assert_eq!(g2_points.len(), g2_reprs.len());
@@ -92,6 +166,25 @@ impl<E: MultiMillerLoop> PairingBatcher<E> {
             })
             .collect()
     }
+
+    /// Same as [`Self::finalize`], but takes prepared G2 points from `cache`
+    /// and stores newly prepared ones there
+    pub fn finalize_with_cache(
+        mut self,
+        cache: &mut G2PreparedCache<E>,
+    ) -> Vec<(E::G1Affine, E::G2Prepared)> {
+        if self.finalized {
+            panic!("Batcher is already consumed!");
+        }
+        self.finalized = true;
+        self.g2_to_g1
+            .iter()
+            .map(|(g2_repr, g1)| {
+                let g2_prepared = cache.get_or_prepare(g2_repr, &self.g2_to_g2[g2_repr]);
+                (g1.to_affine(), g2_prepared)
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -185,4 +278,92 @@ mod tests {
             assert_eq!(3, batched_tuples.len());
         }
     }
+
+    #[test]
+    fn test_add_pairing_with_scalar() {
+        let challenge = Fr::random(OsRng);
+        let weight = Fr::random(OsRng);
+        let a = G1::generator() * Fr::random(OsRng);
+        let c = G1::generator() * Fr::random(OsRng);
+        let b: G2Affine = (G2::generator() * Fr::random(OsRng)).to_affine();
+        let d: G2Affine = (G2::generator() * Fr::random(OsRng)).to_affine();
+        let (a_affine, c_affine): (G1Affine, G1Affine) = (a.into(), c.into());
+
+        let sorted_g1 = |g1s: Vec<G1Affine>| {
+            let mut g1s: Vec<_> = g1s
+                .iter()
+                .map(|g1| g1.to_bytes().as_ref().to_vec())
+                .collect();
+            g1s.sort();
+            g1s
+        };
+
+        // Weighting an equation is the same as weighting its G1 points by hand
+        let mut weighted = PairingBatcher::<Bn256>::new(challenge);
+        weighted.add_pairing_with_scalar(&[(a_affine, b), (c_affine, d)], weight);
+        let mut manual = PairingBatcher::<Bn256>::new(challenge);
+        manual.add_pairing(&[((a * weight).to_affine(), b), ((c * weight).to_affine(), d)]);
+        assert_eq!(
+            sorted_g1(weighted.finalize().into_iter().map(|(g1, _)| g1).collect()),
+            sorted_g1(manual.finalize().into_iter().map(|(g1, _)| g1).collect())
+        );
+
+        // The next equation still gets the first power of the challenge
+        let mut batcher = PairingBatcher::<Bn256>::new(challenge);
+        batcher.add_pairing(&[(a_affine, b)]);
+        batcher.add_pairing_with_scalar(&[(a_affine, b), (c_affine, d)], weight);
+        batcher.add_pairing(&[(c_affine, d)]);
+        let expected = vec![
+            (a + a * weight).to_affine(),
+            (c * weight + c * challenge).to_affine(),
+        ];
+        assert_eq!(
+            sorted_g1(batcher.finalize().into_iter().map(|(g1, _)| g1).collect()),
+            sorted_g1(expected)
+        );
+    }
+
+    #[test]
+    fn test_finalize_with_cache() {
+        let challenge = Fr::random(OsRng);
+        let b = Fr::random(OsRng);
+        let a: G1Affine = (G1::generator() * Fr::random(OsRng)).into();
+        let c: G1Affine = (G1::generator() * Fr::random(OsRng)).into();
+        let b: G2Affine = (G2::generator() * b).to_affine();
+        let g2: G2Affine = G2::generator().to_affine();
+
+        let batcher = || {
+            let mut pairing_batcher = PairingBatcher::<Bn256>::new(challenge);
+            pairing_batcher.add_pairing(&[(a, b), (c, g2)]);
+            pairing_batcher.add_pairing(&[(c, b)]);
+            pairing_batcher
+        };
+        let miller_loop = |tuples: &[(G1Affine, G2Prepared)]| {
+            Bn256::multi_miller_loop(&tuples.iter().map(|(g1, g2)| (g1, g2)).collect::<Vec<_>>())
+                .final_exponentiation()
+        };
+        let sorted_g1 = |tuples: &[(G1Affine, G2Prepared)]| {
+            let mut g1s: Vec<_> = tuples
+                .iter()
+                .map(|(g1, _)| g1.to_bytes().as_ref().to_vec())
+                .collect();
+            g1s.sort();
+            g1s
+        };
+
+        let expected = batcher().finalize();
+        let mut cache = G2PreparedCache::<Bn256>::new();
+
+        let first = batcher().finalize_with_cache(&mut cache);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(miller_loop(&first), miller_loop(&expected));
+        assert_eq!(sorted_g1(&first), sorted_g1(&expected));
+
+        let second = batcher().finalize_with_cache(&mut cache);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(miller_loop(&second), miller_loop(&expected));
+        assert_eq!(sorted_g1(&second), sorted_g1(&expected));
+    }
 }
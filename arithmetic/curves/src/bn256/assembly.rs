@@ -114,6 +114,29 @@ macro_rules! assembly_field {
                 $field([d0, d1, d2, d3])
             }
 
+            /// Parses a decimal or `0x`-prefixed hexadecimal string, returning
+            /// `None` if it is malformed or not smaller than the modulus.
+            pub fn from_str_vartime(s: &str) -> Option<Self> {
+                let limbs = $crate::arithmetic::parse_u256_vartime(s)?;
+                is_less_than(&limbs, &$modulus.0).then(|| Self::from_raw(limbs))
+            }
+
+            /// Returns the canonical little-endian bit decomposition, `NUM_BITS` long.
+            pub fn to_le_bits(&self) -> Vec<bool> {
+                let repr = <Self as ff::PrimeField>::to_repr(self);
+                (0..<Self as ff::PrimeField>::NUM_BITS as usize)
+                    .map(|i| (repr[i / 8] >> (i % 8)) & 1 == 1)
+                    .collect()
+            }
+
+            /// Recomposes little-endian bits into a field element, reducing modulo
+            /// the field order if the value does not fit.
+            pub fn from_le_bits(bits: &[bool]) -> Self {
+                bits.iter()
+                    .rev()
+                    .fold(Self::zero(), |acc, &bit| acc.double() + Self::from(bit))
+            }
+
             /// Attempts to convert a little-endian byte representation of
             /// a scalar into a `Fr`, failing if the input is not canonical.
             pub fn from_bytes(bytes: &[u8; 32]) -> CtOption<$field> {
@@ -125,6 +148,16 @@ macro_rules! assembly_field {
             pub fn to_bytes(&self) -> [u8; 32] {
                 <Self as ff::PrimeField>::to_repr(self)
             }
+
+            /// Returns the lowest 64 bits of the canonical representation, a
+            /// cheaper `get_lower_128` for values known to fit in a `u64`.
+            pub fn get_lower_64(&self) -> u64 {
+                let tmp = $field::montgomery_reduce(&[
+                    self.0[0], self.0[1], self.0[2], self.0[3], 0, 0, 0, 0,
+                ]);
+
+                tmp.0[0]
+            }
         }
 
         impl Group for $field {
@@ -264,6 +297,30 @@ macro_rules! assembly_field {
             }
         }
 
+        impl<T> core::iter::Sum<T> for $field
+        where
+            T: core::borrow::Borrow<$field>,
+        {
+            fn sum<I>(iter: I) -> Self
+            where
+                I: Iterator<Item = T>,
+            {
+                iter.fold(Self::zero(), |acc, item| acc + item.borrow())
+            }
+        }
+
+        impl<T> core::iter::Product<T> for $field
+        where
+            T: core::borrow::Borrow<$field>,
+        {
+            fn product<I>(iter: I) -> Self
+            where
+                I: Iterator<Item = T>,
+            {
+                iter.fold(Self::one(), |acc, item| acc * item.borrow())
+            }
+        }
+
         impl From<$field> for [u8; 32] {
             fn from(value: $field) -> [u8; 32] {
                 value.to_repr()
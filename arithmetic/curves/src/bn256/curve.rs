@@ -2,6 +2,7 @@ use crate::arithmetic::mul_512;
 use crate::bn256::Fq;
 use crate::bn256::Fq2;
 use crate::bn256::Fr;
+use crate::hash_to_curve::svdw_hash_to_curve;
 use crate::{Coordinates, CurveAffine, CurveAffineExt, CurveExt, Group};
 use core::cmp;
 use core::fmt::Debug;
@@ -32,6 +33,7 @@ new_curve_impl!(
     (G1_GENERATOR_X,G1_GENERATOR_Y),
     G1_B,
     "bn256_g1",
+    hash_to_curve: hash_to_curve_g1,
 );
 
 new_curve_impl!(
@@ -47,6 +49,10 @@ new_curve_impl!(
     "bn256_g2",
 );
 
+fn hash_to_curve_g1<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> G1 + 'a> {
+    svdw_hash_to_curve("bn256_g1", domain_prefix, SVDW_Z)
+}
+
 impl CurveAffineExt for G1Affine {
     batch_add!();
 
@@ -66,6 +72,8 @@ impl CurveAffineExt for G2Affine {
 const G1_GENERATOR_X: Fq = Fq::one();
 const G1_GENERATOR_Y: Fq = Fq::from_raw([2, 0, 0, 0]);
 const G1_B: Fq = Fq::from_raw([3, 0, 0, 0]);
+// First `z` in the order of RFC 9380, appendix H.1 that satisfies the SvdW criteria
+const SVDW_Z: Fq = Fq::one();
 const ENDO_G1: [u64; 4] = [
     0x7a7bd9d4391eb18du64,
     0x4ccef014a773d2cfu64,
@@ -249,7 +257,7 @@ impl CofactorGroup for G2 {
 mod tests {
 
     use crate::bn256::{
-        curve::{CurveEndo, ENDO_BETA},
+        curve::{CurveEndo, ENDO_BETA, SVDW_Z},
         Fr, G1Affine, G1, G2,
     };
     use ff::Field;
@@ -263,6 +271,51 @@ mod tests {
         crate::tests::curve::curve_tests::<G2>();
     }
 
+    #[test]
+    fn test_batch_sum() {
+        crate::tests::curve::batch_sum_tests::<G1>();
+        crate::tests::curve::batch_sum_tests::<G2>();
+    }
+
+    #[test]
+    fn test_hash_to_curve() {
+        use group::{cofactor::CofactorGroup, Group};
+
+        let message: &[u8] = b"hash to curve test vector";
+        let a = G1::hash_to_curve("dst-a")(message);
+        let b = G1::hash_to_curve("dst-b")(message);
+
+        for point in [a, b] {
+            assert!(bool::from(point.is_on_curve()));
+            assert!(bool::from(point.is_torsion_free()));
+            assert!(!bool::from(point.is_identity()));
+        }
+        assert_ne!(a, b);
+        assert_eq!(a, G1::hash_to_curve("dst-a")(message));
+        assert_ne!(a, G1::hash_to_curve("dst-a")(&b"another message"[..]));
+    }
+
+    #[test]
+    fn test_svdw_map_to_curve() {
+        use crate::hash_to_curve::{svdw_constants, svdw_map_to_curve};
+        use crate::{bn256::Fq, CurveAffine};
+        use ff::PrimeField;
+
+        let constants = svdw_constants::<G1>(SVDW_Z);
+        // Exceptional inputs of the map are sent to a point on the curve as well
+        let inputs = [Fq::zero(), Fq::one(), -Fq::one()]
+            .iter()
+            .copied()
+            .chain((0..100).map(|_| Fq::random(OsRng)));
+
+        for u in inputs {
+            let point = G1Affine::from(svdw_map_to_curve::<G1>(u, SVDW_Z, constants));
+            assert!(bool::from(point.is_on_curve()));
+            let y = *point.coordinates().unwrap().y();
+            assert_eq!(bool::from(u.is_odd()), bool::from(y.is_odd()));
+        }
+    }
+
     #[test]
     fn test_endo_consistency() {
         let g = G1::generator();
@@ -300,6 +353,54 @@ mod tests {
         crate::tests::curve::random_serialization_test::<G1>();
         crate::tests::curve::random_serialization_test::<G2>();
     }
+
+    #[test]
+    fn test_serialization_rejects_invalid_points() {
+        use super::G2_B;
+        use crate::bn256::{Fq, Fq2, G2Affine};
+        use crate::serde::SerdeObject;
+        use crate::CurveAffine;
+        use group::GroupEncoding;
+
+        // (1, 3) does not satisfy y^2 = x^3 + 3.
+        let off_curve = G1Affine {
+            x: Fq::one(),
+            y: Fq::from(3),
+        };
+        let bytes = off_curve.to_raw_bytes();
+        assert!(G1Affine::from_raw_bytes(&bytes).is_none());
+        assert!(G1Affine::read_raw(&mut &bytes[..]).is_err());
+        // The unchecked path performs no validation and accepts the point as is.
+        assert_eq!(G1Affine::from_raw_bytes_unchecked(&bytes), off_curve);
+        assert_eq!(G1Affine::read_raw_unchecked(&mut &bytes[..]), off_curve);
+
+        // Find a point on the twist which lies outside the prime order subgroup.
+        let outside_subgroup = (1u64..)
+            .find_map(|i| {
+                let x = Fq2 {
+                    c0: Fq::from(i),
+                    c1: Fq::zero(),
+                };
+                let y: Option<Fq2> = (x.square() * x + G2_B).sqrt().into();
+                y.map(|y| G2Affine { x, y })
+                    .filter(|p| !bool::from(p.is_torsion_free()))
+            })
+            .unwrap();
+        assert!(bool::from(outside_subgroup.is_on_curve()));
+        let bytes = outside_subgroup.to_raw_bytes();
+        assert!(G2Affine::from_raw_bytes(&bytes).is_none());
+        assert!(G2Affine::read_raw(&mut &bytes[..]).is_err());
+        assert_eq!(
+            G2Affine::read_raw_unchecked(&mut &bytes[..]),
+            outside_subgroup
+        );
+        assert!(bool::from(
+            G2Affine::from_bytes(&outside_subgroup.to_bytes()).is_none()
+        ));
+
+        let g = G2Affine::generator();
+        assert_eq!(G2Affine::read_raw(&mut &g.to_raw_bytes()[..]).unwrap(), g);
+    }
 }
 
 impl group::UncompressedEncoding for G1Affine {
@@ -335,6 +335,16 @@ mod test {
         crate::tests::field::random_field_tests::<Fr>("bn256 scalar".to_string());
     }
 
+    #[test]
+    fn test_sum_product() {
+        crate::tests::field::random_sum_product_tests::<Fr>("bn256 scalar".to_string());
+    }
+
+    #[test]
+    fn test_bigint_mul() {
+        crate::tests::field::random_bigint_mul_tests::<Fr>("bn256 scalar".to_string());
+    }
+
     #[test]
     fn test_delta() {
         assert_eq!(Fr::DELTA, GENERATOR.pow(&[1u64 << Fr::S, 0, 0, 0]));
@@ -366,6 +376,85 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_from_str_vartime() {
+        assert_eq!(Fr::from_str_vartime("0"), Some(Fr::zero()));
+        assert_eq!(Fr::from_str_vartime("12345"), Some(Fr::from(12345)));
+        assert_eq!(Fr::from_str_vartime("0x3039"), Some(Fr::from(12345)));
+
+        assert_eq!(
+            Fr::from_str_vartime(
+                "21888242871839275222246405745257275088548364400416034343698204186575808495616"
+            ),
+            Some(-Fr::one())
+        );
+        assert_eq!(
+            Fr::from_str_vartime(
+                "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000"
+            ),
+            Some(-Fr::one())
+        );
+
+        assert_eq!(Fr::from_str_vartime(MODULUS_STR), None);
+        assert_eq!(
+            Fr::from_str_vartime(
+                "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+            ),
+            None
+        );
+        assert_eq!(
+            Fr::from_str_vartime(
+                "0x10000000000000000000000000000000000000000000000000000000000000000"
+            ),
+            None
+        );
+        assert_eq!(Fr::from_str_vartime(""), None);
+        assert_eq!(Fr::from_str_vartime("0x"), None);
+        assert_eq!(Fr::from_str_vartime("12a"), None);
+    }
+
+    #[test]
+    fn test_le_bits() {
+        assert_eq!(Fr::zero().to_le_bits(), vec![false; Fr::NUM_BITS as usize]);
+        assert_eq!(Fr::from_le_bits(&[true, false, true]), Fr::from(5));
+
+        let minus_one = -Fr::one();
+        assert_eq!(Fr::from_le_bits(&minus_one.to_le_bits()), minus_one);
+
+        for _ in 0..1000 {
+            let a = Fr::random(OsRng);
+            let bits = a.to_le_bits();
+            assert_eq!(bits.len(), Fr::NUM_BITS as usize);
+            assert_eq!(Fr::from_le_bits(&bits), a);
+
+            let low = bits[..32]
+                .iter()
+                .rev()
+                .fold(0u32, |acc, &bit| (acc << 1) | bit as u32);
+            assert_eq!(low, a.get_lower_128() as u32);
+        }
+    }
+
+    #[test]
+    fn test_get_lower_64() {
+        assert_eq!(Fr::zero().get_lower_64(), 0);
+        assert_eq!(Fr::from(u64::MAX).get_lower_64(), u64::MAX);
+        let minus_one = -Fr::one();
+        assert_eq!(minus_one.get_lower_64(), minus_one.get_lower_128() as u64);
+
+        for _ in 0..1000 {
+            let word = OsRng.next_u32();
+            assert_eq!(Fr::from(word as u64).get_lower_64(), word as u64);
+
+            let small =
+                Fr::from_u128(((OsRng.next_u64() as u128) << 64) | OsRng.next_u64() as u128);
+            assert_eq!(small.get_lower_64(), small.get_lower_128() as u64);
+
+            let a = Fr::random(OsRng);
+            assert_eq!(a.get_lower_64(), a.get_lower_128() as u64);
+        }
+    }
+
     #[test]
     fn test_serialization() {
         crate::tests::field::random_serialization_test::<Fr>("fr".to_string());
@@ -142,6 +142,13 @@ macro_rules! batch_add {
 
 #[macro_export]
 macro_rules! new_curve_impl {
+    (@hash_to_curve $domain_prefix:ident) => {{
+        let _ = $domain_prefix;
+        unimplemented!()
+    }};
+    (@hash_to_curve $domain_prefix:ident, $hash_to_curve:path) => {
+        $hash_to_curve($domain_prefix)
+    };
     (($($privacy:tt)*),
     $name:ident,
     $name_affine:ident,
@@ -152,6 +159,7 @@ macro_rules! new_curve_impl {
     $generator:expr,
     $constant_b:expr,
     $curve_id:literal,
+    $(hash_to_curve: $hash_to_curve:path,)?
     ) => {
 
         #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
@@ -183,6 +191,12 @@ macro_rules! new_curve_impl {
             const fn curve_constant_b() -> $base {
                 $name_affine::curve_constant_b()
             }
+
+            /// Returns whether the point is on the curve and in the prime order subgroup.
+            pub fn is_valid(&self) -> Choice {
+                use $crate::group::cofactor::CofactorGroup;
+                self.is_on_curve() & self.is_torsion_free()
+            }
         }
 
         impl $name_affine {
@@ -197,6 +211,17 @@ macro_rules! new_curve_impl {
                 $constant_b
             }
 
+            /// Returns whether the point is in the prime order subgroup.
+            pub fn is_torsion_free(&self) -> Choice {
+                use $crate::group::cofactor::CofactorGroup;
+                $name::from(*self).is_torsion_free()
+            }
+
+            /// Returns whether the point is on the curve and in the prime order subgroup.
+            pub fn is_valid(&self) -> Choice {
+                self.is_on_curve() & self.is_torsion_free()
+            }
+
             pub fn random(mut rng: impl RngCore) -> Self {
                 loop {
                     let x = $base::random(&mut rng);
@@ -327,8 +352,8 @@ macro_rules! new_curve_impl {
             }
 
 
-            fn hash_to_curve<'a>(_: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
-                unimplemented!();
+            fn hash_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+                $crate::new_curve_impl!(@hash_to_curve domain_prefix $(, $hash_to_curve)?)
             }
 
             fn is_on_curve(&self) -> Choice {
@@ -471,7 +496,7 @@ macro_rules! new_curve_impl {
             }
 
             fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
-                $name_affine::from_bytes(bytes).map(Self::from)
+                $name_affine::from_bytes_unchecked(bytes).map(Self::from)
             }
 
             fn to_bytes(&self) -> Self::Repr {
@@ -494,8 +519,8 @@ macro_rules! new_curve_impl {
                     [0, 1, 2].map(|i| $base::from_raw_bytes(&bytes[i * $base::size()..(i + 1) * $base::size()]));
                 x.zip(y).zip(z).and_then(|((x, y), z)| {
                     let res = Self { x, y, z };
-                    // Check that the point is on the curve.
-                    bool::from(res.is_on_curve()).then(|| res)
+                    // Check that the point is on the curve and in the prime order subgroup.
+                    bool::from(res.is_valid()).then(|| res)
                 })
             }
             fn to_raw_bytes(&self) -> Vec<u8> {
@@ -511,7 +536,13 @@ macro_rules! new_curve_impl {
                 let x = $base::read_raw(reader)?;
                 let y = $base::read_raw(reader)?;
                 let z = $base::read_raw(reader)?;
-                Ok(Self { x, y, z })
+                let res = Self { x, y, z };
+                bool::from(res.is_valid()).then(|| res).ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "point is not on the curve or not in the prime order subgroup",
+                    )
+                })
             }
             fn write_raw<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
                 self.x.write_raw(writer)?;
@@ -601,6 +632,10 @@ macro_rules! new_curve_impl {
             type Repr = $name_compressed;
 
             fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+                Self::from_bytes_unchecked(bytes).and_then(|p| CtOption::new(p, p.is_torsion_free()))
+            }
+
+            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
                 let bytes = &bytes.0;
                 let mut tmp = *bytes;
                 let ysign = Choice::from(tmp[$compressed_size - 1] >> 7);
@@ -628,10 +663,6 @@ macro_rules! new_curve_impl {
                 })
             }
 
-            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
-                Self::from_bytes(bytes)
-            }
-
             fn to_bytes(&self) -> Self::Repr {
                 if bool::from(self.is_identity()) {
                     $name_compressed::default()
@@ -660,8 +691,8 @@ macro_rules! new_curve_impl {
                 let [x, y] = [0, $base::size()].map(|i| $base::from_raw_bytes(&bytes[i..i + $base::size()]));
                 x.zip(y).and_then(|(x, y)| {
                     let res = Self { x, y };
-                    // Check that the point is on the curve.
-                    bool::from(res.is_on_curve()).then(|| res)
+                    // Check that the point is on the curve and in the prime order subgroup.
+                    bool::from(res.is_valid()).then(|| res)
                 })
             }
             fn to_raw_bytes(&self) -> Vec<u8> {
@@ -676,7 +707,13 @@ macro_rules! new_curve_impl {
             fn read_raw<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
                 let x = $base::read_raw(reader)?;
                 let y = $base::read_raw(reader)?;
-                Ok(Self { x, y })
+                let res = Self { x, y };
+                bool::from(res.is_valid()).then(|| res).ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "point is not on the curve or not in the prime order subgroup",
+                    )
+                })
             }
             fn write_raw<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
                 self.x.write_raw(writer)?;
@@ -0,0 +1,143 @@
+//! Hashing to curves of the form `y^2 = x^3 + b` with the Shallue-van de Woestijne
+//! map of [RFC 9380, section 6.6.1](https://www.rfc-editor.org/rfc/rfc9380.html#section-6.6.1).
+//!
+//! The simplified SWU map requires `a != 0`, and no isogeny to such a curve is
+//! standardized for bn256, so SvdW is used instead. Field elements are derived with
+//! `expand_message_xmd` over BLAKE2b-512, as in `pasta_curves`.
+
+use pasta_curves::arithmetic::{CurveExt, FieldExt};
+
+/// Hashes `message` to two field elements with `expand_message_xmd` over BLAKE2b-512,
+/// using `<domain_prefix>-<curve_id>_XMD:BLAKE2b_SVDW_RO_` as domain separation tag
+pub(crate) fn hash_to_field<F: FieldExt>(
+    curve_id: &str,
+    domain_prefix: &str,
+    message: &[u8],
+    buf: &mut [F; 2],
+) {
+    assert!(domain_prefix.len() < 256);
+    assert!((22 + curve_id.len() + domain_prefix.len()) < 256);
+
+    // Field elements are at most 32 bytes, so 64 bytes per element leave a
+    // negligible bias after reduction
+    const CHUNKLEN: usize = 64;
+    const SUITE: &[u8] = b"_XMD:BLAKE2b_SVDW_RO_";
+    let dst_len = [(1 + SUITE.len() + curve_id.len() + domain_prefix.len()) as u8];
+
+    let personal = [0u8; 16];
+    let empty_hasher = blake2b_simd::Params::new()
+        .hash_length(CHUNKLEN)
+        .personal(&personal)
+        .to_state();
+
+    let b_0 = empty_hasher
+        .clone()
+        .update(&[0; 128])
+        .update(message)
+        .update(&[0, (CHUNKLEN * 2) as u8, 0])
+        .update(domain_prefix.as_bytes())
+        .update(b"-")
+        .update(curve_id.as_bytes())
+        .update(SUITE)
+        .update(&dst_len)
+        .finalize();
+
+    let b_1 = empty_hasher
+        .clone()
+        .update(b_0.as_array())
+        .update(&[1])
+        .update(domain_prefix.as_bytes())
+        .update(b"-")
+        .update(curve_id.as_bytes())
+        .update(SUITE)
+        .update(&dst_len)
+        .finalize();
+
+    let b_2 = {
+        let mut hasher = empty_hasher;
+        for (l, r) in b_0.as_array().iter().zip(b_1.as_array().iter()) {
+            hasher.update(&[*l ^ *r]);
+        }
+        hasher
+            .update(&[2])
+            .update(domain_prefix.as_bytes())
+            .update(b"-")
+            .update(curve_id.as_bytes())
+            .update(SUITE)
+            .update(&dst_len)
+            .finalize()
+    };
+
+    for (big, buf) in [b_1, b_2].iter().zip(buf.iter_mut()) {
+        let mut little = [0u8; CHUNKLEN];
+        little.copy_from_slice(big.as_array());
+        little.reverse();
+        *buf = F::from_bytes_wide(&little);
+    }
+}
+
+/// Constants `c1, c2, c3, c4` of the SvdW map for a given `z`
+pub(crate) fn svdw_constants<C: CurveExt>(z: C::Base) -> [C::Base; 4] {
+    let g = |x: C::Base| (x.square() + C::a()) * x + C::b();
+
+    let gz = g(z);
+    // 3 * z^2 + 4 * a
+    let tz = z.square() * C::Base::from(3u64) + C::a() * C::Base::from(4u64);
+
+    let c1 = gz;
+    let c2 = -z * C::Base::TWO_INV;
+    let c3 = (-gz * tz).sqrt().expect("z is a valid SvdW constant");
+    let c3 = C::Base::conditional_select(&c3, &-c3, c3.is_odd());
+    let c4 = -gz * C::Base::from(4u64) * tz.invert().unwrap();
+
+    [c1, c2, c3, c4]
+}
+
+/// Maps `u` to a point of `C`, following the straight-line description of the
+/// SvdW map in RFC 9380, appendix F.1
+pub(crate) fn svdw_map_to_curve<C: CurveExt>(
+    u: C::Base,
+    z: C::Base,
+    [c1, c2, c3, c4]: [C::Base; 4],
+) -> C {
+    let one = C::Base::one();
+    let g = |x: C::Base| (x.square() + C::a()) * x + C::b();
+
+    let tv1 = u.square() * c1;
+    let tv2 = one + tv1;
+    let tv1 = one - tv1;
+    let tv3 = (tv1 * tv2).invert().unwrap_or(C::Base::zero());
+    let tv4 = u * tv1 * tv3 * c3;
+
+    let x1 = c2 - tv4;
+    let e1 = g(x1).sqrt().is_some();
+    let x2 = c2 + tv4;
+    let e2 = g(x2).sqrt().is_some() & !e1;
+    let x3 = (tv2.square() * tv3).square() * c4 + z;
+
+    let x = C::Base::conditional_select(&x3, &x1, e1);
+    let x = C::Base::conditional_select(&x, &x2, e2);
+
+    // One of the three candidates is always on the curve
+    let y = g(x).sqrt().unwrap();
+    let y = C::Base::conditional_select(&-y, &y, !(u.is_odd() ^ y.is_odd()));
+
+    C::new_jacobian(x, y, one).unwrap()
+}
+
+/// `hash_to_curve` for prime order curves with `a = 0`, mapping two field elements
+/// with [`svdw_map_to_curve`] and adding the results
+pub(crate) fn svdw_hash_to_curve<'a, C: CurveExt>(
+    curve_id: &'static str,
+    domain_prefix: &'a str,
+    z: C::Base,
+) -> Box<dyn Fn(&[u8]) -> C + 'a> {
+    let constants = svdw_constants::<C>(z);
+
+    Box::new(move |message| {
+        let mut us = [C::Base::zero(); 2];
+        hash_to_field(curve_id, domain_prefix, message, &mut us);
+        let [q0, q1] = us.map(|u| svdw_map_to_curve::<C>(u, z, constants));
+        q0 + q1
+    })
+}
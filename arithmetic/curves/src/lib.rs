@@ -3,6 +3,7 @@
 #![feature(const_bigint_helper_methods)]
 
 mod arithmetic;
+mod hash_to_curve;
 
 pub mod batch_pairing;
 pub mod bn256;
@@ -276,6 +276,16 @@ mod test {
         crate::tests::field::random_field_tests::<Fp>("secp256k1 base".to_string());
     }
 
+    #[test]
+    fn test_sum_product() {
+        crate::tests::field::random_sum_product_tests::<Fp>("secp256k1 base".to_string());
+    }
+
+    #[test]
+    fn test_bigint_mul() {
+        crate::tests::field::random_bigint_mul_tests::<Fp>("secp256k1 base".to_string());
+    }
+
     #[test]
     fn test_serialization() {
         crate::tests::field::random_serialization_test::<Fp>("secp256k1 base".to_string());
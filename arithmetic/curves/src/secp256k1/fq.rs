@@ -319,6 +319,16 @@ mod test {
         crate::tests::field::random_field_tests::<Fq>("secp256k1 scalar".to_string());
     }
 
+    #[test]
+    fn test_sum_product() {
+        crate::tests::field::random_sum_product_tests::<Fq>("secp256k1 scalar".to_string());
+    }
+
+    #[test]
+    fn test_bigint_mul() {
+        crate::tests::field::random_bigint_mul_tests::<Fq>("secp256k1 scalar".to_string());
+    }
+
     #[test]
     fn test_serialization() {
         crate::tests::field::random_serialization_test::<Fq>("secp256k1 scalar".to_string());
@@ -1,5 +1,5 @@
 #![allow(clippy::eq_op)]
-use crate::{group::GroupEncoding, serde::SerdeObject};
+use crate::{group::GroupEncoding, serde::SerdeObject, CurveAffineExt};
 use ff::Field;
 use group::prime::PrimeCurveAffine;
 use pasta_curves::arithmetic::{CurveAffine, CurveExt};
@@ -285,3 +285,35 @@ fn multiplication<G: CurveExt>() {
         assert_eq!(t0, t1);
     }
 }
+
+pub fn batch_sum_tests<G: CurveExt>()
+where
+    G::AffineExt: CurveAffineExt,
+{
+    for len in [0, 1, 2, 3, 7, 64, 100] {
+        let points: Vec<G::AffineExt> = (0..len).map(|_| G::random(OsRng).into()).collect();
+        let expected = points.iter().fold(G::identity(), |acc, &p| acc + p);
+        assert_eq!(
+            G::AffineExt::batch_sum(&points),
+            G::AffineExt::from(expected)
+        );
+    }
+
+    // Doublings, cancellations and the identity take the complete formulas
+    let p: G::AffineExt = G::random(OsRng).into();
+    let q: G::AffineExt = G::random(OsRng).into();
+    let identity = G::AffineExt::identity();
+    for points in [
+        vec![p, p],
+        vec![p, -p],
+        vec![p, identity],
+        vec![identity, p, q, q],
+        vec![p, q, -q, p, identity, -p],
+    ] {
+        let expected = points.iter().fold(G::identity(), |acc, &p| acc + p);
+        assert_eq!(
+            G::AffineExt::batch_sum(&points),
+            G::AffineExt::from(expected)
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use ark_std::{end_timer, start_timer};
-use ff::Field;
+use ff::{Field, PrimeField};
+use num_bigint::BigUint;
 use rand::{RngCore, SeedableRng};
 use rand_xorshift::XorShiftRng;
 
@@ -229,3 +230,93 @@ pub fn random_serialization_test<F: Field + SerdeObject>(type_name: String) {
     }
     end_timer!(start);
 }
+
+pub fn random_sum_product_tests<F>(type_name: String)
+where
+    F: Field + core::iter::Sum + core::iter::Product,
+    for<'a> F: core::iter::Sum<&'a F> + core::iter::Product<&'a F>,
+{
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let message = format!("sum and product {}", type_name);
+    let start = start_timer!(|| message);
+    for _ in 0..1000 {
+        let elements: Vec<F> = (0..100).map(|_| F::random(&mut rng)).collect();
+
+        let sum = elements.iter().fold(F::zero(), |acc, e| acc + e);
+        assert_eq!(elements.iter().sum::<F>(), sum);
+        assert_eq!(elements.iter().copied().sum::<F>(), sum);
+
+        let product = elements.iter().fold(F::one(), |acc, e| acc * e);
+        assert_eq!(elements.iter().product::<F>(), product);
+        assert_eq!(elements.iter().copied().product::<F>(), product);
+    }
+    assert_eq!(core::iter::empty::<F>().sum::<F>(), F::zero());
+    assert_eq!(core::iter::empty::<F>().product::<F>(), F::one());
+    end_timer!(start);
+}
+
+/// Compares multiplication and squaring against big integer arithmetic modulo
+/// `F::MODULUS`, on random operands, operands just below the modulus and limb
+/// boundaries, where the carries of the Montgomery reduction are exercised.
+pub fn random_bigint_mul_tests<F: PrimeField>(type_name: String) {
+    let mut rng = XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let message = format!("bigint multiplication {}", type_name);
+    let start = start_timer!(|| message);
+
+    let modulus = BigUint::parse_bytes(F::MODULUS.trim_start_matches("0x").as_bytes(), 16).unwrap();
+    let to_bigint = |f: &F| BigUint::from_bytes_le(f.to_repr().as_ref());
+    let from_bigint = |n: &BigUint| {
+        let mut repr = F::Repr::default();
+        let bytes = n.to_bytes_le();
+        repr.as_mut()[..bytes.len()].copy_from_slice(&bytes);
+        F::from_repr(repr).unwrap()
+    };
+    let check = |a: &F, b: &F| {
+        let expected = (to_bigint(a) * to_bigint(b)) % &modulus;
+        assert_eq!(to_bigint(&(*a * b)), expected);
+        if a == b {
+            assert_eq!(to_bigint(&a.square()), expected);
+        }
+    };
+
+    let one = BigUint::from(1u64);
+    let mut edges: Vec<BigUint> = vec![
+        BigUint::from(0u64),
+        one.clone(),
+        BigUint::from(2u64),
+        &modulus - 1u64,
+        &modulus - 2u64,
+        (&modulus - 1u64) >> 1,
+        (&modulus + 1u64) >> 1,
+    ];
+    for limb in 1..4usize {
+        edges.push((&one << (64 * limb)) - 1u64);
+        edges.push(&one << (64 * limb));
+        edges.push(&modulus - (&one << (64 * limb)));
+    }
+    let edges: Vec<F> = edges.iter().map(from_bigint).collect();
+    for a in edges.iter() {
+        for b in edges.iter() {
+            check(a, b);
+        }
+    }
+
+    for _ in 0..100000 {
+        let a = F::random(&mut rng);
+        let b = F::random(&mut rng);
+        check(&a, &b);
+        check(&a, &a);
+
+        // Operands within 2^64 of the modulus
+        let near = from_bigint(&(&modulus - 1u64 - rng.next_u64()));
+        check(&near, &b);
+        check(&near, &near);
+    }
+    end_timer!(start);
+}
@@ -1,7 +1,9 @@
 #[macro_use]
 extern crate criterion;
 
-use crate::arithmetic::best_fft;
+use crate::arithmetic::{
+    best_fft, recursive_butterfly_arithmetic, recursive_butterfly_arithmetic_radix4,
+};
 use group::ff::Field;
 use halo2_proofs::*;
 use halo2curves::pasta::Fp;
@@ -20,6 +22,35 @@ fn criterion_benchmark(c: &mut Criterion) {
             });
         });
     }
+    group.finish();
+
+    // Compares the radix-2 kernel with the radix-4 one `best_fft` uses for large
+    // domains with the `fft-radix4` feature
+    let mut group = c.benchmark_group("fft_kernel");
+    for k in [10, 14, 18] {
+        let n = 1 << k;
+        let omega = Fp::random(OsRng);
+        let twiddles = (0..n / 2)
+            .scan(Fp::one(), |w, _| {
+                let tw = *w;
+                *w *= omega;
+                Some(tw)
+            })
+            .collect::<Vec<_>>();
+        group.bench_function(BenchmarkId::new("radix2", k), |b| {
+            let mut a = (0..n).map(|_| Fp::random(OsRng)).collect::<Vec<_>>();
+            b.iter(|| {
+                recursive_butterfly_arithmetic(&mut a, n, 1, &twiddles);
+            });
+        });
+        group.bench_function(BenchmarkId::new("radix4", k), |b| {
+            let mut a = (0..n).map(|_| Fp::random(OsRng)).collect::<Vec<_>>();
+            b.iter(|| {
+                recursive_butterfly_arithmetic_radix4(&mut a, n, 1, &twiddles);
+            });
+        });
+    }
+    group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -169,35 +169,13 @@ pub fn best_multiexp<C: CurveAffine>(coeffs: &[C::Scalar], bases: &[C]) -> C::Cu
 ///
 /// This will use multithreading if beneficial.
 pub fn best_fft<G: Group>(a: &mut [G], omega: G::Scalar, log_n: u32) {
-    fn bitreverse(mut n: usize, l: usize) -> usize {
-        let mut r = 0;
-        for _ in 0..l {
-            r = (r << 1) | (n & 1);
-            n >>= 1;
-        }
-        r
-    }
-
     let threads = multicore::current_num_threads();
     let log_threads = log2_floor(threads);
     let n = a.len() as usize;
     assert_eq!(n, 1 << log_n);
 
-    for k in 0..n {
-        let rk = bitreverse(k, log_n as usize);
-        if k < rk {
-            a.swap(rk, k);
-        }
-    }
-
-    // precompute twiddle factors
-    let twiddles: Vec<_> = (0..(n / 2) as usize)
-        .scan(G::Scalar::one(), |w, _| {
-            let tw = *w;
-            w.group_scale(&omega);
-            Some(tw)
-        })
-        .collect();
+    bitreverse_permutation(a, log_n);
+    let twiddles = fft_twiddles::<G>(omega, n);
 
     if log_n <= log_threads {
         let mut chunk = 2_usize;
@@ -228,11 +206,48 @@ pub fn best_fft<G: Group>(a: &mut [G], omega: G::Scalar, log_n: u32) {
             chunk *= 2;
             twiddle_chunk /= 2;
         }
+    } else if cfg!(feature = "fft-radix4") && log_n >= FFT_RADIX4_MIN_LOG_N {
+        recursive_butterfly_arithmetic_radix4(a, n, 1, &twiddles)
     } else {
         recursive_butterfly_arithmetic(a, n, 1, &twiddles)
     }
 }
 
+/// Smallest domain, as a power of two, for which [`best_fft`] switches to the
+/// radix-4 kernel once the transform is split across threads. The switch is only
+/// made with the `fft-radix4` feature, as the kernel saves no multiplications.
+const FFT_RADIX4_MIN_LOG_N: u32 = 10;
+
+/// Reorders `a` into bit-reversed index order, as expected by the butterfly kernels
+fn bitreverse_permutation<G>(a: &mut [G], log_n: u32) {
+    fn bitreverse(mut n: usize, l: usize) -> usize {
+        let mut r = 0;
+        for _ in 0..l {
+            r = (r << 1) | (n & 1);
+            n >>= 1;
+        }
+        r
+    }
+
+    for k in 0..a.len() {
+        let rk = bitreverse(k, log_n as usize);
+        if k < rk {
+            a.swap(rk, k);
+        }
+    }
+}
+
+/// Powers `omega^0, ..., omega^(n/2 - 1)` used as twiddle factors by the butterfly kernels
+fn fft_twiddles<G: Group>(omega: G::Scalar, n: usize) -> Vec<G::Scalar> {
+    (0..n / 2)
+        .scan(G::Scalar::one(), |w, _| {
+            let tw = *w;
+            w.group_scale(&omega);
+            Some(tw)
+        })
+        .collect()
+}
+
 /// This perform recursive butterfly arithmetic
 pub fn recursive_butterfly_arithmetic<G: Group>(
     a: &mut [G],
@@ -273,6 +288,69 @@ pub fn recursive_butterfly_arithmetic<G: Group>(
     }
 }
 
+/// Radix-4 variant of [`recursive_butterfly_arithmetic`]. Every level merges four
+/// sub-transforms at once, so the data is traversed half as many times; the number
+/// of multiplications by twiddle factors is the same.
+pub fn recursive_butterfly_arithmetic_radix4<G: Group>(
+    a: &mut [G],
+    n: usize,
+    twiddle_chunk: usize,
+    twiddles: &[G::Scalar],
+) {
+    if n == 2 {
+        let t = a[1];
+        a[1] = a[0];
+        a[0].group_add(&t);
+        a[1].group_sub(&t);
+        return;
+    }
+
+    // In bit-reversed order the quarters hold the transforms of the elements
+    // with indices 0, 2, 1 and 3 modulo 4
+    let m = n / 4;
+    let (left, right) = a.split_at_mut(n / 2);
+    let (a0, a1) = left.split_at_mut(m);
+    let (a2, a3) = right.split_at_mut(m);
+    if m > 1 {
+        let recurse =
+            |a: &mut [G]| recursive_butterfly_arithmetic_radix4(a, m, twiddle_chunk * 4, twiddles);
+        rayon::join(
+            || rayon::join(|| recurse(a0), || recurse(a1)),
+            || rayon::join(|| recurse(a2), || recurse(a3)),
+        );
+    }
+
+    for j in 0..m {
+        // Two radix-2 butterflies of size 2m ...
+        let (mut b, mut d) = (a1[j], a3[j]);
+        if j > 0 {
+            b.group_scale(&twiddles[2 * j * twiddle_chunk]);
+            d.group_scale(&twiddles[2 * j * twiddle_chunk]);
+        }
+        let (mut even_lo, mut even_hi) = (a0[j], a0[j]);
+        even_lo.group_add(&b);
+        even_hi.group_sub(&b);
+        let (mut odd_lo, mut odd_hi) = (a2[j], a2[j]);
+        odd_lo.group_add(&d);
+        odd_hi.group_sub(&d);
+
+        // ... merged by one of size 4m
+        if j > 0 {
+            odd_lo.group_scale(&twiddles[j * twiddle_chunk]);
+        }
+        odd_hi.group_scale(&twiddles[(j + m) * twiddle_chunk]);
+
+        a0[j] = even_lo;
+        a0[j].group_add(&odd_lo);
+        a2[j] = even_lo;
+        a2[j].group_sub(&odd_lo);
+        a1[j] = even_hi;
+        a1[j].group_add(&odd_hi);
+        a3[j] = even_hi;
+        a3[j].group_sub(&odd_hi);
+    }
+}
+
 /// Convert coefficient bases group elements to lagrange basis by inverse FFT.
 pub fn g_to_lagrange<C: CurveAffine>(g_projective: Vec<C::Curve>, k: u32) -> Vec<C> {
     let n_inv = C::Scalar::TWO_INV.pow_vartime(&[k as u64, 0, 0, 0]);
@@ -328,6 +406,49 @@ pub fn eval_polynomial<F: Field>(poly: &[F], point: F) -> F {
     }
 }
 
+/// Evaluates `poly` at each of `points` like [`eval_polynomial`], walking the
+/// coefficients once for all points.
+pub fn eval_polynomial_batch<F: Field>(poly: &[F], points: &[F]) -> Vec<F> {
+    fn evaluate<F: Field>(poly: &[F], points: &[F]) -> Vec<F> {
+        let mut evals = vec![F::zero(); points.len()];
+        for coeff in poly.iter().rev() {
+            for (eval, point) in evals.iter_mut().zip(points.iter()) {
+                *eval = *eval * point + coeff;
+            }
+        }
+        evals
+    }
+    let n = poly.len();
+    let num_threads = multicore::current_num_threads();
+    if n * 2 < num_threads {
+        evaluate(poly, points)
+    } else {
+        let chunk_size = (n + num_threads - 1) / num_threads;
+        let mut parts = vec![vec![]; num_threads];
+        multicore::scope(|scope| {
+            for (chunk_idx, (out, poly)) in
+                parts.iter_mut().zip(poly.chunks(chunk_size)).enumerate()
+            {
+                scope.spawn(move |_| {
+                    let start = [(chunk_idx * chunk_size) as u64, 0, 0, 0];
+                    *out = evaluate(poly, points)
+                        .into_iter()
+                        .zip(points.iter())
+                        .map(|(eval, point)| eval * point.pow_vartime(&start))
+                        .collect();
+                });
+            }
+        });
+        let mut evals = vec![F::zero(); points.len()];
+        for part in parts {
+            for (eval, part) in evals.iter_mut().zip(part) {
+                *eval += part;
+            }
+        }
+        evals
+    }
+}
+
 /// This computes the inner product of two vectors `a` and `b`.
 ///
 /// This function will panic if the two vectors are not the same size.
@@ -386,6 +507,34 @@ where
     q
 }
 
+/// Divides polynomial `a` in `X` by `X - b` for every `b` in `roots`, passing
+/// each quotient to `f` and collecting its results in the order of `roots`.
+///
+/// Every quotient is computed on its own by the same schoolbook division as
+/// [`kate_division`], without its sanity check, so the cost is O(n) per root.
+/// Roots are divided in parallel, and each quotient is dropped once `f` returns,
+/// so only one quotient per thread is held in memory.
+pub fn kate_division_per_root<F: FieldExt, T: Send>(
+    a: &[F],
+    roots: &[F],
+    f: impl Fn(&F, Vec<F>) -> T + Sync,
+) -> Vec<T> {
+    use rayon::prelude::*;
+
+    roots
+        .par_iter()
+        .map(|b| {
+            let mut q = vec![F::zero(); a.len() - 1];
+            let mut tmp = F::zero();
+            for (q, r) in q.iter_mut().rev().zip(a.iter().rev()) {
+                *q = *r + tmp;
+                tmp = *q * b;
+            }
+            f(b, q)
+        })
+        .collect()
+}
+
 /// This simple utility function will parallelize an operation that is to be
 /// performed over a mutable slice.
 pub fn parallelize<T: Send, F: Fn(&mut [T], usize) + Send + Sync + Clone>(v: &mut [T], f: F) {
@@ -526,3 +675,86 @@ fn test_lagrange_interpolate() {
         }
     }
 }
+
+#[test]
+fn test_eval_polynomial_batch() {
+    let rng = OsRng;
+
+    let points = (0..4).map(|_| Fp::random(rng)).collect::<Vec<_>>();
+    for degree in [0, 1, 7, 100] {
+        let poly = (0..degree).map(|_| Fp::random(rng)).collect::<Vec<_>>();
+        let expected: Vec<_> = points
+            .iter()
+            .map(|point| eval_polynomial(&poly, *point))
+            .collect();
+        assert_eq!(eval_polynomial_batch(&poly, &points), expected);
+    }
+}
+
+#[test]
+fn test_kate_division() {
+    let rng = OsRng;
+
+    for degree in 1..10 {
+        let poly = (0..=degree).map(|_| Fp::random(rng)).collect::<Vec<_>>();
+        let root = Fp::random(rng);
+        let quotient = kate_division(&poly, root);
+        assert_eq!(quotient.len(), degree);
+
+        // (p(X) - p(r)) = q(X) * (X - r) at a random point
+        let x = Fp::random(rng);
+        assert_eq!(
+            eval_polynomial(&poly, x) - eval_polynomial(&poly, root),
+            eval_polynomial(&quotient, x) * (x - root)
+        );
+    }
+}
+
+#[test]
+fn test_kate_division_per_root() {
+    let rng = OsRng;
+
+    let poly = (0..16).map(|_| Fp::random(rng)).collect::<Vec<_>>();
+    let roots = (0..8).map(|_| Fp::random(rng)).collect::<Vec<_>>();
+
+    let quotients = kate_division_per_root(&poly, &roots, |&root, quotient| (root, quotient));
+    assert_eq!(quotients.len(), roots.len());
+    for ((root, quotient), &expected_root) in quotients.into_iter().zip(roots.iter()) {
+        assert_eq!(root, expected_root);
+        assert_eq!(quotient, kate_division(&poly, root));
+    }
+}
+
+#[test]
+fn test_radix4_fft_matches_radix2() {
+    let rng = OsRng;
+
+    for log_n in 1..=12 {
+        let n = 1 << log_n;
+        let mut omega = Fp::ROOT_OF_UNITY;
+        for _ in log_n..Fp::S {
+            omega = omega.square();
+        }
+        let twiddles = fft_twiddles::<Fp>(omega, n);
+        let poly = (0..n).map(|_| Fp::random(rng)).collect::<Vec<_>>();
+
+        let mut radix2 = poly.clone();
+        bitreverse_permutation(&mut radix2, log_n);
+        recursive_butterfly_arithmetic(&mut radix2, n, 1, &twiddles);
+
+        let mut radix4 = poly.clone();
+        bitreverse_permutation(&mut radix4, log_n);
+        recursive_butterfly_arithmetic_radix4(&mut radix4, n, 1, &twiddles);
+
+        assert_eq!(radix2, radix4, "kernels disagree for n = 2^{}", log_n);
+
+        if log_n <= 4 {
+            for (i, eval) in radix4.iter().enumerate() {
+                assert_eq!(
+                    *eval,
+                    eval_polynomial(&poly, omega.pow_vartime(&[i as u64]))
+                );
+            }
+        }
+    }
+}
@@ -9,7 +9,8 @@ use crate::{
     arithmetic::FieldExt,
     plonk::{
         static_lookup::{StaticTable, StaticTableId},
-        Advice, Any, Assigned, Challenge, Column, Error, Fixed, Instance, Selector, TableColumn,
+        Advice, Any, Assigned, Challenge, Column, Error, Fixed, Instance, Phase, Selector,
+        TableColumn,
     },
 };
 
@@ -430,6 +431,27 @@ pub trait Layouter<F: Field> {
         N: Fn() -> NR,
         NR: Into<String>;
 
+    /// Assign a region of gates in the given `phase`.
+    ///
+    /// Synthesis is first advanced to `phase`, committing the advice columns of the
+    /// earlier phases so that their challenges can be queried within `assignment`.
+    /// Phases cannot be revisited, so regions should be assigned in phase order.
+    fn assign_region_in_phase<P, A, AR, N, NR>(
+        &mut self,
+        phase: P,
+        name: N,
+        assignment: A,
+    ) -> Result<AR, Error>
+    where
+        P: Phase,
+        A: FnOnce(Region<'_, F>) -> Result<AR, Error>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        self.get_root().enter_phase(phase);
+        self.assign_region(name, assignment)
+    }
+
     /// Assign a table region to an absolute row number.
     ///
     /// ```ignore
@@ -451,6 +473,24 @@ pub trait Layouter<F: Field> {
     /// absolute position.
     fn constrain_instance(&mut self, cell: Cell, column: Column<Instance>, row: usize);
 
+    /// Assigns an instance column's row value at an absolute position.
+    ///
+    /// This is only possible during [`FirstPhase`] synthesis, before the instances
+    /// are absorbed into the transcript. The row must be within the instance values
+    /// passed to the prover, and the assigned value is usually tied to the circuit
+    /// with [`Layouter::constrain_instance`]. Layouters that do not track instance
+    /// values ignore this.
+    ///
+    /// [`FirstPhase`]: crate::plonk::FirstPhase
+    fn assign_instance(
+        &mut self,
+        _column: Column<Instance>,
+        _row: usize,
+        _value: Value<F>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Queries the value of the given challenge.
     ///
     /// Returns `Value::unknown()` if the current synthesis phase is before the challenge can be queried.
@@ -474,6 +514,11 @@ pub trait Layouter<F: Field> {
     /// Not intended for downstream consumption; use [`Layouter::namespace`] instead.
     fn pop_namespace(&mut self, gadget_name: Option<String>);
 
+    /// Advances synthesis to `phase`.
+    ///
+    /// Not intended for downstream consumption; use [`Layouter::assign_region_in_phase`] instead.
+    fn enter_phase<P: Phase>(&mut self, _phase: P) {}
+
     /// Enters into a namespace.
     fn namespace<NR, N>(&mut self, name_fn: N) -> NamespacedLayouter<'_, Self::E, F, Self::Root>
     where
@@ -528,6 +573,15 @@ impl<'a, E: MultiMillerLoop<Scalar = F>, F: Field, L: Layouter<F, E = E> + 'a> L
         self.0.constrain_instance(cell, column, row);
     }
 
+    fn assign_instance(
+        &mut self,
+        column: Column<Instance>,
+        row: usize,
+        value: Value<F>,
+    ) -> Result<(), Error> {
+        self.0.assign_instance(column, row, value)
+    }
+
     fn get_challenge(&self, challenge: Challenge) -> Value<F> {
         self.0.get_challenge(challenge)
     }
@@ -547,6 +601,10 @@ impl<'a, E: MultiMillerLoop<Scalar = F>, F: Field, L: Layouter<F, E = E> + 'a> L
     fn pop_namespace(&mut self, _gadget_name: Option<String>) {
         panic!("Only the root's pop_namespace should be called");
     }
+
+    fn enter_phase<P: Phase>(&mut self, phase: P) {
+        self.0.enter_phase(phase);
+    }
 }
 
 impl<'a, E: MultiMillerLoop<Scalar = F>, F: Field, L: Layouter<F, E = E> + 'a> Drop
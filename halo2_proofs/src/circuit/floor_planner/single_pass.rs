@@ -14,7 +14,7 @@ use crate::{
     },
     plonk::{
         Advice, Any, Assigned, Assignment, Challenge, Circuit, Column, Error, Fixed, FloorPlanner,
-        Instance, Selector, TableColumn,
+        Instance, Phase, Selector, TableColumn,
     },
 };
 
@@ -233,6 +233,15 @@ impl<'a, E: MultiMillerLoop<Scalar = F>, F: Field, CS: Assignment<F, E = E> + 'a
         );
     }
 
+    fn assign_instance(
+        &mut self,
+        instance: Column<Instance>,
+        row: usize,
+        value: Value<F>,
+    ) -> Result<(), Error> {
+        self.cs.assign_instance(instance, row, value)
+    }
+
     fn get_challenge(&self, challenge: Challenge) -> Value<F> {
         self.cs.get_challenge(challenge)
     }
@@ -252,6 +261,10 @@ impl<'a, E: MultiMillerLoop<Scalar = F>, F: Field, CS: Assignment<F, E = E> + 'a
     fn pop_namespace(&mut self, gadget_name: Option<String>) {
         self.cs.pop_namespace(gadget_name)
     }
+
+    fn enter_phase<P: Phase>(&mut self, phase: P) {
+        self.cs.enter_phase(phase)
+    }
 }
 
 struct SingleChipLayouterRegion<
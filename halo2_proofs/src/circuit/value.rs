@@ -104,6 +104,17 @@ impl<V> Value<V> {
         }
     }
 
+    /// Maps a `Value<V>` to `Value<W>` by applying a fallible function to the contained
+    /// value, returning its error if it fails.
+    ///
+    /// The function is not called if `self` is [`Value::unknown()`], so the error is only
+    /// surfaced when witnesses are known.
+    pub fn try_map<W, F: FnOnce(V) -> Result<W, Error>>(self, f: F) -> Result<Value<W>, Error> {
+        Ok(Value {
+            inner: self.inner.map(f).transpose()?,
+        })
+    }
+
     /// Zips `self` with another `Value`.
     ///
     /// If `self` is `Value::known(s)` and `other` is `Value::known(o)`, this method
@@ -696,3 +707,75 @@ impl<F: Field> Value<Assigned<F>> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use halo2curves::bn256::{Bn256, Fr as Fp};
+
+    use super::Value;
+    use crate::{
+        circuit::{Layouter, SimpleFloorPlanner},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error},
+    };
+
+    #[test]
+    fn try_map_error_aborts_synthesis() {
+        const K: u32 = 4;
+
+        struct LimbCircuit {
+            word: Value<u64>,
+        }
+
+        impl Circuit<Bn256> for LimbCircuit {
+            type Config = Column<Advice>;
+            type FloorPlanner = SimpleFloorPlanner<Bn256>;
+
+            fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+                meta.advice_column()
+            }
+
+            fn without_witnesses(&self) -> Self {
+                Self {
+                    word: Value::unknown(),
+                }
+            }
+
+            fn synthesize(
+                &self,
+                config: Self::Config,
+                mut layouter: impl Layouter<Fp>,
+            ) -> Result<(), Error> {
+                layouter.assign_region(
+                    || "limb",
+                    |mut region| {
+                        // The word must fit in a single 8-bit limb
+                        let limb = self.word.try_map(|word| {
+                            if word < 256 {
+                                Ok(Fp::from(word))
+                            } else {
+                                Err(Error::Synthesis)
+                            }
+                        })?;
+                        region.assign_advice(config, 0, limb)?;
+
+                        Ok(())
+                    },
+                )
+            }
+        }
+
+        let in_range = LimbCircuit {
+            word: Value::known(255),
+        };
+        assert!(MockProver::run(K, &in_range, vec![]).is_ok());
+
+        let over_range = LimbCircuit {
+            word: Value::known(256),
+        };
+        assert!(matches!(
+            MockProver::run(K, &over_range, vec![]),
+            Err(Error::Synthesis)
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 //! Tools for developing circuits.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
@@ -17,7 +18,7 @@ use crate::{
     circuit,
     plonk::{
         permutation,
-        static_lookup::{StaticTable, StaticTableId},
+        static_lookup::{StaticTable, StaticTableId, StaticTableValues},
         Advice, Any, Assigned, Assignment, Challenge, Circuit, Column, ColumnType,
         ConstraintSystem, Error, Expression, Fixed, FloorPlanner, Instance, Phase, Selector,
         VirtualCell,
@@ -44,6 +45,9 @@ pub use failure::{FailureLocation, VerifyFailure};
 mod gates;
 pub use gates::CircuitGates;
 
+mod counting;
+pub use counting::{AssignmentCounts, CountingAssignment};
+
 #[cfg(feature = "dev-graph")]
 mod graph;
 
@@ -315,6 +319,11 @@ pub struct MockProver<F: Group + Field, E: MultiMillerLoop<Scalar = F>> {
 
     permutation: permutation::keygen::Assembly,
 
+    // The static tables registered during synthesis, with their opened values.
+    static_tables: BTreeMap<StaticTableId<String>, StaticTableValues<E>>,
+    // The first id registered again with a different table, reported by `run`
+    conflicting_static_table: Option<StaticTableId<String>>,
+
     // A range of available rows for assignment and copies.
     usable_rows: Range<usize>,
     _marker: PhantomData<E>,
@@ -344,11 +353,19 @@ impl<F: Field + Group, E: MultiMillerLoop<Scalar = F>> Assignment<F> for MockPro
 
     fn register_static_table(
         &mut self,
-        _id: StaticTableId<String>,
-        _static_table: StaticTable<Self::E>,
+        id: StaticTableId<String>,
+        static_table: StaticTable<Self::E>,
     ) {
-        // if ctx = prover then check that prover part is some and take it else panic
-        // if ctx = verifier then check that verifier part is some and take it else panic
+        // Tables registered without their opened values (as a verifier would) cannot be
+        // checked, so static lookups into them are reported as unsatisfied.
+        if let Some(opened) = static_table.opened {
+            if let Some(registered) = self.static_tables.get(&id) {
+                if *registered != opened && self.conflicting_static_table.is_none() {
+                    self.conflicting_static_table = Some(id.clone());
+                }
+            }
+            self.static_tables.insert(id, opened);
+        }
     }
 
     fn enable_selector<A, AR>(&mut self, _: A, selector: &Selector, row: usize) -> Result<(), Error>
@@ -391,6 +408,26 @@ impl<F: Field + Group, E: MultiMillerLoop<Scalar = F>> Assignment<F> for MockPro
             .ok_or(Error::BoundsFailure)
     }
 
+    fn assign_instance(
+        &mut self,
+        column: Column<Instance>,
+        row: usize,
+        to: circuit::Value<F>,
+    ) -> Result<(), Error> {
+        if !self.usable_rows.contains(&row) {
+            return Err(Error::not_enough_rows_available(self.k));
+        }
+
+        let instance_get_mut = self
+            .instance
+            .get_mut(column.index())
+            .and_then(|v| v.get_mut(row))
+            .ok_or(Error::BoundsFailure)?;
+        *instance_get_mut = to.assign()?;
+
+        Ok(())
+    }
+
     fn assign_advice<'r, 'v>(
         //<V, VR, A, AR>(
         &'r mut self,
@@ -577,12 +614,18 @@ impl<F: FieldExt, E: MultiMillerLoop<Scalar = F> + std::marker::Sync> MockProver
             selectors,
             challenges,
             permutation,
+            static_tables: BTreeMap::new(),
+            conflicting_static_table: None,
             usable_rows: 0..usable_rows,
             _marker: PhantomData,
         };
 
         ConcreteCircuit::FloorPlanner::synthesize(&mut prover, circuit, config, constants)?;
 
+        if let Some(id) = prover.conflicting_static_table.take() {
+            return Err(Error::ConflictingStaticTable(id));
+        }
+
         let (cs, selector_polys) = prover.cs.compress_selectors(prover.selectors.clone());
         prover.cs = cs;
         prover.fixed.extend(selector_polys.into_iter().map(|poly| {
@@ -905,6 +948,9 @@ impl<F: FieldExt, E: MultiMillerLoop<Scalar = F> + std::marker::Sync> MockProver
                         .collect::<Vec<_>>()
                 });
 
+        // Check that all static lookup inputs exist in their tables at a common index.
+        let static_lookup_errors = self.static_lookup_errors(advice, lookup_input_row_ids.clone());
+
         // Check that permutations preserve the original values of the cells.
         let perm_errors = {
             // Original values of columns involved in the permutation.
@@ -954,6 +1000,7 @@ impl<F: FieldExt, E: MultiMillerLoop<Scalar = F> + std::marker::Sync> MockProver
             //.chain(selector_errors)
             .chain(gate_errors)
             .chain(lookup_errors)
+            .chain(static_lookup_errors)
             .chain(perm_errors)
             .collect();
         if errors.is_empty() {
@@ -1280,6 +1327,10 @@ impl<F: FieldExt, E: MultiMillerLoop<Scalar = F> + std::marker::Sync> MockProver
                         .collect::<Vec<_>>()
                 });
 
+        // Check that all static lookup inputs exist in their tables at a common index.
+        let static_lookup_errors =
+            self.static_lookup_errors(advice, lookup_input_row_ids.iter().copied());
+
         // Check that permutations preserve the original values of the cells.
         let perm_errors = {
             // Original values of columns involved in the permutation.
@@ -1333,6 +1384,7 @@ impl<F: FieldExt, E: MultiMillerLoop<Scalar = F> + std::marker::Sync> MockProver
             .chain(selector_errors)
             .chain(gate_errors)
             .chain(lookup_errors)
+            .chain(static_lookup_errors)
             .chain(perm_errors)
             .collect();
         if errors.is_empty() {
@@ -1352,6 +1404,97 @@ impl<F: FieldExt, E: MultiMillerLoop<Scalar = F> + std::marker::Sync> MockProver
         }
     }
 
+    /// Checks the static lookups at `lookup_input_row_ids` against the registered
+    /// static tables, mirroring the cq prover: every input must be present in its
+    /// table, and all inputs of a lookup must sit at the same table index.
+    fn static_lookup_errors(
+        &self,
+        advice: &[Vec<CellValue<F>>],
+        lookup_input_row_ids: impl Iterator<Item = usize> + Clone,
+    ) -> Vec<VerifyFailure> {
+        let n = self.n as i32;
+
+        let load = |expression: &Expression<F>, row: usize| {
+            expression.evaluate_lazy(
+                &|scalar| Value::Real(scalar),
+                &|_| panic!("virtual selectors are removed during optimization"),
+                &|query| {
+                    let query = self.cs.fixed_queries[query.index];
+                    let column_index = query.0.index();
+                    let rotation = query.1 .0;
+                    self.fixed[column_index][(row as i32 + n + rotation) as usize % n as usize]
+                        .into()
+                },
+                &|query| {
+                    let query = self.cs.advice_queries[query.index];
+                    let column_index = query.0.index();
+                    let rotation = query.1 .0;
+                    advice[column_index][(row as i32 + n + rotation) as usize % n as usize].into()
+                },
+                &|query| {
+                    let query = self.cs.instance_queries[query.index];
+                    let column_index = query.0.index();
+                    let rotation = query.1 .0;
+                    Value::Real(
+                        self.instance[column_index]
+                            [(row as i32 + n + rotation) as usize % n as usize],
+                    )
+                },
+                &|challenge| Value::Real(self.challenges[challenge.index()]),
+                &|a| -a,
+                &|a, b| a + b,
+                &|a, b| a * b,
+                &|a, scalar| a * scalar,
+                &Value::Real(F::zero()),
+            )
+        };
+
+        self.cs
+            .static_lookups
+            .iter()
+            .enumerate()
+            .flat_map(|(lookup_index, lookup)| {
+                let tables: Option<Vec<_>> = lookup
+                    .table_ids()
+                    .iter()
+                    .map(|id| self.static_tables.get(id))
+                    .collect();
+
+                lookup_input_row_ids
+                    .clone()
+                    .filter_map(move |row| {
+                        let satisfied =
+                            tables.as_ref().map_or(false, |tables| {
+                                let mut indices = lookup.input().iter().zip(tables.iter()).map(
+                                    |(input, table)| match load(input, row) {
+                                        Value::Real(value) => table.index_of(&value),
+                                        Value::Poison => None,
+                                    },
+                                );
+                                let first = indices.next().flatten();
+                                first.is_some() && indices.all(|index| index == first)
+                            });
+
+                        if satisfied {
+                            None
+                        } else {
+                            Some(VerifyFailure::StaticLookup {
+                                name: lookup.name(),
+                                lookup_index,
+                                location: FailureLocation::find_expressions(
+                                    &self.cs,
+                                    &self.regions,
+                                    row,
+                                    lookup.input().iter(),
+                                ),
+                            })
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
     /// Panics if the circuit being checked by this `MockProver` is not satisfied.
     ///
     /// Any verification failures will be pretty-printed to stderr before the function
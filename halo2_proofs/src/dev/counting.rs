@@ -0,0 +1,196 @@
+//! An [`Assignment`] wrapper that tallies the calls a circuit makes during synthesis.
+
+use std::collections::BTreeMap;
+
+use ff::Field;
+
+use crate::{
+    circuit::Value,
+    plonk::{
+        static_lookup::{StaticTable, StaticTableId},
+        Advice, Any, Assigned, Assignment, Challenge, Column, Error, Fixed, Instance, Phase,
+        Selector,
+    },
+};
+
+/// Number of assignment calls recorded within a single namespace.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AssignmentCounts {
+    /// Calls to `assign_advice`.
+    pub advice: usize,
+    /// Calls to `assign_fixed`.
+    pub fixed: usize,
+    /// Calls to `copy`.
+    pub copies: usize,
+    /// Calls to `enable_selector`.
+    pub selectors: usize,
+}
+
+impl AssignmentCounts {
+    fn add(&mut self, other: &AssignmentCounts) {
+        self.advice += other.advice;
+        self.fixed += other.fixed;
+        self.copies += other.copies;
+        self.selectors += other.selectors;
+    }
+}
+
+/// Wraps an [`Assignment`] and counts the advice and fixed assignments, copies
+/// and enabled selectors it receives, grouped by namespace.
+///
+/// Namespaces are keyed by their full path joined with `/`; calls made outside
+/// any namespace are recorded under the empty string.
+#[derive(Debug)]
+pub struct CountingAssignment<A> {
+    inner: A,
+    namespace: Vec<String>,
+    counts: BTreeMap<String, AssignmentCounts>,
+}
+
+impl<A> CountingAssignment<A> {
+    /// Wraps `inner`, starting with no recorded calls.
+    pub fn new(inner: A) -> Self {
+        Self {
+            inner,
+            namespace: vec![],
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the counts recorded per namespace path.
+    pub fn counts(&self) -> &BTreeMap<String, AssignmentCounts> {
+        &self.counts
+    }
+
+    /// Returns the counts summed over every namespace.
+    pub fn total(&self) -> AssignmentCounts {
+        let mut total = AssignmentCounts::default();
+        for counts in self.counts.values() {
+            total.add(counts);
+        }
+        total
+    }
+
+    /// Returns the wrapped assignment.
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    fn current(&mut self) -> &mut AssignmentCounts {
+        self.counts.entry(self.namespace.join("/")).or_default()
+    }
+}
+
+impl<F: Field, A: Assignment<F>> Assignment<F> for CountingAssignment<A> {
+    type E = A::E;
+
+    fn enter_region<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.inner.enter_region(name_fn)
+    }
+
+    fn exit_region(&mut self) {
+        self.inner.exit_region()
+    }
+
+    fn register_static_table(
+        &mut self,
+        id: StaticTableId<String>,
+        static_table: StaticTable<Self::E>,
+    ) {
+        self.inner.register_static_table(id, static_table)
+    }
+
+    fn enable_selector<AN, AR>(
+        &mut self,
+        annotation: AN,
+        selector: &Selector,
+        row: usize,
+    ) -> Result<(), Error>
+    where
+        AN: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.current().selectors += 1;
+        self.inner.enable_selector(annotation, selector, row)
+    }
+
+    fn query_instance(&self, column: Column<Instance>, row: usize) -> Result<Value<F>, Error> {
+        self.inner.query_instance(column, row)
+    }
+
+    fn assign_instance(
+        &mut self,
+        column: Column<Instance>,
+        row: usize,
+        to: Value<F>,
+    ) -> Result<(), Error> {
+        self.inner.assign_instance(column, row, to)
+    }
+
+    fn assign_advice<'r, 'v>(
+        &'r mut self,
+        column: Column<Advice>,
+        row: usize,
+        to: Value<Assigned<F>>,
+    ) -> Result<Value<&'v Assigned<F>>, Error> {
+        self.current().advice += 1;
+        self.inner.assign_advice(column, row, to)
+    }
+
+    fn assign_fixed(&mut self, column: Column<Fixed>, row: usize, to: Assigned<F>) {
+        self.current().fixed += 1;
+        self.inner.assign_fixed(column, row, to)
+    }
+
+    fn copy(
+        &mut self,
+        left_column: Column<Any>,
+        left_row: usize,
+        right_column: Column<Any>,
+        right_row: usize,
+    ) {
+        self.current().copies += 1;
+        self.inner
+            .copy(left_column, left_row, right_column, right_row)
+    }
+
+    fn fill_from_row(
+        &mut self,
+        column: Column<Fixed>,
+        row: usize,
+        to: Value<Assigned<F>>,
+    ) -> Result<(), Error> {
+        self.inner.fill_from_row(column, row, to)
+    }
+
+    fn get_challenge(&self, challenge: Challenge) -> Value<F> {
+        self.inner.get_challenge(challenge)
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        let name: String = name_fn().into();
+        self.namespace.push(name.clone());
+        self.inner.push_namespace(|| name)
+    }
+
+    fn pop_namespace(&mut self, gadget_name: Option<String>) {
+        self.namespace.pop();
+        self.inner.pop_namespace(gadget_name)
+    }
+
+    fn next_phase(&mut self) {
+        self.inner.next_phase()
+    }
+
+    fn enter_phase<P: Phase>(&mut self, phase: P) {
+        self.inner.enter_phase(phase)
+    }
+}
@@ -172,6 +172,18 @@ pub enum VerifyFailure {
         ///   lookup is active on a row adjacent to an unrelated region.
         location: FailureLocation,
     },
+    /// A static lookup input did not exist in its table, or the inputs of a
+    /// multi-table static lookup were found at different table indices.
+    StaticLookup {
+        /// The name of the static lookup that is not satisfied.
+        name: &'static str,
+        /// The index of the static lookup that is not satisfied. These indices are
+        /// assigned in the order in which `ConstraintSystem::lookup_static` is called
+        /// during `Circuit::configure`.
+        lookup_index: usize,
+        /// The location at which the static lookup is not satisfied.
+        location: FailureLocation,
+    },
     /// A permutation did not preserve the original value of a cell.
     Permutation {
         /// The column in which this permutation is not satisfied.
@@ -226,6 +238,17 @@ impl fmt::Display for VerifyFailure {
                     name, lookup_index, location
                 )
             }
+            Self::StaticLookup {
+                name,
+                lookup_index,
+                location,
+            } => {
+                write!(
+                    f,
+                    "Static lookup {}(index: {}) is not satisfied {}",
+                    name, lookup_index, location
+                )
+            }
             Self::Permutation { column, location } => {
                 write!(
                     f,
@@ -3,6 +3,8 @@ use ff::PrimeField;
 use halo2curves::{pairing::Engine, serde::SerdeObject, CurveAffine};
 use std::io;
 
+pub(crate) mod json;
+
 /// This enum specifies how various types are serialized and deserialized.
 #[derive(Clone, Copy, Debug)]
 pub enum SerdeFormat {
@@ -12,11 +14,20 @@ pub enum SerdeFormat {
     Processed,
     /// Curve elements are serialized in uncompressed form. Field elements are serialized
     /// in their internal Montgomery representation.
-    /// When deserializing, checks are performed to ensure curve elements indeed lie on the curve and field elements
-    /// are less than modulus.
+    /// When deserializing, checks are performed to ensure curve elements indeed lie on the curve and in
+    /// the prime order subgroup, and field elements are less than modulus.
     RawBytes,
-    /// Serialization is the same as `RawBytes`, but no checks are performed.
+    /// Serialization is the same as `RawBytes`, but no checks are performed, so off-curve or
+    /// out-of-subgroup points are accepted as is.
     RawBytesUnchecked,
+    /// Human-readable JSON for debugging and interchange of verifying keys and parameters.
+    /// Curve elements are `0x`-prefixed hex strings of their compressed form, field elements
+    /// are decimal strings. Proving keys cannot be serialized in this format.
+    ///
+    /// Only documents as written by this crate can be read back: object keys must appear in
+    /// the order they are written in, and strings cannot contain escapes. This is not a
+    /// general JSON parser.
+    Json,
 }
 
 // Keep this trait for compatibility with IPA serialization
@@ -39,19 +50,25 @@ pub trait SerdeCurveAffine: CurveAffine + SerdeObject {
     /// Checks that field elements are less than modulus, and then checks that the point is on the curve.
     /// - `RawBytesUnchecked`: Reads an uncompressed curve element with coordinates in Montgomery form;
     /// does not perform any checks
-    fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> Self {
+    /// - `Json`: Reads a hex string of a compressed curve element and decompresses it.
+    fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> io::Result<Self> {
         match format {
-            SerdeFormat::Processed => <Self as CurveRead>::read(reader).unwrap(),
-            SerdeFormat::RawBytes => <Self as SerdeObject>::read_raw(reader).unwrap(),
-            SerdeFormat::RawBytesUnchecked => <Self as SerdeObject>::read_raw_unchecked(reader),
+            SerdeFormat::Processed => <Self as CurveRead>::read(reader),
+            SerdeFormat::RawBytes => <Self as SerdeObject>::read_raw(reader),
+            SerdeFormat::RawBytesUnchecked => Ok(<Self as SerdeObject>::read_raw_unchecked(reader)),
+            SerdeFormat::Json => point_from_hex(&json::read_string(reader)?),
         }
     }
     /// Writes a curve element according to `format`:
     /// - `Processed`: Writes a compressed curve element
+    /// - `Json`: Writes a compressed curve element as a hex string
     /// - Otherwise: Writes an uncompressed curve element with coordinates in Montgomery form
     fn write<W: io::Write>(&self, writer: &mut W, format: SerdeFormat) {
         match format {
             SerdeFormat::Processed => writer.write_all(self.to_bytes().as_ref()).unwrap(),
+            SerdeFormat::Json => {
+                json::write_string(writer, &json::to_hex(self.to_bytes().as_ref())).unwrap()
+            }
             _ => self.write_raw(writer).unwrap(),
         }
     }
@@ -65,16 +82,41 @@ pub trait SerdePrimeField: PrimeField + SerdeObject {
     /// - `RawBytes`: Reads a field element from raw bytes in its internal Montgomery representations,
     /// and checks that the element is less than the modulus.
     /// - `RawBytesUnchecked`: Reads a field element in Montgomery form and performs no checks.
-    fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> Self {
+    fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> io::Result<Self> {
         match format {
             SerdeFormat::Processed => {
                 let mut compressed = Self::Repr::default();
-                reader.read_exact(compressed.as_mut()).unwrap();
-                Option::from(Self::from_repr(compressed))
-                    .unwrap_or_else(|| panic!("Invalid prime field point encoding"))
+                reader.read_exact(compressed.as_mut())?;
+                Option::from(Self::from_repr(compressed)).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Invalid prime field point encoding",
+                    )
+                })
+            }
+            SerdeFormat::RawBytes => <Self as SerdeObject>::read_raw(reader),
+            SerdeFormat::RawBytesUnchecked => Ok(<Self as SerdeObject>::read_raw_unchecked(reader)),
+            SerdeFormat::Json => {
+                let decimal = json::read_string(reader)?;
+                if decimal.is_empty() || !decimal.bytes().all(|digit| digit.is_ascii_digit()) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Invalid decimal field element",
+                    ));
+                }
+                let ten = Self::from(10);
+                let value = decimal.bytes().fold(Self::zero(), |acc, digit| {
+                    acc * ten + Self::from((digit - b'0') as u64)
+                });
+                // The fold reduces modulo p, so only the canonical encoding is accepted
+                if field_to_decimal(&value) != decimal {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Decimal field element is not canonical",
+                    ));
+                }
+                Ok(value)
             }
-            SerdeFormat::RawBytes => <Self as SerdeObject>::read_raw(reader).unwrap(),
-            SerdeFormat::RawBytesUnchecked => <Self as SerdeObject>::read_raw_unchecked(reader),
         }
     }
 
@@ -86,12 +128,37 @@ pub trait SerdePrimeField: PrimeField + SerdeObject {
     fn write<W: io::Write>(&self, writer: &mut W, format: SerdeFormat) {
         match format {
             SerdeFormat::Processed => writer.write_all(self.to_repr().as_ref()).unwrap(),
+            SerdeFormat::Json => json::write_string(writer, &field_to_decimal(self)).unwrap(),
             _ => self.write_raw(writer).unwrap(),
         }
     }
 }
 impl<F: PrimeField + SerdeObject> SerdePrimeField for F {}
 
+/// Decimal representation of a field element, assuming a little-endian `Repr` as for
+/// all fields of `halo2curves`
+fn field_to_decimal<F: PrimeField>(value: &F) -> String {
+    json::le_bytes_to_decimal(value.to_repr().as_ref())
+}
+
+/// Parses a curve element from the hex string of its compressed form
+fn point_from_hex<C: CurveAffine>(hex: &str) -> io::Result<C> {
+    let mut compressed = C::Repr::default();
+    json::from_hex(hex, compressed.as_mut())?;
+    Option::from(C::from_bytes(&compressed))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid point encoding"))
+}
+
+/// Reads a JSON array of curve elements written with `SerdeFormat::Json`
+pub(crate) fn read_point_array_json<R: io::Read, C: CurveAffine>(
+    reader: &mut R,
+) -> io::Result<Vec<C>> {
+    json::read_string_array(reader)?
+        .iter()
+        .map(|hex| point_from_hex(hex))
+        .collect()
+}
+
 /// Convert a slice of `bool` into a `u8`.
 ///
 /// Panics if the slice has length greater than 8.
@@ -115,9 +182,9 @@ pub fn unpack(byte: u8, bits: &mut [bool]) {
 pub(crate) fn read_polynomial_vec<R: io::Read, F: SerdePrimeField, B>(
     reader: &mut R,
     format: SerdeFormat,
-) -> Vec<Polynomial<F, B>> {
+) -> io::Result<Vec<Polynomial<F, B>>> {
     let mut len = [0u8; 4];
-    reader.read_exact(&mut len).unwrap();
+    reader.read_exact(&mut len)?;
     let len = u32::from_be_bytes(len);
 
     (0..len)
@@ -139,6 +206,49 @@ pub(crate) fn write_polynomial_slice<W: io::Write, F: SerdePrimeField, B>(
     }
 }
 
+/// Reads a vector of curve points from buffer
+pub(crate) fn read_point_vec<R: io::Read, C: SerdeCurveAffine>(
+    reader: &mut R,
+    format: SerdeFormat,
+) -> io::Result<Vec<C>> {
+    if let SerdeFormat::Json = format {
+        return read_point_array_json(reader);
+    }
+
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len);
+
+    (0..len).map(|_| C::read(reader, format)).collect()
+}
+
+/// Writes a slice of curve points to buffer
+pub(crate) fn write_point_slice<W: io::Write, C: SerdeCurveAffine>(
+    slice: &[C],
+    writer: &mut W,
+    format: SerdeFormat,
+) {
+    if let SerdeFormat::Json = format {
+        return json::write_array(writer, slice, |writer, point| {
+            point.write(writer, format);
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    writer
+        .write_all(&(slice.len() as u32).to_be_bytes())
+        .unwrap();
+    for point in slice.iter() {
+        point.write(writer, format);
+    }
+}
+
+/// Gets the total number of bytes of a slice of curve points in compressed form
+pub(crate) fn point_slice_byte_length<C: CurveAffine>(slice: &[C]) -> usize {
+    4 + slice.len() * C::default().to_bytes().as_ref().len()
+}
+
 /// Gets the total number of bytes of a slice of polynomials, assuming all polynomials are the same length
 pub(crate) fn polynomial_slice_byte_length<F: PrimeField, B>(slice: &[Polynomial<F, B>]) -> usize {
     let field_len = F::default().to_repr().as_ref().len();
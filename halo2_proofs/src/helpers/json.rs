@@ -0,0 +1,328 @@
+//! Minimal JSON reading and writing for [`SerdeFormat::Json`](crate::SerdeFormat::Json).
+//!
+//! Documents are written without whitespace. Readers accept whitespace between tokens,
+//! but expect object keys in the order they are written in, and strings without escapes.
+//! This is not a general JSON parser: it only reads documents as written by this crate,
+//! and returns an `InvalidData` error on anything else.
+
+use std::io;
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+fn read_byte<R: io::Read>(reader: &mut R) -> io::Result<u8> {
+    let mut byte = [0u8];
+    reader.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+/// Reads the next byte that is not whitespace
+fn next_token<R: io::Read>(reader: &mut R) -> io::Result<u8> {
+    loop {
+        let byte = read_byte(reader)?;
+        if !is_whitespace(byte) {
+            return Ok(byte);
+        }
+    }
+}
+
+/// Consumes `token`, failing on any other byte that is not whitespace
+pub(crate) fn expect<R: io::Read>(reader: &mut R, token: u8) -> io::Result<()> {
+    match next_token(reader)? {
+        found if found == token => Ok(()),
+        found => Err(invalid_data(format!(
+            "expected '{}', found '{}'",
+            token as char, found as char
+        ))),
+    }
+}
+
+/// Reads the rest of a string whose opening quote was already consumed
+fn read_string_body<R: io::Read>(reader: &mut R) -> io::Result<String> {
+    let mut bytes = vec![];
+    loop {
+        match read_byte(reader)? {
+            b'"' => break,
+            b'\\' => return Err(invalid_data("escaped characters are not supported")),
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid_data("string is not valid UTF-8"))
+}
+
+pub(crate) fn read_string<R: io::Read>(reader: &mut R) -> io::Result<String> {
+    expect(reader, b'"')?;
+    read_string_body(reader)
+}
+
+pub(crate) fn write_string<W: io::Write>(writer: &mut W, s: &str) -> io::Result<()> {
+    write!(writer, "\"{}\"", s)
+}
+
+/// Writes `"key":`
+pub(crate) fn write_key<W: io::Write>(writer: &mut W, key: &str) -> io::Result<()> {
+    write!(writer, "\"{}\":", key)
+}
+
+/// Reads `"key":`, failing if the next key is a different one
+pub(crate) fn read_key<R: io::Read>(reader: &mut R, key: &str) -> io::Result<()> {
+    let found = read_string(reader)?;
+    if found != key {
+        return Err(invalid_data(format!(
+            "expected key \"{}\", found \"{}\"",
+            key, found
+        )));
+    }
+    expect(reader, b':')
+}
+
+/// Reads a `u32` and the `terminator` following it
+pub(crate) fn read_u32<R: io::Read>(reader: &mut R, terminator: u8) -> io::Result<u32> {
+    let mut byte = next_token(reader)?;
+    if !byte.is_ascii_digit() {
+        return Err(invalid_data("expected a number"));
+    }
+
+    let mut value = 0u32;
+    while byte.is_ascii_digit() {
+        value = value
+            .checked_mul(10)
+            .and_then(|value| value.checked_add((byte - b'0') as u32))
+            .ok_or_else(|| invalid_data("number does not fit in a u32"))?;
+        byte = read_byte(reader)?;
+    }
+    if is_whitespace(byte) {
+        byte = next_token(reader)?;
+    }
+
+    if byte != terminator {
+        return Err(invalid_data(format!(
+            "expected '{}', found '{}'",
+            terminator as char, byte as char
+        )));
+    }
+    Ok(value)
+}
+
+/// Writes `items` as an array, each with `write_item`
+pub(crate) fn write_array<W: io::Write, T>(
+    writer: &mut W,
+    items: impl IntoIterator<Item = T>,
+    mut write_item: impl FnMut(&mut W, T) -> io::Result<()>,
+) -> io::Result<()> {
+    writer.write_all(b"[")?;
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        write_item(writer, item)?;
+    }
+    writer.write_all(b"]")
+}
+
+/// Reads an array of strings
+pub(crate) fn read_string_array<R: io::Read>(reader: &mut R) -> io::Result<Vec<String>> {
+    expect(reader, b'[')?;
+
+    let mut items = vec![];
+    let mut token = next_token(reader)?;
+    if token == b']' {
+        return Ok(items);
+    }
+    loop {
+        if token != b'"' {
+            return Err(invalid_data("expected a string"));
+        }
+        items.push(read_string_body(reader)?);
+
+        match next_token(reader)? {
+            b',' => token = next_token(reader)?,
+            b']' => return Ok(items),
+            found => {
+                return Err(invalid_data(format!(
+                    "expected ',' or ']', found '{}'",
+                    found as char
+                )))
+            }
+        }
+    }
+}
+
+/// Reads an array of objects, reading the fields of each with `read_fields`
+pub(crate) fn read_object_array<R: io::Read, T>(
+    reader: &mut R,
+    mut read_fields: impl FnMut(&mut R) -> io::Result<T>,
+) -> io::Result<Vec<T>> {
+    expect(reader, b'[')?;
+
+    let mut items = vec![];
+    let mut token = next_token(reader)?;
+    if token == b']' {
+        return Ok(items);
+    }
+    loop {
+        if token != b'{' {
+            return Err(invalid_data("expected an object"));
+        }
+        items.push(read_fields(reader)?);
+        expect(reader, b'}')?;
+
+        match next_token(reader)? {
+            b',' => token = next_token(reader)?,
+            b']' => return Ok(items),
+            found => {
+                return Err(invalid_data(format!(
+                    "expected ',' or ']', found '{}'",
+                    found as char
+                )))
+            }
+        }
+    }
+}
+
+/// Encodes `bytes` as a `0x`-prefixed lowercase hex string
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(2 + 2 * bytes.len());
+    hex.push_str("0x");
+    for byte in bytes {
+        hex.push_str(&format!("{:02x}", byte));
+    }
+    hex
+}
+
+/// Decodes a `0x`-prefixed hex string of exactly `out.len()` bytes into `out`
+pub(crate) fn from_hex(hex: &str, out: &mut [u8]) -> io::Result<()> {
+    let digits = hex
+        .strip_prefix("0x")
+        .ok_or_else(|| invalid_data("hex strings must start with 0x"))?;
+    if !digits.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+        return Err(invalid_data("invalid hex digit"));
+    }
+    if digits.len() != 2 * out.len() {
+        return Err(invalid_data(format!(
+            "expected {} hex digits, found {}",
+            2 * out.len(),
+            digits.len()
+        )));
+    }
+
+    for (byte, pair) in out.iter_mut().zip(digits.as_bytes().chunks(2)) {
+        // Both digits are ASCII, so this is valid UTF-8 and a valid hex number
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap();
+    }
+    Ok(())
+}
+
+/// Decimal representation of the unsigned integer with little-endian `bytes`
+pub(crate) fn le_bytes_to_decimal(bytes: &[u8]) -> String {
+    const BASE: u64 = 1_000_000_000;
+
+    let mut limbs: Vec<u32> = bytes
+        .chunks(4)
+        .map(|chunk| {
+            let mut limb = [0u8; 4];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(limb)
+        })
+        .collect();
+
+    // Digits in base 10^9, least significant first
+    let mut digits = vec![];
+    while limbs.iter().any(|&limb| limb != 0) {
+        let mut remainder = 0u64;
+        for limb in limbs.iter_mut().rev() {
+            let current = (remainder << 32) | *limb as u64;
+            *limb = (current / BASE) as u32;
+            remainder = current % BASE;
+        }
+        digits.push(remainder);
+    }
+
+    match digits.split_last() {
+        None => String::from("0"),
+        Some((most_significant, rest)) => {
+            let mut decimal = most_significant.to_string();
+            for digit in rest.iter().rev() {
+                decimal.push_str(&format!("{:09}", digit));
+            }
+            decimal
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_roundtrip() {
+        let bytes = [0x00, 0x01, 0xab, 0xff];
+        assert_eq!(to_hex(&bytes), "0x0001abff");
+
+        let mut decoded = [0u8; 4];
+        from_hex("0x0001ABff", &mut decoded).unwrap();
+        assert_eq!(decoded, bytes);
+
+        assert!(from_hex("0001abff", &mut decoded).is_err());
+        assert!(from_hex("0x0001ab", &mut decoded).is_err());
+        assert!(from_hex("0x+001abff", &mut decoded).is_err());
+    }
+
+    #[test]
+    fn decimal_of_le_bytes() {
+        assert_eq!(le_bytes_to_decimal(&[0; 32]), "0");
+        assert_eq!(le_bytes_to_decimal(&[0x39, 0x30]), "12345");
+        // 2^64
+        assert_eq!(
+            le_bytes_to_decimal(&[0, 0, 0, 0, 0, 0, 0, 0, 1]),
+            "18446744073709551616"
+        );
+        // 10^9 has a zero digit in base 10^9
+        assert_eq!(le_bytes_to_decimal(&[0x00, 0xca, 0x9a, 0x3b]), "1000000000");
+    }
+
+    #[test]
+    fn read_tokens_with_whitespace() {
+        let mut reader = &b" { \"k\" : 12 ,\n \"xs\": [ \"a\" , \"b\" ], \"ys\":[] }"[..];
+        expect(&mut reader, b'{').unwrap();
+        read_key(&mut reader, "k").unwrap();
+        assert_eq!(read_u32(&mut reader, b',').unwrap(), 12);
+        read_key(&mut reader, "xs").unwrap();
+        assert_eq!(read_string_array(&mut reader).unwrap(), vec!["a", "b"]);
+        expect(&mut reader, b',').unwrap();
+        read_key(&mut reader, "ys").unwrap();
+        assert!(read_string_array(&mut reader).unwrap().is_empty());
+        expect(&mut reader, b'}').unwrap();
+
+        let mut reader = &b"\"j\":"[..];
+        assert!(read_key(&mut reader, "k").is_err());
+    }
+
+    #[test]
+    fn read_objects() {
+        let read_fields = |reader: &mut &[u8]| -> io::Result<(u32, String)> {
+            read_key(reader, "k")?;
+            let k = read_u32(reader, b',')?;
+            read_key(reader, "s")?;
+            Ok((k, read_string(reader)?))
+        };
+        let mut reader = &b"[{\"k\":1,\"s\":\"a\"} , { \"k\": 2, \"s\": \"b\" }]"[..];
+        assert_eq!(
+            read_object_array(&mut reader, read_fields).unwrap(),
+            vec![(1, String::from("a")), (2, String::from("b"))]
+        );
+
+        let mut reader = &b" [ ]"[..];
+        assert!(read_object_array(&mut reader, read_fields)
+            .unwrap()
+            .is_empty());
+
+        let mut reader = &b"[\"k\"]"[..];
+        assert!(read_object_array(&mut reader, read_fields).is_err());
+    }
+}
@@ -14,8 +14,9 @@ use halo2curves::CurveExt;
 
 use crate::arithmetic::{CurveAffine, FieldExt};
 use crate::helpers::{
-    polynomial_slice_byte_length, read_polynomial_vec, write_polynomial_slice, SerdeCurveAffine,
-    SerdePrimeField,
+    json, point_slice_byte_length, polynomial_slice_byte_length, read_point_array_json,
+    read_point_vec, read_polynomial_vec, write_point_slice, write_polynomial_slice,
+    SerdeCurveAffine, SerdePrimeField,
 };
 use crate::poly::{
     commitment::Params, Coeff, EvaluationDomain, ExtendedLagrangeCoeff, LagrangeCoeff,
@@ -45,7 +46,7 @@ pub use prover::*;
 pub use verifier::*;
 
 use evaluation::Evaluator;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
 use std::io;
 
@@ -87,10 +88,15 @@ where
     /// - `Processed`: Writes a compressed curve element with coordinates in standard form.
     /// Writes a field element in standard form, with endianness specified by the
     /// `PrimeField` implementation.
+    /// - `Json`: Writes a JSON object with curve elements as hex strings of their compressed form.
     /// - Otherwise: Writes an uncompressed curve element with coordinates in Montgomery form
     /// Writes a field element into raw bytes in its internal Montgomery representation,
     /// WITHOUT performing the expensive Montgomery reduction.
     pub fn write<W: io::Write>(&self, writer: &mut W, format: SerdeFormat) -> io::Result<()> {
+        if let SerdeFormat::Json = format {
+            return self.write_json(writer);
+        }
+
         writer.write_all(&self.domain.k().to_be_bytes()).unwrap();
         writer
             .write_all(&(self.fixed_commitments.len() as u32).to_be_bytes())
@@ -107,6 +113,12 @@ where
                 writer.write_all(&[crate::helpers::pack(bits)]).unwrap();
             }
         }
+
+        writer.write_all(&(self.static_table_mapping.len() as u32).to_be_bytes())?;
+        for (id, table) in self.static_table_mapping.iter() {
+            id.write(writer)?;
+            table.write(writer, format)?;
+        }
         Ok(())
     }
 
@@ -120,46 +132,165 @@ where
     /// Checks that field elements are less than modulus, and then checks that the point is on the curve.
     /// - `RawBytesUnchecked`: Reads an uncompressed curve element with coordinates in Montgomery form;
     /// does not perform any checks
+    /// - `Json`: Reads a JSON object written by [`Self::write`] and decompresses curve elements.
     pub fn read<R: io::Read, ConcreteCircuit: Circuit<E>>(
         reader: &mut R,
         format: SerdeFormat,
     ) -> io::Result<Self> {
+        if let SerdeFormat::Json = format {
+            return Self::read_json::<R, ConcreteCircuit>(reader);
+        }
+
         let mut k = [0u8; 4];
         reader.read_exact(&mut k)?;
         let k = u32::from_be_bytes(k);
         let (domain, cs, _) = keygen::create_domain::<E, ConcreteCircuit>(k);
         let mut num_fixed_columns = [0u8; 4];
-        reader.read_exact(&mut num_fixed_columns).unwrap();
+        reader.read_exact(&mut num_fixed_columns)?;
         let num_fixed_columns = u32::from_be_bytes(num_fixed_columns);
 
-        let fixed_commitments: Vec<_> = (0..num_fixed_columns)
+        let fixed_commitments = (0..num_fixed_columns)
             .map(|_| E::G1Affine::read(reader, format))
-            .collect();
+            .collect::<io::Result<Vec<_>>>()?;
 
-        let permutation = permutation::VerifyingKey::read(reader, &cs.permutation, format);
+        let permutation = permutation::VerifyingKey::read(reader, &cs.permutation, format)?;
 
         // read selectors
         let selectors: Vec<Vec<bool>> = vec![vec![false; 1 << k]; cs.num_selectors]
             .into_iter()
             .map(|mut selector| {
                 let mut selector_bytes = vec![0u8; (selector.len() + 7) / 8];
-                reader.read_exact(&mut selector_bytes).unwrap();
+                reader.read_exact(&mut selector_bytes)?;
                 for (bits, byte) in selector.chunks_mut(8).into_iter().zip(selector_bytes) {
                     crate::helpers::unpack(byte, bits);
                 }
-                selector
+                Ok(selector)
             })
-            .collect();
+            .collect::<io::Result<_>>()?;
         let (cs, _) = cs.compress_selectors(selectors.clone());
 
+        let mut num_tables = [0u8; 4];
+        reader.read_exact(&mut num_tables)?;
+        let static_table_mapping = (0..u32::from_be_bytes(num_tables))
+            .map(|_| {
+                let id = StaticTableId::read(reader)?;
+                Ok((id, StaticCommittedTable::read(reader, format)?))
+            })
+            .collect::<io::Result<_>>()?;
+
         Ok(Self::from_parts(
             domain,
             fixed_commitments,
             permutation,
             cs,
             selectors,
-            // TODO: FIXME
-            BTreeMap::default(),
+            static_table_mapping,
+        ))
+    }
+
+    /// Writes `{"k":..,"fixed_commitments":[..],"permutation":[..],"selectors":[..],
+    /// "static_tables":[..]}`, with each selector as the hex string of its bits packed as in
+    /// the binary formats, and each static table as an object with its `id` followed by
+    /// the fields of [`StaticCommittedTable`]
+    fn write_json<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        let format = SerdeFormat::Json;
+
+        writer.write_all(b"{")?;
+        json::write_key(writer, "k")?;
+        write!(writer, "{},", self.domain.k())?;
+        json::write_key(writer, "fixed_commitments")?;
+        write_point_slice(&self.fixed_commitments, writer, format);
+        writer.write_all(b",")?;
+        json::write_key(writer, "permutation")?;
+        self.permutation.write(writer, format);
+        writer.write_all(b",")?;
+        json::write_key(writer, "selectors")?;
+        json::write_array(writer, &self.selectors, |writer, selector| {
+            let bytes: Vec<u8> = selector.chunks(8).map(crate::helpers::pack).collect();
+            json::write_string(writer, &json::to_hex(&bytes))
+        })?;
+        writer.write_all(b",")?;
+        json::write_key(writer, "static_tables")?;
+        json::write_array(writer, &self.static_table_mapping, |writer, (id, table)| {
+            if id.id().contains(|c| c == '"' || c == '\\') {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("static table id {:?} cannot be written as JSON", id.id()),
+                ));
+            }
+            writer.write_all(b"{")?;
+            json::write_key(writer, "id")?;
+            json::write_string(writer, id.id())?;
+            writer.write_all(b",")?;
+            table.write(writer, format)?;
+            writer.write_all(b"}")
+        })?;
+        writer.write_all(b"}")
+    }
+
+    /// Reads a verifying key written by [`Self::write_json`]
+    fn read_json<R: io::Read, ConcreteCircuit: Circuit<E>>(reader: &mut R) -> io::Result<Self> {
+        let format = SerdeFormat::Json;
+
+        json::expect(reader, b'{')?;
+        json::read_key(reader, "k")?;
+        let k = json::read_u32(reader, b',')?;
+        let (domain, cs, _) = keygen::create_domain::<E, ConcreteCircuit>(k);
+
+        json::read_key(reader, "fixed_commitments")?;
+        let fixed_commitments = read_point_array_json(reader)?;
+        json::expect(reader, b',')?;
+
+        json::read_key(reader, "permutation")?;
+        let permutation = permutation::VerifyingKey::read(reader, &cs.permutation, format)?;
+        json::expect(reader, b',')?;
+
+        json::read_key(reader, "selectors")?;
+        let selectors = json::read_string_array(reader)?;
+        if selectors.len() != cs.num_selectors {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "expected {} selectors, read {}",
+                    cs.num_selectors,
+                    selectors.len()
+                ),
+            ));
+        }
+        let selectors = selectors
+            .iter()
+            .map(|hex| {
+                let mut selector = vec![false; 1 << k];
+                let mut selector_bytes = vec![0u8; (selector.len() + 7) / 8];
+                json::from_hex(hex, &mut selector_bytes)?;
+                for (bits, byte) in selector.chunks_mut(8).zip(selector_bytes) {
+                    crate::helpers::unpack(byte, bits);
+                }
+                Ok(selector)
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        json::expect(reader, b',')?;
+
+        json::read_key(reader, "static_tables")?;
+        let static_table_mapping = json::read_object_array(reader, |reader| {
+            json::read_key(reader, "id")?;
+            let id = StaticTableId(json::read_string(reader)?);
+            json::expect(reader, b',')?;
+            Ok((id, StaticCommittedTable::read(reader, format)?))
+        })?
+        .into_iter()
+        .collect();
+        json::expect(reader, b'}')?;
+
+        let (cs, _) = cs.compress_selectors(selectors.clone());
+
+        Ok(Self::from_parts(
+            domain,
+            fixed_commitments,
+            permutation,
+            cs,
+            selectors,
+            static_table_mapping,
         ))
     }
 
@@ -193,6 +324,12 @@ where
                     .get(0)
                     .map(|selector| selector.len() / 8 + 1)
                     .unwrap_or(0))
+            + 4
+            + self
+                .static_table_mapping
+                .iter()
+                .map(|(id, table)| id.bytes_length() + table.bytes_length())
+                .sum::<usize>()
     }
 
     fn from_parts(
@@ -202,7 +339,11 @@ where
         cs: ConstraintSystem<E::Scalar>,
         selectors: Vec<Vec<bool>>,
         static_table_mapping: BTreeMap<StaticTableId<String>, StaticCommittedTable<E>>,
-    ) -> Self {
+    ) -> Self
+    where
+        E::G2Affine: SerdeCurveAffine,
+        E::Scalar: SerdePrimeField,
+    {
         // Compute cached values.
         let cs_degree = cs.degree();
 
@@ -228,6 +369,22 @@ where
         hasher.update(&(s.len() as u64).to_le_bytes());
         hasher.update(s.as_bytes());
 
+        // Static tables are not part of the pinned key; bind their commitments too, so
+        // circuits that only differ in their tables do not share a transcript. They are
+        // hashed as written by `write`, which does not depend on their `Debug` output.
+        let mut tables = (vk.static_table_mapping.len() as u32)
+            .to_be_bytes()
+            .to_vec();
+        for (id, table) in vk.static_table_mapping.iter() {
+            id.write(&mut tables)
+                .expect("writing to a Vec should not fail");
+            table
+                .write(&mut tables, SerdeFormat::Processed)
+                .expect("writing to a Vec should not fail");
+        }
+        hasher.update(&(tables.len() as u64).to_le_bytes());
+        hasher.update(&tables);
+
         // Hash in final Blake2bState
         vk.transcript_repr = E::Scalar::from_bytes_wide(hasher.finalize().as_array());
 
@@ -271,6 +428,21 @@ where
     pub fn cs(&self) -> &ConstraintSystem<E::Scalar> {
         &self.cs
     }
+
+    /// Returns the distinct sizes of the registered static tables, in increasing order
+    pub fn static_table_sizes(&self) -> Vec<usize> {
+        self.static_table_mapping
+            .values()
+            .map(|table| table.size)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Returns the commitments of the registered static tables, by id
+    pub fn static_commitments(&self) -> &BTreeMap<StaticTableId<String>, StaticCommittedTable<E>> {
+        &self.static_table_mapping
+    }
 }
 
 /// Minimal representation of a verification key that can be used to identify
@@ -317,6 +489,17 @@ where
         &self.vk
     }
 
+    /// Returns the distinct sizes of the registered static tables, in increasing order.
+    /// `keygen_pk` expects a `StaticTableConfig` for each of them.
+    pub fn static_table_sizes(&self) -> Vec<usize> {
+        self.static_table_mapping
+            .values()
+            .map(|table| table.size())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
     /// Gets the total number of bytes in the serialization of `self`
     fn bytes_length(&self) -> usize {
         let scalar_len = E::Scalar::default().to_repr().as_ref().len();
@@ -327,6 +510,19 @@ where
             + polynomial_slice_byte_length(&self.fixed_polys)
             + polynomial_slice_byte_length(&self.fixed_cosets)
             + self.permutation.bytes_length()
+            + 4
+            + self
+                .static_table_mapping
+                .iter()
+                .map(|(id, table)| id.bytes_length() + table.bytes_length())
+                .sum::<usize>()
+            + 4
+            + self
+                .static_table_configs
+                .values()
+                .map(|config| config.bytes_length())
+                .sum::<usize>()
+            + point_slice_byte_length(&self.b0_g1_bound)
     }
 }
 
@@ -347,6 +543,10 @@ where
     /// WITHOUT performing the expensive Montgomery reduction.
     /// Does so by first writing the verifying key and then serializing the rest of the data (in the form of field polynomials)
     pub fn write<W: io::Write>(&self, writer: &mut W, format: SerdeFormat) -> io::Result<()> {
+        if let SerdeFormat::Json = format {
+            return Err(json_proving_key_error());
+        }
+
         self.vk.write(writer, format).unwrap();
         self.l0.write(writer, format);
         self.l_last.write(writer, format);
@@ -355,11 +555,24 @@ where
         write_polynomial_slice(&self.fixed_polys, writer, format);
         write_polynomial_slice(&self.fixed_cosets, writer, format);
         self.permutation.write(writer, format);
+
+        writer.write_all(&(self.static_table_mapping.len() as u32).to_be_bytes())?;
+        for (id, table) in self.static_table_mapping.iter() {
+            id.write(writer)?;
+            table.write(writer, format);
+        }
+        writer.write_all(&(self.static_table_configs.len() as u32).to_be_bytes())?;
+        for config in self.static_table_configs.values() {
+            config.write(writer, format);
+        }
+        write_point_slice(&self.b0_g1_bound, writer, format);
         Ok(())
     }
 
     /// Reads a proving key from a buffer.
     /// Does so by reading verification key first, and then deserializing the rest of the file into the remaining proving key data.
+    /// Fails if the buffer ends early, or if the polynomials do not match the domain of the
+    /// verification key.
     ///
     /// Reads a curve element from the buffer and parses it according to the `format`:
     /// - `Processed`: Reads a compressed curve element and decompresses it.
@@ -373,16 +586,53 @@ where
         reader: &mut R,
         format: SerdeFormat,
     ) -> io::Result<Self> {
-        let vk = VerifyingKey::<E>::read::<R, ConcreteCircuit>(reader, format).unwrap();
-        let l0 = Polynomial::read(reader, format);
-        let l_last = Polynomial::read(reader, format);
-        let l_active_row = Polynomial::read(reader, format);
-        let fixed_values = read_polynomial_vec(reader, format);
-        let fixed_polys = read_polynomial_vec(reader, format);
-        let fixed_cosets = read_polynomial_vec(reader, format);
-        let permutation = permutation::ProvingKey::read(reader, format);
-        // TODO:
-        // let static_tables = static_lookup::StaticTable::read(reader, format);
+        if let SerdeFormat::Json = format {
+            return Err(json_proving_key_error());
+        }
+
+        let vk = VerifyingKey::<E>::read::<R, ConcreteCircuit>(reader, format)?;
+        let n = vk.domain.n as usize;
+        let extended_len = vk.domain.extended_len();
+        let num_fixed = vk.fixed_commitments.len();
+
+        let l0 = Polynomial::read(reader, format)?;
+        check_polynomial_lengths("l0", std::slice::from_ref(&l0), 1, extended_len)?;
+        let l_last = Polynomial::read(reader, format)?;
+        check_polynomial_lengths("l_last", std::slice::from_ref(&l_last), 1, extended_len)?;
+        let l_active_row = Polynomial::read(reader, format)?;
+        check_polynomial_lengths(
+            "l_active_row",
+            std::slice::from_ref(&l_active_row),
+            1,
+            extended_len,
+        )?;
+        let fixed_values = read_polynomial_vec(reader, format)?;
+        check_polynomial_lengths("fixed_values", &fixed_values, num_fixed, n)?;
+        let fixed_polys = read_polynomial_vec(reader, format)?;
+        check_polynomial_lengths("fixed_polys", &fixed_polys, num_fixed, n)?;
+        let fixed_cosets = read_polynomial_vec(reader, format)?;
+        check_polynomial_lengths("fixed_cosets", &fixed_cosets, num_fixed, extended_len)?;
+        let permutation = permutation::ProvingKey::read(reader, format)?;
+
+        let mut num_tables = [0u8; 4];
+        reader.read_exact(&mut num_tables)?;
+        let static_table_mapping = (0..u32::from_be_bytes(num_tables))
+            .map(|_| {
+                let id = StaticTableId::read(reader)?;
+                Ok((id, StaticTableValues::read(reader, format)?))
+            })
+            .collect::<io::Result<_>>()?;
+
+        let mut num_configs = [0u8; 4];
+        reader.read_exact(&mut num_configs)?;
+        let static_table_configs = (0..u32::from_be_bytes(num_configs))
+            .map(|_| {
+                let config = StaticTableConfig::read(reader, format)?;
+                Ok((config.size(), config))
+            })
+            .collect::<io::Result<_>>()?;
+        let b0_g1_bound = read_point_vec(reader, format)?;
+
         let ev = Evaluator::new(vk.cs());
 
         Ok(Self {
@@ -395,10 +645,9 @@ where
             fixed_cosets,
             permutation,
             ev,
-            // FIXME
-            static_table_mapping: BTreeMap::default(),
-            static_table_configs: BTreeMap::default(),
-            b0_g1_bound: vec![],
+            static_table_mapping,
+            static_table_configs,
+            b0_g1_bound,
         })
     }
 
@@ -418,6 +667,47 @@ where
     }
 }
 
+fn json_proving_key_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "proving keys cannot be serialized as JSON",
+    )
+}
+
+/// Checks that `polys` holds `count` polynomials of `len` values each, so that a
+/// proving key read from a corrupted file or for another `k` is rejected instead of
+/// being used with the wrong domain.
+fn check_polynomial_lengths<F, B>(
+    name: &str,
+    polys: &[Polynomial<F, B>],
+    count: usize,
+    len: usize,
+) -> io::Result<()> {
+    if polys.len() != count {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "expected {} {} polynomials, read {}",
+                count,
+                name,
+                polys.len()
+            ),
+        ));
+    }
+    match polys.iter().find(|poly| poly.len() != len) {
+        Some(poly) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "expected {} polynomials of length {}, read one of length {}",
+                name,
+                len,
+                poly.len()
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
 impl<E: MultiMillerLoop + Debug> VerifyingKey<E>
 where
     E::G1Affine: SerdeObject,
@@ -168,6 +168,12 @@ impl<F: Field> AddAssign<&Assigned<F>> for Assigned<F> {
     }
 }
 
+impl<F: Field> AddAssign<F> for Assigned<F> {
+    fn add_assign(&mut self, rhs: F) {
+        *self = *self + rhs;
+    }
+}
+
 impl<F: Field> Sub for Assigned<F> {
     type Output = Assigned<F>;
     fn sub(self, rhs: Assigned<F>) -> Assigned<F> {
@@ -222,6 +228,12 @@ impl<F: Field> SubAssign<&Assigned<F>> for Assigned<F> {
     }
 }
 
+impl<F: Field> SubAssign<F> for Assigned<F> {
+    fn sub_assign(&mut self, rhs: F) {
+        *self = *self - rhs;
+    }
+}
+
 impl<F: Field> Mul for Assigned<F> {
     type Output = Assigned<F>;
     fn mul(self, rhs: Assigned<F>) -> Assigned<F> {
@@ -276,6 +288,12 @@ impl<F: Field> MulAssign<&Assigned<F>> for Assigned<F> {
     }
 }
 
+impl<F: Field> MulAssign<F> for Assigned<F> {
+    fn mul_assign(&mut self, rhs: F) {
+        *self = *self * rhs;
+    }
+}
+
 impl<F: Field> Assigned<F> {
     /// Returns the numerator.
     pub fn numerator(&self) -> F {
@@ -568,6 +568,24 @@ pub trait Assignment<F: Field> {
     /// Returns the cell's value, if known.
     fn query_instance(&self, column: Column<Instance>, row: usize) -> Result<Value<F>, Error>;
 
+    /// Assigns the cell of an instance column at a particular absolute row.
+    ///
+    /// Instances are absorbed into the transcript when [`FirstPhase`] ends, so they
+    /// can only be changed during first-phase synthesis. Backends that do not track
+    /// instance values ignore this.
+    ///
+    /// Not intended for downstream consumption; use [`Layouter::assign_instance`] instead.
+    ///
+    /// [`Layouter::assign_instance`]: crate::circuit::Layouter#method.assign_instance
+    fn assign_instance(
+        &mut self,
+        _column: Column<Instance>,
+        _row: usize,
+        _to: Value<F>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Assign an advice column value (witness)
     fn assign_advice<'r, 'v>(
         //<V, VR, A, AR>(
@@ -628,6 +646,14 @@ pub trait Assignment<F: Field> {
     /// Commit advice columns in current phase and squeeze challenges. This can be
     /// called DURING synthesize.
     fn next_phase(&mut self) {}
+
+    /// Calls [`Assignment::next_phase`] until `phase` is the current phase. Does
+    /// nothing if `phase` is current or has already passed.
+    ///
+    /// Not intended for downstream consumption; use [`Layouter::assign_region_in_phase`] instead.
+    ///
+    /// [`Layouter::assign_region_in_phase`]: crate::circuit::Layouter#method.assign_region_in_phase
+    fn enter_phase<P: Phase>(&mut self, _phase: P) {}
 }
 
 /// A floor planning strategy for a circuit.
@@ -2058,6 +2084,14 @@ impl<F: Field> ConstraintSystem<F> {
             + 1 // for at least one row
     }
 
+    /// Returns the smallest `k` for which a circuit of `2^k` rows has the
+    /// [`minimum_rows`](Self::minimum_rows), i.e. the smallest `k` accepted by
+    /// keygen. Static lookups need no more: their B0 polynomial only requires
+    /// two rows.
+    pub fn minimum_k(&self) -> u32 {
+        self.minimum_rows().next_power_of_two().trailing_zeros()
+    }
+
     /// Returns number of fixed columns
     pub fn num_fixed_columns(&self) -> usize {
         self.num_fixed_columns
@@ -2121,6 +2155,11 @@ impl<F: Field> ConstraintSystem<F> {
         &self.lookups
     }
 
+    /// Returns static lookup arguments
+    pub fn static_lookups(&self) -> &[static_lookup::Argument<F>] {
+        &self.static_lookups
+    }
+
     /// Returns constants
     pub fn constants(&self) -> &Vec<Column<Fixed>> {
         &self.constants
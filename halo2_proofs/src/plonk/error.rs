@@ -3,7 +3,7 @@ use std::error;
 use std::fmt;
 use std::io;
 
-use super::{Any, Column};
+use super::{static_lookup::StaticTableId, Any, Column, ProofArgument};
 
 /// This is an error that could occur during proving or circuit synthesis.
 // TODO: these errors need to be cleaned up
@@ -14,6 +14,8 @@ pub enum Error {
     Synthesis,
     /// The provided instances do not match the circuit parameters.
     InvalidInstances,
+    /// No proofs were given to verify as a batch.
+    NoProofs,
     /// The constraint system is not satisfied.
     ConstraintSystemFailure,
     /// Out of bounds index passed to a backend
@@ -37,6 +39,53 @@ pub enum Error {
     /// The instance sets up a copy constraint involving a column that has not been
     /// included in the permutation.
     ColumnNotInPermutation(Column<Any>),
+    /// A static lookup references a table that was not registered during synthesis.
+    UnregisteredStaticTable(StaticTableId<String>),
+    /// Two different static tables were registered under the same id during synthesis.
+    ConflictingStaticTable(StaticTableId<String>),
+    /// A static table has the given number of values, which is not a power of two.
+    TableSizeNotPowerOfTwo(usize),
+    /// The value of a static table at the given index repeats an earlier value.
+    DuplicateTableValue(usize),
+    /// The default value of a static table is not one of its values.
+    DefaultValueNotInTable,
+    /// A static table of the given size is used but no `StaticTableConfig` was provided for it.
+    StaticLookupConfig(usize),
+    /// The `StaticTableConfig` of the given size does not match the SRS.
+    InvalidStaticTableConfig(usize),
+    /// A static table cannot be committed for a circuit domain with the given SRS, see
+    /// [`StaticTableValues::commit`].
+    ///
+    /// [`StaticTableValues::commit`]: crate::plonk::static_lookup::StaticTableValues::commit
+    StaticTableDomain {
+        /// The number of values in the table.
+        table_size: usize,
+        /// The number of rows of the circuit.
+        circuit_domain: usize,
+        /// The number of G1 points of the SRS.
+        srs_g1_len: usize,
+        /// The number of G2 points of the SRS.
+        srs_g2_len: usize,
+    },
+    /// The pairing equations of the given argument do not hold.
+    ArgumentFailed(ProofArgument),
+    /// An advice cell was assigned `Value::unknown()` while creating a proof.
+    UnknownAdvice {
+        /// The column of the unknown cell.
+        column: Column<Any>,
+        /// The row of the unknown cell.
+        row: usize,
+    },
+    /// An instance cell was assigned a new value after [`FirstPhase`] synthesis, when
+    /// the instances were already absorbed into the transcript.
+    ///
+    /// [`FirstPhase`]: crate::plonk::FirstPhase
+    InstanceAfterFirstPhase {
+        /// The column of the instance cell.
+        column: Column<Any>,
+        /// The row of the instance cell.
+        row: usize,
+    },
 }
 
 impl From<io::Error> for Error {
@@ -58,6 +107,7 @@ impl fmt::Display for Error {
         match self {
             Error::Synthesis => write!(f, "General synthesis error"),
             Error::InvalidInstances => write!(f, "Provided instances do not match the circuit"),
+            Error::NoProofs => write!(f, "No proofs were provided to verify"),
             Error::ConstraintSystemFailure => write!(f, "The constraint system is not satisfied"),
             Error::BoundsFailure => write!(f, "An out-of-bounds index was passed to the backend"),
             Error::Opening => write!(f, "Multi-opening proof was invalid"),
@@ -79,6 +129,69 @@ impl fmt::Display for Error {
                 "Column {:?} must be included in the permutation. Help: try applying `meta.enable_equalty` on the column",
                 column
             ),
+            Error::UnregisteredStaticTable(id) => write!(
+                f,
+                "Static table {:?} is used in a lookup but was never registered. Help: try calling `layouter.register_static_table` with this id",
+                id.id()
+            ),
+            Error::ConflictingStaticTable(id) => write!(
+                f,
+                "Static table {:?} was registered twice with different contents. Help: give every table its own id",
+                id.id()
+            ),
+            Error::TableSizeNotPowerOfTwo(size) => write!(
+                f,
+                "A static table has {} values, which is not a power of two. Help: pad the table with distinct values up to the next power of two",
+                size
+            ),
+            Error::DuplicateTableValue(index) => write!(
+                f,
+                "The value at index {} of a static table repeats an earlier value. Help: static tables must hold distinct values",
+                index
+            ),
+            Error::DefaultValueNotInTable => write!(
+                f,
+                "The default value of a static table is not one of its values. Help: pick a default value from the table values"
+            ),
+            Error::StaticLookupConfig(size) => write!(
+                f,
+                "No static table config was provided for tables of size {}. Help: try adding a `StaticTableConfig` of this size to the configs passed to `keygen_pk`",
+                size
+            ),
+            Error::InvalidStaticTableConfig(size) => write!(
+                f,
+                "The static table config for tables of size {} does not match the SRS. Help: check that `g1_lagrange` and `g_lagrange_opening_at_0` come from the same `TableSRS`",
+                size
+            ),
+            Error::StaticTableDomain {
+                table_size,
+                circuit_domain,
+                srs_g1_len,
+                srs_g2_len,
+            } => write!(
+                f,
+                "Cannot commit a static table of size {} for a circuit domain of {} rows with an SRS of {} G1 and {} G2 points. Help: the circuit domain must be between 2 and the number of G1 points, the table must fit in the G1 points, and the SRS needs the G2 powers up to the table size and to the B0 degree bound",
+                table_size, circuit_domain, srs_g1_len, srs_g2_len
+            ),
+            Error::ArgumentFailed(argument) => match argument {
+                ProofArgument::StaticLookup(index) => {
+                    write!(f, "Static lookup {} does not verify", index)
+                }
+                ProofArgument::Opening => write!(
+                    f,
+                    "Multiopen argument does not verify, one of the gates, the permutation, a lookup or the vanishing argument is not satisfied"
+                ),
+            },
+            Error::UnknownAdvice { column, row } => write!(
+                f,
+                "Advice cell ({:?}, row {}) was assigned an unknown value during proving. Help: check that witness data is not taken from `without_witnesses`",
+                column, row
+            ),
+            Error::InstanceAfterFirstPhase { column, row } => write!(
+                f,
+                "Instance cell ({:?}, row {}) was assigned after the first phase. Help: assign instances in a region of `FirstPhase`",
+                column, row
+            ),
         }
     }
 }
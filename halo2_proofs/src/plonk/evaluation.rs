@@ -26,7 +26,7 @@ use std::fmt::Debug;
 use std::num::ParseIntError;
 use std::slice;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     iter,
     ops::{Index, Mul, MulAssign},
 };
@@ -541,8 +541,13 @@ impl<C: CurveAffine> Evaluator<C> {
                         let idx = start + i;
 
                         *value = *value * y
-                            + (b_coset[idx] * (f_coset[idx] * l_active_row[idx] + beta)
-                                - E::Scalar::one());
+                            + static_lookup::log_derivative_identity(
+                                b_coset[idx],
+                                f_coset[idx],
+                                l_active_row[idx],
+                                lookup.default_value,
+                                beta,
+                            );
                     }
                 });
             }
@@ -814,3 +819,173 @@ pub fn evaluate<F: FieldExt, B: Basis>(
     });
     values
 }
+
+/// Evaluates several expressions over the same columns, like calling
+/// [`evaluate`] on each of them, but computes every distinct subexpression
+/// (column queries included) only once per row. Subexpressions are identified by
+/// [`Expression::identifier`].
+///
+/// Rows are evaluated in parallel chunks, each with a scratch value per distinct
+/// subexpression, so no intermediate column is allocated.
+pub fn evaluate_many<F: FieldExt, B: Basis>(
+    expressions: &[Expression<F>],
+    size: usize,
+    rot_scale: i32,
+    fixed: &[Polynomial<F, B>],
+    advice: &[Polynomial<F, B>],
+    instance: &[Polynomial<F, B>],
+    challenges: &[F],
+) -> Vec<Vec<F>> {
+    let mut nodes = vec![];
+    let mut node_indices = HashMap::new();
+    let outputs: Vec<usize> = expressions
+        .iter()
+        .map(|expression| add_node(expression, &mut nodes, &mut node_indices, challenges))
+        .collect();
+
+    // Values of all expressions, row by row
+    let num_outputs = outputs.len();
+    let mut values = vec![F::zero(); size * num_outputs];
+    let mut rows: Vec<&mut [F]> = values.chunks_mut(num_outputs.max(1)).collect();
+    let isize = size as i32;
+    parallelize(&mut rows, |rows, start| {
+        let mut scratch = vec![F::zero(); nodes.len()];
+        for (row, values) in (start..).zip(rows.iter_mut()) {
+            let rotated = |rotation: i32| get_rotation_idx(row, rotation, rot_scale, isize);
+            for (index, node) in nodes.iter().enumerate() {
+                scratch[index] = match *node {
+                    Node::Scalar(scalar) => scalar,
+                    Node::Fixed(column, rotation) => fixed[column][rotated(rotation)],
+                    Node::Advice(column, rotation) => advice[column][rotated(rotation)],
+                    Node::Instance(column, rotation) => instance[column][rotated(rotation)],
+                    Node::Negated(a) => -scratch[a],
+                    Node::Sum(a, b) => scratch[a] + scratch[b],
+                    Node::Product(a, b) => scratch[a] * scratch[b],
+                    Node::Scaled(a, scalar) => scratch[a] * scalar,
+                };
+            }
+            for (value, &output) in values.iter_mut().zip(outputs.iter()) {
+                *value = scratch[output];
+            }
+        }
+    });
+
+    (0..num_outputs)
+        .map(|output| {
+            let mut column = vec![F::zero(); size];
+            parallelize(&mut column, |column, start| {
+                for (row, value) in (start..).zip(column.iter_mut()) {
+                    *value = values[row * num_outputs + output];
+                }
+            });
+            column
+        })
+        .collect()
+}
+
+/// A distinct subexpression in [`evaluate_many`], whose operands are the indices
+/// of earlier nodes
+enum Node<F> {
+    /// A constant or a challenge, which is the same in every row
+    Scalar(F),
+    Fixed(usize, i32),
+    Advice(usize, i32),
+    Instance(usize, i32),
+    Negated(usize),
+    Sum(usize, usize),
+    Product(usize, usize),
+    Scaled(usize, F),
+}
+
+/// Adds the nodes of `expression` that are not in `nodes` yet, returning its index
+fn add_node<F: FieldExt>(
+    expression: &Expression<F>,
+    nodes: &mut Vec<Node<F>>,
+    node_indices: &mut HashMap<String, usize>,
+    challenges: &[F],
+) -> usize {
+    let id = expression.identifier();
+    if let Some(&index) = node_indices.get(&id) {
+        return index;
+    }
+
+    let mut add = |expression: &Expression<F>, nodes: &mut Vec<Node<F>>| {
+        add_node(expression, nodes, node_indices, challenges)
+    };
+    let node = match expression {
+        Expression::Constant(scalar) => Node::Scalar(*scalar),
+        Expression::Selector(_) => panic!("virtual selectors are removed during optimization"),
+        Expression::Fixed(q) => Node::Fixed(q.column_index, q.rotation.0),
+        Expression::Advice(q) => Node::Advice(q.column_index, q.rotation.0),
+        Expression::Instance(q) => Node::Instance(q.column_index, q.rotation.0),
+        Expression::Challenge(challenge) => Node::Scalar(challenges[challenge.index()]),
+        Expression::Negated(a) => Node::Negated(add(a, nodes)),
+        Expression::Sum(a, b) => Node::Sum(add(a, nodes), add(b, nodes)),
+        Expression::Product(a, b) => Node::Product(add(a, nodes), add(b, nodes)),
+        Expression::Scaled(a, scalar) => Node::Scaled(add(a, nodes), *scalar),
+    };
+
+    nodes.push(node);
+    node_indices.insert(id, nodes.len() - 1);
+    nodes.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use ff::Field;
+    use halo2curves::bn256::Fr;
+    use rand_core::OsRng;
+
+    use super::{evaluate, evaluate_many};
+    use crate::{
+        plonk::{ConstraintSystem, Expression, FirstPhase},
+        poly::{EvaluationDomain, Rotation},
+    };
+
+    #[test]
+    fn evaluate_many_matches_evaluate() {
+        let mut meta = ConstraintSystem::<Fr>::default();
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let f = meta.fixed_column();
+        let i = meta.instance_column();
+        let challenge = meta.challenge_usable_after(FirstPhase);
+
+        meta.create_gate("shared subexpressions", |vc| {
+            let a_cur = vc.query_advice(a, Rotation::cur());
+            let a_next = vc.query_advice(a, Rotation::next());
+            let b_prev = vc.query_advice(b, Rotation::prev());
+            let f = vc.query_fixed(f, Rotation::cur());
+            let i = vc.query_instance(i, Rotation::cur());
+            let c = vc.query_challenge(challenge);
+
+            let ab = a_cur.clone() * b_prev.clone();
+            vec![
+                ab.clone() + f.clone(),
+                (ab.clone() + f) * Expression::Constant(Fr::from(3)),
+                ab - a_next.clone(),
+                -(a_next * c) + i,
+                b_prev,
+            ]
+        });
+        let expressions: Vec<_> = meta.gates()[0].polynomials().to_vec();
+
+        let domain = EvaluationDomain::<Fr>::new(1, 4);
+        let n = 1 << 4;
+        let random_column =
+            || domain.lagrange_from_vec((0..n).map(|_| Fr::random(OsRng)).collect());
+        let advice = vec![random_column(), random_column()];
+        let fixed = vec![random_column()];
+        let instance = vec![random_column()];
+        let challenges = vec![Fr::random(OsRng)];
+
+        let cached = evaluate_many(&expressions, n, 1, &fixed, &advice, &instance, &challenges);
+        assert_eq!(cached.len(), expressions.len());
+        for (expression, values) in expressions.iter().zip(cached.iter()) {
+            assert_eq!(
+                values,
+                &evaluate(expression, n, 1, &fixed, &advice, &instance, &challenges)
+            );
+        }
+    }
+}
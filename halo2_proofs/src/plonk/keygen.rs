@@ -24,6 +24,7 @@ use crate::{
     poly::{
         batch_invert_assigned,
         commitment::{Blind, Params, MSM},
+        kzg::commitment::ParamsKZG,
         EvaluationDomain,
     },
 };
@@ -65,6 +66,8 @@ struct Assembly<F: Field, E: MultiMillerLoop<Scalar = F>> {
     // A range of available rows for assignment and copies.
     usable_rows: Range<usize>,
     static_table_mapping: BTreeMap<StaticTableId<String>, StaticTable<E>>,
+    // The first id registered again with a different table, reported after synthesis
+    conflicting_static_table: Option<StaticTableId<String>>,
     ctx: SynthCtx,
     _marker: std::marker::PhantomData<F>,
 }
@@ -96,6 +99,17 @@ impl<F: Field, E: MultiMillerLoop<Scalar = F>> Assignment<F> for Assembly<F, E>
             }
         }
 
+        // Registering the same table again is harmless, a different one would overwrite it
+        if let Some(registered) = self.static_table_mapping.get(&id) {
+            let same = match self.ctx {
+                SynthCtx::Prover => registered.opened == static_table.opened,
+                SynthCtx::Verifier => registered.committed == static_table.committed,
+            };
+            if !same && self.conflicting_static_table.is_none() {
+                self.conflicting_static_table = Some(id.clone());
+            }
+        }
+
         self.static_table_mapping.insert(id, static_table);
     }
 
@@ -213,6 +227,7 @@ where
     E: MultiMillerLoop + Debug,
     E::G1Affine: SerdeObject,
     E::G2Affine: SerdeObject,
+    E::Scalar: SerdeObject,
     P: Params<'params, E::G1Affine>,
     ConcreteCircuit: Circuit<E>,
 {
@@ -229,6 +244,7 @@ where
         selectors: vec![vec![false; params.n() as usize]; cs.num_selectors],
         usable_rows: 0..params.n() as usize - (cs.blinding_factors() + 1),
         static_table_mapping: BTreeMap::default(),
+        conflicting_static_table: None,
         ctx: SynthCtx::Verifier,
         _marker: std::marker::PhantomData,
     };
@@ -241,6 +257,11 @@ where
         cs.constants.clone(),
     )?;
 
+    if let Some(id) = assembly.conflicting_static_table.take() {
+        return Err(Error::ConflictingStaticTable(id));
+    }
+    check_static_tables_registered(&cs, &assembly.static_table_mapping)?;
+
     let mut fixed = batch_invert_assigned(assembly.fixed);
     let (cs, selector_polys) = cs.compress_selectors(assembly.selectors.clone());
     fixed.extend(
@@ -274,9 +295,58 @@ where
     ))
 }
 
+/// Checks that every table referenced by a static lookup has been registered
+fn check_static_tables_registered<F: Field, T>(
+    cs: &ConstraintSystem<F>,
+    static_table_mapping: &BTreeMap<StaticTableId<String>, T>,
+) -> Result<(), Error> {
+    for argument in cs.static_lookups.iter() {
+        for table_id in argument.table_ids() {
+            if !static_table_mapping.contains_key(table_id) {
+                return Err(Error::UnregisteredStaticTable(table_id.clone()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that there is a config for the size of every table referenced by a static lookup
+fn check_static_table_configs<E: MultiMillerLoop>(
+    cs: &ConstraintSystem<E::Scalar>,
+    static_table_mapping: &BTreeMap<StaticTableId<String>, StaticTableValues<E>>,
+    static_table_configs: &BTreeMap<usize, StaticTableConfig<E>>,
+) -> Result<(), Error> {
+    for argument in cs.static_lookups.iter() {
+        for table_id in argument.table_ids() {
+            // presence of the table is checked in `check_static_tables_registered`
+            let size = static_table_mapping[table_id].size();
+            if !static_table_configs.contains_key(&size) {
+                return Err(Error::StaticLookupConfig(size));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks every config against the SRS of `params`
+fn check_static_table_configs_match_srs<E: MultiMillerLoop + Debug>(
+    params: &ParamsKZG<E>,
+    static_table_configs: &BTreeMap<usize, StaticTableConfig<E>>,
+) -> Result<(), Error> {
+    for (&size, config) in static_table_configs.iter() {
+        if !config.validate(params.g2(), params.s_g2()) {
+            return Err(Error::InvalidStaticTableConfig(size));
+        }
+    }
+
+    Ok(())
+}
+
 /// Generate a `ProvingKey` from a `VerifyingKey` and an instance of `Circuit`.
-pub fn keygen_pk<'params, E, P, ConcreteCircuit>(
-    params: &P,
+pub fn keygen_pk<E, ConcreteCircuit>(
+    params: &ParamsKZG<E>,
     static_table_configs: BTreeMap<usize, StaticTableConfig<E>>,
     b0_g1_bound: Vec<E::G1Affine>,
     vk: VerifyingKey<E>,
@@ -286,7 +356,6 @@ where
     E: MultiMillerLoop + Debug,
     E::G1Affine: SerdeObject,
     E::G2Affine: SerdeObject,
-    P: Params<'params, E::G1Affine>,
     ConcreteCircuit: Circuit<E>,
 {
     let mut cs = ConstraintSystem::default();
@@ -305,6 +374,7 @@ where
         selectors: vec![vec![false; params.n() as usize]; cs.num_selectors],
         usable_rows: 0..params.n() as usize - (cs.blinding_factors() + 1),
         static_table_mapping: BTreeMap::default(),
+        conflicting_static_table: None,
         ctx: SynthCtx::Prover,
         _marker: std::marker::PhantomData,
     };
@@ -317,6 +387,11 @@ where
         cs.constants.clone(),
     )?;
 
+    if let Some(id) = assembly.conflicting_static_table.take() {
+        return Err(Error::ConflictingStaticTable(id));
+    }
+    check_static_tables_registered(&cs, &assembly.static_table_mapping)?;
+
     let mut fixed = batch_invert_assigned(assembly.fixed);
     let (cs, selector_polys) = cs.compress_selectors(assembly.selectors);
     fixed.extend(
@@ -380,6 +455,9 @@ where
         .map(|(k, v)| (k.clone(), v.opened.clone().unwrap())) //safe to unwrap since this is checked in register_static_table method
         .collect();
 
+    check_static_table_configs(&cs, &static_table_mapping, &static_table_configs)?;
+    check_static_table_configs_match_srs(params, &static_table_configs)?;
+
     Ok(ProvingKey {
         vk,
         l0,
@@ -395,3 +473,151 @@ where
         b0_g1_bound,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use halo2curves::bn256::{Bn256, Fr};
+
+    use super::{create_domain, Assembly, SynthCtx};
+    use crate::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::{AssignmentCounts, CountingAssignment},
+        plonk::{
+            permutation, Advice, Circuit, Column, ConstraintSystem, Error, Fixed, FloorPlanner,
+            Selector,
+        },
+        poly::Rotation,
+    };
+
+    #[derive(Clone)]
+    struct CountedConfig {
+        a: Column<Advice>,
+        b: Column<Advice>,
+        c: Column<Fixed>,
+        q: Selector,
+    }
+
+    struct CountedCircuit;
+
+    impl Circuit<Bn256> for CountedCircuit {
+        type Config = CountedConfig;
+        type FloorPlanner = SimpleFloorPlanner<Bn256>;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let a = meta.advice_column();
+            let b = meta.advice_column();
+            let c = meta.fixed_column();
+            let q = meta.selector();
+            meta.enable_equality(a);
+            meta.enable_equality(b);
+
+            meta.create_gate("a + c = b", |vc| {
+                let a = vc.query_advice(a, Rotation::cur());
+                let b = vc.query_advice(b, Rotation::cur());
+                let c = vc.query_fixed(c, Rotation::cur());
+                let q = vc.query_selector(q);
+                vec![q * (a + c - b)]
+            });
+
+            CountedConfig { a, b, c, q }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            layouter.namespace(|| "first").assign_region(
+                || "add",
+                |mut region| {
+                    config.q.enable(&mut region, 0)?;
+                    config.q.enable(&mut region, 1)?;
+                    region.assign_fixed(config.c, 0, Fr::one());
+                    region.assign_fixed(config.c, 1, Fr::one());
+                    region.assign_advice(config.a, 0, Value::known(Fr::one()))?;
+                    let b = region.assign_advice(config.b, 0, Value::known(Fr::from(2)))?;
+                    let a = region.assign_advice(config.a, 1, Value::known(Fr::from(2)))?;
+                    region.assign_advice(config.b, 1, Value::known(Fr::from(3)))?;
+                    region.constrain_equal(b.cell(), a.cell());
+                    Ok(())
+                },
+            )?;
+
+            layouter.namespace(|| "second").assign_region(
+                || "add",
+                |mut region| {
+                    config.q.enable(&mut region, 0)?;
+                    region.assign_fixed(config.c, 0, Fr::zero());
+                    region.assign_advice(config.a, 0, Value::known(Fr::one()))?;
+                    region.assign_advice(config.b, 0, Value::known(Fr::one()))?;
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn counting_assignment_tallies_keygen_calls() {
+        const K: u32 = 4;
+        let n = 1usize << K;
+        let (domain, cs, config) = create_domain::<Bn256, CountedCircuit>(K);
+
+        let assembly: Assembly<Fr, Bn256> = Assembly {
+            k: K,
+            fixed: vec![domain.empty_lagrange_assigned(); cs.num_fixed_columns],
+            permutation: permutation::keygen::Assembly::new(n, &cs.permutation),
+            selectors: vec![vec![false; n]; cs.num_selectors],
+            usable_rows: 0..n - (cs.blinding_factors() + 1),
+            static_table_mapping: BTreeMap::default(),
+            conflicting_static_table: None,
+            ctx: SynthCtx::Verifier,
+            _marker: std::marker::PhantomData,
+        };
+        let mut counting = CountingAssignment::new(assembly);
+
+        <CountedCircuit as Circuit<Bn256>>::FloorPlanner::synthesize(
+            &mut counting,
+            &CountedCircuit,
+            config,
+            cs.constants.clone(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            counting.counts().get("first"),
+            Some(&AssignmentCounts {
+                advice: 4,
+                fixed: 2,
+                copies: 1,
+                selectors: 2,
+            })
+        );
+        assert_eq!(
+            counting.counts().get("second"),
+            Some(&AssignmentCounts {
+                advice: 2,
+                fixed: 1,
+                copies: 0,
+                selectors: 1,
+            })
+        );
+        assert_eq!(
+            counting.total(),
+            AssignmentCounts {
+                advice: 6,
+                fixed: 3,
+                copies: 1,
+                selectors: 3,
+            }
+        );
+
+        let assembly = counting.into_inner();
+        assert!(assembly.selectors[0][..2].iter().all(|&enabled| enabled));
+    }
+}
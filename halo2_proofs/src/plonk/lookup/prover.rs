@@ -5,7 +5,7 @@ use super::super::{
 use super::Argument;
 use crate::plonk::evaluation::evaluate;
 use crate::{
-    arithmetic::{eval_polynomial, parallelize, CurveAffine, FieldExt},
+    arithmetic::{eval_polynomial, eval_polynomial_batch, parallelize, CurveAffine, FieldExt},
     poly::{
         commitment::{Blind, Params},
         Coeff, EvaluationDomain, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial, ProverQuery,
@@ -333,10 +333,14 @@ impl<C: CurveAffine> Committed<C> {
         let x_inv = domain.rotate_omega(*x, Rotation::prev());
         let x_next = domain.rotate_omega(*x, Rotation::next());
 
-        let product_eval = eval_polynomial(&self.product_poly, *x);
-        let product_next_eval = eval_polynomial(&self.product_poly, x_next);
-        let permuted_input_eval = eval_polynomial(&self.permuted_input_poly, *x);
-        let permuted_input_inv_eval = eval_polynomial(&self.permuted_input_poly, x_inv);
+        let [product_eval, product_next_eval]: [_; 2] =
+            eval_polynomial_batch(&self.product_poly, &[*x, x_next])
+                .try_into()
+                .unwrap();
+        let [permuted_input_eval, permuted_input_inv_eval]: [_; 2] =
+            eval_polynomial_batch(&self.permuted_input_poly, &[*x, x_inv])
+                .try_into()
+                .unwrap();
         let permuted_table_eval = eval_polynomial(&self.permuted_table_poly, *x);
 
         // Hash each advice evaluation
@@ -2,8 +2,8 @@ use super::circuit::{Any, Column};
 use crate::{
     arithmetic::CurveAffine,
     helpers::{
-        polynomial_slice_byte_length, read_polynomial_vec, write_polynomial_slice,
-        SerdeCurveAffine, SerdePrimeField,
+        polynomial_slice_byte_length, read_point_vec, read_polynomial_vec, write_point_slice,
+        write_polynomial_slice, SerdeCurveAffine, SerdePrimeField,
     },
     poly::{Coeff, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial},
     SerdeFormat,
@@ -93,6 +93,10 @@ impl<C: CurveAffine> VerifyingKey<C> {
     where
         C: SerdeCurveAffine,
     {
+        if let SerdeFormat::Json = format {
+            return write_point_slice(&self.commitments, writer, format);
+        }
+
         for commitment in &self.commitments {
             commitment.write(writer, format);
         }
@@ -102,14 +106,30 @@ impl<C: CurveAffine> VerifyingKey<C> {
         reader: &mut R,
         argument: &Argument,
         format: SerdeFormat,
-    ) -> Self
+    ) -> io::Result<Self>
     where
         C: SerdeCurveAffine,
     {
-        let commitments = (0..argument.columns.len())
-            .map(|_| C::read(reader, format))
-            .collect();
-        VerifyingKey { commitments }
+        let commitments = match format {
+            SerdeFormat::Json => {
+                let commitments = read_point_vec(reader, format)?;
+                if commitments.len() != argument.columns.len() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "expected {} permutation commitments, read {}",
+                            argument.columns.len(),
+                            commitments.len()
+                        ),
+                    ));
+                }
+                commitments
+            }
+            _ => (0..argument.columns.len())
+                .map(|_| C::read(reader, format))
+                .collect::<io::Result<_>>()?,
+        };
+        Ok(VerifyingKey { commitments })
     }
 
     pub(crate) fn bytes_length(&self) -> usize {
@@ -130,15 +150,15 @@ where
     C::Scalar: SerdePrimeField,
 {
     /// Reads proving key for a single permutation argument from buffer using `Polynomial::read`.  
-    pub(super) fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> Self {
-        let permutations = read_polynomial_vec(reader, format);
-        let polys = read_polynomial_vec(reader, format);
-        let cosets = read_polynomial_vec(reader, format);
-        ProvingKey {
+    pub(super) fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> io::Result<Self> {
+        let permutations = read_polynomial_vec(reader, format)?;
+        let polys = read_polynomial_vec(reader, format)?;
+        let cosets = read_polynomial_vec(reader, format)?;
+        Ok(ProvingKey {
             permutations,
             polys,
             cosets,
-        }
+        })
     }
 
     /// Writes proving key for a single permutation argument to buffer using `Polynomial::write`.  
@@ -9,7 +9,7 @@ use std::iter::{self, ExactSizeIterator};
 use super::super::{circuit::Any, ChallengeBeta, ChallengeGamma, ChallengeX};
 use super::{Argument, ProvingKey};
 use crate::{
-    arithmetic::{eval_polynomial, parallelize, CurveAffine, FieldExt},
+    arithmetic::{eval_polynomial, eval_polynomial_batch, parallelize, CurveAffine, FieldExt},
     plonk::{self, Error},
     poly::{
         self,
@@ -258,12 +258,13 @@ impl<C: CurveAffine> Constructed<C> {
             let mut sets = self.sets.iter();
 
             while let Some(set) = sets.next() {
-                let permutation_product_eval = eval_polynomial(&set.permutation_product_poly, *x);
-
-                let permutation_product_next_eval = eval_polynomial(
-                    &set.permutation_product_poly,
-                    domain.rotate_omega(*x, Rotation::next()),
-                );
+                let [permutation_product_eval, permutation_product_next_eval]: [_; 2] =
+                    eval_polynomial_batch(
+                        &set.permutation_product_poly,
+                        &[*x, domain.rotate_omega(*x, Rotation::next())],
+                    )
+                    .try_into()
+                    .unwrap();
 
                 // Hash permutation product evals
                 for eval in iter::empty()
@@ -17,8 +17,8 @@ use std::{collections::HashMap, iter, mem, sync::atomic::Ordering};
 use super::{
     circuit::{
         sealed::{self, SealedPhase},
-        Advice, Any, Assignment, Challenge, Circuit, Column, ConstraintSystem, FirstPhase, Fixed,
-        FloorPlanner, Instance, Selector,
+        Advice, Any, Assignment, Challenge, Circuit, Column, ColumnType, ConstraintSystem,
+        FirstPhase, Fixed, FloorPlanner, Instance, Phase, Selector,
     },
     lookup, permutation, vanishing, ChallengeBeta, ChallengeGamma, ChallengeTheta, ChallengeX,
     ChallengeY, Error, Expression, ProvingKey,
@@ -29,13 +29,14 @@ use crate::poly::commitment::ParamsProver;
 use crate::poly::kzg::commitment::KZGCommitmentScheme;
 use crate::transcript::Transcript;
 use crate::{
-    arithmetic::{eval_polynomial, CurveAffine, FieldExt},
+    arithmetic::{eval_polynomial_batch, CurveAffine, FieldExt},
     circuit::Value,
     plonk::Assigned,
     poly::{
         self,
         commitment::{Blind, CommitmentScheme, Params, Prover},
-        Basis, Coeff, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial, ProverQuery,
+        Basis, Coeff, EvaluationDomain, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial,
+        ProverQuery, Rotation,
     },
 };
 use crate::{
@@ -48,6 +49,9 @@ use group::prime::PrimeCurveAffine;
 /// parameters `params` and the proving key [`ProvingKey`] that was
 /// generated previously for the same circuit. The provided `instances`
 /// are zero-padded internally.
+///
+/// If the circuit assigns instances during [`FirstPhase`], use
+/// [`create_proof_returning_instances`] to obtain the instances to verify with.
 pub fn create_proof<
     'params,
     'a,
@@ -62,9 +66,108 @@ pub fn create_proof<
     pk: &ProvingKey<E>,
     circuits: &[ConcreteCircuit],
     instances: &[&[&'a [E::Scalar]]],
+    rng: R,
+    transcript: &'a mut T,
+) -> Result<(), Error>
+where
+    E::G1Affine: SerdeObject,
+    E::G2Affine: SerdeObject,
+{
+    create_proof_returning_instances::<E, P, EC, R, T, ConcreteCircuit>(
+        params, pk, circuits, instances, rng, transcript,
+    )
+    .map(|_| ())
+}
+
+/// How the prover samples the blinding factors of the vanishing argument's
+/// commitments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlindingStrategy {
+    /// Blinding factors are drawn from the prover's rng.
+    Random,
+    /// The vanishing argument draws nothing from the rng. Its blinding factors are
+    /// zero, like the ones of the static lookup commitments, and h(X) is masked by
+    /// the constant polynomial one, as the commitment to zero cannot be written to
+    /// the transcript.
+    ///
+    /// The other arguments still blind with the rng, so proofs are reproducible
+    /// with a seeded rng.
+    Zero,
+}
+
+impl BlindingStrategy {
+    /// Returns a blinding factor, drawn from `rng` unless blinding is zero
+    pub(in crate::plonk) fn blind<F: Field, R: RngCore>(self, rng: R) -> Blind<F> {
+        match self {
+            BlindingStrategy::Random => Blind(F::random(rng)),
+            BlindingStrategy::Zero => Blind(F::zero()),
+        }
+    }
+}
+
+/// Creates a proof like [`create_proof`], and returns the instances of each
+/// circuit as they were absorbed into the transcript, arranged as
+/// `[circuit][column][row]`.
+///
+/// A circuit may overwrite the provided `instances` during [`FirstPhase`]
+/// synthesis with [`Layouter::assign_instance`], e.g. to expose a value it
+/// derives from its advice. The returned instances contain those values and
+/// are the ones the proof verifies against.
+///
+/// [`Layouter::assign_instance`]: crate::circuit::Layouter::assign_instance
+pub fn create_proof_returning_instances<
+    'params,
+    'a,
+    E: MultiMillerLoop + Debug,
+    P: Prover<'params, E>,
+    EC: EncodedChallenge<E::G1Affine>,
+    R: RngCore + 'a,
+    T: TranscriptWrite<E::G1Affine, EC>,
+    ConcreteCircuit: Circuit<E>,
+>(
+    params: &'params <KZGCommitmentScheme<E> as CommitmentScheme>::ParamsProver,
+    pk: &ProvingKey<E>,
+    circuits: &[ConcreteCircuit],
+    instances: &[&[&'a [E::Scalar]]],
+    rng: R,
+    transcript: &'a mut T,
+) -> Result<Vec<Vec<Vec<E::Scalar>>>, Error>
+where
+    E::G1Affine: SerdeObject,
+    E::G2Affine: SerdeObject,
+{
+    create_proof_with_blinding::<E, P, EC, R, T, ConcreteCircuit>(
+        params,
+        pk,
+        circuits,
+        instances,
+        BlindingStrategy::Random,
+        rng,
+        transcript,
+    )
+}
+
+/// Creates a proof like [`create_proof_returning_instances`], blinding the
+/// vanishing argument according to `blinding`.
+#[allow(clippy::too_many_arguments)]
+pub fn create_proof_with_blinding<
+    'params,
+    'a,
+    E: MultiMillerLoop + Debug,
+    P: Prover<'params, E>,
+    EC: EncodedChallenge<E::G1Affine>,
+    R: RngCore + 'a,
+    T: TranscriptWrite<E::G1Affine, EC>,
+    ConcreteCircuit: Circuit<E>,
+>(
+    params: &'params <KZGCommitmentScheme<E> as CommitmentScheme>::ParamsProver,
+    pk: &ProvingKey<E>,
+    circuits: &[ConcreteCircuit],
+    instances: &[&[&'a [E::Scalar]]],
+    blinding: BlindingStrategy,
     mut rng: R,
     mut transcript: &'a mut T,
-) -> Result<(), Error>
+) -> Result<Vec<Vec<Vec<E::Scalar>>>, Error>
 where
     E::G1Affine: SerdeObject,
     E::G2Affine: SerdeObject,
@@ -97,10 +200,12 @@ where
         pub instance_polys: Vec<Polynomial<C::Scalar, Coeff>>,
     }
 
-    let instance: Vec<InstanceSingle<E::G1Affine>> = instances
+    // The instance values may still be assigned during `FirstPhase` synthesis, so
+    // they are interpolated only once the advice has been committed.
+    let mut instance_values: Vec<Vec<Polynomial<E::Scalar, LagrangeCoeff>>> = instances
         .iter()
-        .map(|instance| -> Result<InstanceSingle<E::G1Affine>, Error> {
-            let instance_values = instance
+        .map(|instance| {
+            instance
                 .iter()
                 .map(|values| {
                     let mut poly = domain.empty_lagrange();
@@ -113,22 +218,9 @@ where
                     }
                     poly
                 })
-                .collect::<Vec<_>>();
-
-            let instance_polys: Vec<_> = instance_values
-                .iter()
-                .map(|poly| {
-                    let lagrange_vec = domain.lagrange_from_vec(poly.to_vec());
-                    domain.lagrange_to_coeff(lagrange_vec)
-                })
-                .collect();
-
-            Ok(InstanceSingle {
-                instance_values,
-                instance_polys,
-            })
+                .collect()
         })
-        .collect::<Result<Vec<_>, _>>()?;
+        .collect();
 
     #[derive(Clone)]
     struct AdviceSingle<C: CurveAffine, B: Basis> {
@@ -151,9 +243,9 @@ where
         advice: Vec<Polynomial<Assigned<E::Scalar>, LagrangeCoeff>>,
         challenges: &'b mut HashMap<usize, E::Scalar>,
         instances: &'b [&'a [E::Scalar]],
+        instance_values: &'b mut [Polynomial<E::Scalar, LagrangeCoeff>],
         usable_rows: RangeTo<usize>,
         advice_single: AdviceSingle<E::G1Affine, LagrangeCoeff>,
-        instance_single: &'b InstanceSingle<E::G1Affine>,
         rng: &'b mut R,
         transcript: &'b mut &'a mut T,
         column_indices: [Vec<usize>; 3],
@@ -215,11 +307,47 @@ where
 
             self.instances
                 .get(column.index())
-                .and_then(|column| column.get(row))
-                .map(|v| Value::known(*v))
+                .filter(|values| row < values.len())
+                .map(|_| Value::known(self.instance_values[column.index()][row]))
                 .ok_or(Error::BoundsFailure)
         }
 
+        fn assign_instance(
+            &mut self,
+            column: Column<Instance>,
+            row: usize,
+            to: Value<E::Scalar>,
+        ) -> Result<(), Error> {
+            if !self.usable_rows.contains(&row) {
+                return Err(Error::not_enough_rows_available(self.params.k()));
+            }
+
+            // Only the rows of the provided instances are absorbed into the transcript
+            if !self
+                .instances
+                .get(column.index())
+                .map_or(false, |values| row < values.len())
+            {
+                return Err(Error::BoundsFailure);
+            }
+
+            let cell = &mut self.instance_values[column.index()][row];
+            if self.current_phase.to_u8() > 0 {
+                // The instances were already absorbed; circuits synthesized once per
+                // phase may only assign the same values again.
+                return match to.assign() {
+                    Ok(value) if value != *cell => Err(Error::InstanceAfterFirstPhase {
+                        column: column.into(),
+                        row,
+                    }),
+                    _ => Ok(()),
+                };
+            }
+
+            *cell = to.assign()?;
+            Ok(())
+        }
+
         fn assign_advice<'r, 'v>(
             //<V, VR, A, AR>(
             &'r mut self,
@@ -252,9 +380,10 @@ where
                     .get_unchecked_mut(row)
             };
             */
-            *advice_get_mut = to
-                .assign()
-                .expect("No Value::unknown() in advice column allowed during create_proof");
+            *advice_get_mut = to.assign().map_err(|_| Error::UnknownAdvice {
+                column: column.into(),
+                row,
+            })?;
             let immutable_raw_ptr = advice_get_mut as *const Assigned<E::Scalar>;
             Ok(Value::known(unsafe { &*immutable_raw_ptr }))
         }
@@ -296,6 +425,13 @@ where
             // Do nothing; we don't care about namespaces in this context.
         }
 
+        fn enter_phase<Ph: Phase>(&mut self, phase: Ph) {
+            let phase = phase.to_sealed();
+            while self.current_phase < phase {
+                self.next_phase();
+            }
+        }
+
         fn next_phase(&mut self) {
             let phase = self.current_phase.to_u8() as usize;
             if phase == 0 {
@@ -303,20 +439,16 @@ where
                 // Do this here and not earlier in case we want to be able to mutate
                 // the instances during synthesize in FirstPhase in the future
                 if !P::QUERY_INSTANCE {
-                    for values in self.instances.iter() {
-                        for value in values.iter() {
+                    for (values, poly) in self.instances.iter().zip(self.instance_values.iter()) {
+                        for value in poly[..values.len()].iter() {
                             self.transcript
                                 .common_scalar(*value)
                                 .expect("Absorbing instance value to transcript failed");
                         }
                     }
                 } else {
-                    let instance_commitments_projective: Vec<_> = self
-                        .instance_single
-                        .instance_values
-                        .iter()
-                        .map(|poly| self.params.commit_lagrange(poly, Blind::default()))
-                        .collect();
+                    let instance_commitments_projective =
+                        self.params.commit_lagrange_batch(self.instance_values);
                     let mut instance_commitments =
                         vec![E::G1Affine::identity(); instance_commitments_projective.len()];
                     <E::G1Affine as CurveAffine>::CurveExt::batch_normalize(
@@ -353,11 +485,8 @@ where
                 .iter()
                 .map(|_| Blind(E::Scalar::random(&mut self.rng)))
                 .collect();
-            let advice_commitments_projective: Vec<_> = advice_values
-                .iter()
-                .zip(blinds.iter())
-                .map(|(poly, blind)| self.params.commit_lagrange(poly, *blind))
-                .collect();
+            // KZG commitments are not blinded, see `ParamsKZG::commit_lagrange`
+            let advice_commitments_projective = self.params.commit_lagrange_batch(&advice_values);
             let mut advice_commitments =
                 vec![E::G1Affine::identity(); advice_commitments_projective.len()];
             <E::G1Affine as CurveAffine>::CurveExt::batch_normalize(
@@ -415,14 +544,17 @@ where
             1,
             "New challenge API doesn't work with multiple circuits yet"
         );
-        for ((circuit, instances), instance_single) in
-            circuits.iter().zip(instances).zip(instance.iter())
+        for ((circuit, instances), instance_values) in circuits
+            .iter()
+            .zip(instances)
+            .zip(instance_values.iter_mut())
         {
             let mut witness: WitnessCollection<E, P, EC, _, _> = WitnessCollection {
                 params,
                 current_phase: phases[0],
                 advice: vec![domain.empty_lagrange_assigned(); meta.num_advice_columns],
                 instances,
+                instance_values,
                 challenges: &mut challenges,
                 // The prover will not be allowed to assign values to advice
                 // cells that exist within inactive rows, which include some
@@ -433,7 +565,6 @@ where
                     advice_polys: vec![domain.empty_lagrange(); meta.num_advice_columns],
                     advice_blinds: vec![Blind::default(); meta.num_advice_columns],
                 },
-                instance_single,
                 rng: &mut rng,
                 transcript: &mut transcript,
                 column_indices: column_indices.clone(),
@@ -451,8 +582,7 @@ where
                     circuit,
                     config.clone(),
                     meta.constants.clone(),
-                )
-                .unwrap();
+                )?;
                 if witness.current_phase.to_u8() < num_phases as u8 {
                     witness.next_phase();
                 }
@@ -468,6 +598,36 @@ where
         (advice, challenges)
     };
 
+    let assigned_instances: Vec<Vec<Vec<E::Scalar>>> = instances
+        .iter()
+        .zip(instance_values.iter())
+        .map(|(instance, instance_values)| {
+            instance
+                .iter()
+                .zip(instance_values.iter())
+                .map(|(values, poly)| poly[..values.len()].to_vec())
+                .collect()
+        })
+        .collect();
+
+    let instance: Vec<InstanceSingle<E::G1Affine>> = instance_values
+        .into_iter()
+        .map(|instance_values| {
+            let instance_polys: Vec<_> = instance_values
+                .iter()
+                .map(|poly| {
+                    let lagrange_vec = domain.lagrange_from_vec(poly.to_vec());
+                    domain.lagrange_to_coeff(lagrange_vec)
+                })
+                .collect();
+
+            InstanceSingle {
+                instance_values,
+                instance_polys,
+            }
+        })
+        .collect();
+
     // Sample theta challenge for keeping lookup columns linearly independent
     let theta: ChallengeTheta<_> = transcript.squeeze_challenge_scalar();
 
@@ -578,7 +738,7 @@ where
         .collect::<Result<Vec<_>, _>>()?;
 
     // Commit to the vanishing argument's random polynomial for blinding h(x_3)
-    let vanishing = vanishing::Argument::commit(params, domain, &mut rng, transcript)?;
+    let vanishing = vanishing::Argument::commit(params, domain, blinding, &mut rng, transcript)?;
 
     // Obtain challenge for keeping all separate gates linearly independent
     let y: ChallengeY<_> = transcript.squeeze_challenge_scalar();
@@ -624,7 +784,7 @@ where
     );
 
     // Construct the vanishing argument's h(X) commitments
-    let vanishing = vanishing.construct(params, domain, h_poly, &mut rng, transcript)?;
+    let vanishing = vanishing.construct(params, domain, h_poly, blinding, &mut rng, transcript)?;
 
     let x: ChallengeX<_> = transcript.squeeze_challenge_scalar();
     let xn = x.pow(&[params.n(), 0, 0, 0]);
@@ -633,16 +793,8 @@ where
         // Compute and hash instance evals for each circuit instance
         for instance in instance.iter() {
             // Evaluate polynomials at omega^i x
-            let instance_evals: Vec<_> = meta
-                .instance_queries
-                .iter()
-                .map(|&(column, at)| {
-                    eval_polynomial(
-                        &instance.instance_polys[column.index()],
-                        domain.rotate_omega(*x, at),
-                    )
-                })
-                .collect();
+            let instance_evals =
+                evaluate_queries(&instance.instance_polys, &meta.instance_queries, domain, *x);
 
             // Hash each instance column evaluation
             for eval in instance_evals.iter() {
@@ -654,16 +806,7 @@ where
     // Compute and hash advice evals for each circuit instance
     for advice in advice.iter() {
         // Evaluate polynomials at omega^i x
-        let advice_evals: Vec<_> = meta
-            .advice_queries
-            .iter()
-            .map(|&(column, at)| {
-                eval_polynomial(
-                    &advice.advice_polys[column.index()],
-                    domain.rotate_omega(*x, at),
-                )
-            })
-            .collect();
+        let advice_evals = evaluate_queries(&advice.advice_polys, &meta.advice_queries, domain, *x);
 
         // Hash each advice column evaluation
         for eval in advice_evals.iter() {
@@ -672,13 +815,7 @@ where
     }
 
     // Compute and hash fixed evals (shared across all circuit instances)
-    let fixed_evals: Vec<_> = meta
-        .fixed_queries
-        .iter()
-        .map(|&(column, at)| {
-            eval_polynomial(&pk.fixed_polys[column.index()], domain.rotate_omega(*x, at))
-        })
-        .collect();
+    let fixed_evals = evaluate_queries(&pk.fixed_polys, &meta.fixed_queries, domain, *x);
 
     // Hash each fixed column evaluation
     for eval in fixed_evals.iter() {
@@ -775,5 +912,35 @@ where
     let prover = P::new(params);
     prover
         .create_proof(&mut rng, transcript, instances)
-        .map_err(|_| Error::ConstraintSystemFailure)
+        .map_err(|_| Error::ConstraintSystemFailure)?;
+
+    Ok(assigned_instances)
+}
+
+/// Evaluates the polynomials of the queried columns at `x` rotated by each
+/// query, in the order of `queries`. A column queried at several rotations is
+/// evaluated at all of them at once.
+fn evaluate_queries<F: Field, T: ColumnType>(
+    polys: &[Polynomial<F, Coeff>],
+    queries: &[(Column<T>, Rotation)],
+    domain: &EvaluationDomain<F>,
+    x: F,
+) -> Vec<F> {
+    let mut points_by_column = HashMap::<usize, Vec<(usize, F)>>::new();
+    for (query_index, &(column, at)) in queries.iter().enumerate() {
+        points_by_column
+            .entry(column.index())
+            .or_default()
+            .push((query_index, domain.rotate_omega(x, at)));
+    }
+
+    let mut evals = vec![F::zero(); queries.len()];
+    for (column, points) in points_by_column {
+        let (query_indices, points): (Vec<_>, Vec<_>) = points.into_iter().unzip();
+        let column_evals = eval_polynomial_batch(&polys[column], &points);
+        for (query_index, eval) in query_indices.into_iter().zip(column_evals) {
+            evals[query_index] = eval;
+        }
+    }
+    evals
 }
@@ -1,5 +1,5 @@
-use ff::Field;
-use group::prime::PrimeCurveAffine;
+use ff::{Field, PrimeField};
+use group::{prime::PrimeCurveAffine, Curve, GroupEncoding};
 use halo2curves::{
     pairing::{Engine, MultiMillerLoop},
     FieldExt,
@@ -9,16 +9,54 @@ use rand_core::OsRng;
 pub(crate) mod prover;
 pub(crate) mod verifier;
 
-use std::{collections::BTreeMap, io};
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+    io,
+};
 
 use crate::{
-    arithmetic::{best_multiexp, kate_division},
-    helpers::SerdePrimeField,
-    poly::{kzg::commitment::ParamsKZG, EvaluationDomain},
+    arithmetic::{best_multiexp, kate_division_per_root},
+    helpers::{
+        json, point_slice_byte_length, read_point_vec, write_point_slice, SerdeCurveAffine,
+        SerdePrimeField,
+    },
+    poly::{
+        kzg::commitment::{pairing_equal, ParamsKZG},
+        EvaluationDomain,
+    },
     SerdeFormat,
 };
 
-use super::Expression;
+use super::{Error, Expression};
+
+/// Compresses the values of the columns of a vector lookup into
+/// `theta^(m - 1) * values[0] + ... + theta * values[m - 2] + values[m - 1]`
+///
+/// Prover and verifier both compress with it, so that they agree on the order of the
+/// powers of `theta`. As a Horner evaluation, it costs one multiplication per value.
+pub(crate) fn compress_with_theta<F: Field>(values: impl IntoIterator<Item = F>, theta: F) -> F {
+    values
+        .into_iter()
+        .fold(F::zero(), |acc, value| acc * theta + value)
+}
+
+/// The log-derivative identity `b * (l_active * f + (1 - l_active) * t_0 + beta) - 1`,
+/// which vanishes on every row of the circuit domain
+///
+/// `l_active` acts as the selector of the lookup: active rows look up `f`, while the
+/// blinding rows and the last row look up the default value `t_0` of the tables, whatever
+/// `f` holds there. The verifier computes `l_active` from the Lagrange basis, so it
+/// needs no fixed column of its own.
+pub(crate) fn log_derivative_identity<F: Field>(
+    b: F,
+    f: F,
+    l_active: F,
+    default_value: F,
+    beta: F,
+) -> F {
+    b * (l_active * f + (F::one() - l_active) * default_value + beta) - F::one()
+}
 
 pub fn is_pow_2(x: usize) -> bool {
     (x & (x - 1)) == 0
@@ -34,6 +72,46 @@ pub struct StaticTable<E: MultiMillerLoop> {
     pub committed: Option<StaticCommittedTable<E>>,
 }
 
+impl<E: MultiMillerLoop> StaticTable<E> {
+    /// Fills in `committed` from `opened`, tables without values are left untouched
+    pub fn commit(
+        &mut self,
+        srs_g1_len: usize,
+        srs_g2: &[E::G2Affine],
+        circuit_domain: usize,
+    ) -> Result<(), Error> {
+        if let Some(opened) = &self.opened {
+            self.committed = Some(opened.commit(srs_g1_len, srs_g2, circuit_domain)?);
+        }
+        Ok(())
+    }
+
+    /// Commits every table in `tables`, see [`StaticTable::commit`]
+    pub fn commit_all<'a>(
+        tables: impl IntoIterator<Item = &'a mut Self>,
+        srs_g1_len: usize,
+        srs_g2: &[E::G2Affine],
+        circuit_domain: usize,
+    ) -> Result<(), Error>
+    where
+        E: 'a,
+    {
+        for table in tables {
+            table.commit(srs_g1_len, srs_g2, circuit_domain)?;
+        }
+        Ok(())
+    }
+
+    /// Value at index 0 of the table, looked up by the blinding rows. Rows of the
+    /// circuit that do not use the lookup should be padded with it as well.
+    pub fn default_value(&self) -> Option<E::Scalar> {
+        self.opened
+            .as_ref()
+            .map(StaticTableValues::default_value)
+            .or_else(|| self.committed.as_ref().map(|table| table.default_value))
+    }
+}
+
 /// Abstract type that allows to store MAP(table_id => static_table) in proving(verifying) key
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct StaticTableId<T: Clone + Ord>(pub T);
@@ -44,6 +122,29 @@ impl<T: Clone + Ord> StaticTableId<T> {
     }
 }
 
+impl StaticTableId<String> {
+    /// Writes the id as a length-prefixed UTF-8 string.
+    pub(crate) fn write<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&(self.0.len() as u32).to_be_bytes())?;
+        writer.write_all(self.0.as_bytes())
+    }
+
+    /// Reads an id written by [`Self::write`].
+    pub(crate) fn read<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let mut len = [0u8; 4];
+        reader.read_exact(&mut len)?;
+        let mut bytes = vec![0u8; u32::from_be_bytes(len) as usize];
+        reader.read_exact(&mut bytes)?;
+        String::from_utf8(bytes)
+            .map(StaticTableId)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub(crate) fn bytes_length(&self) -> usize {
+        4 + self.0.len()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StaticTableConfig<E: MultiMillerLoop> {
     size: usize,
@@ -51,6 +152,16 @@ pub struct StaticTableConfig<E: MultiMillerLoop> {
     g_lagrange_opening_at_0: Vec<E::G1Affine>,
 }
 
+impl<E: MultiMillerLoop> PartialEq for StaticTableConfig<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size
+            && self.g1_lagrange == other.g1_lagrange
+            && self.g_lagrange_opening_at_0 == other.g_lagrange_opening_at_0
+    }
+}
+
+impl<E: MultiMillerLoop> Eq for StaticTableConfig<E> {}
+
 impl<E: MultiMillerLoop> StaticTableConfig<E> {
     pub fn new(
         size: usize,
@@ -63,6 +174,70 @@ impl<E: MultiMillerLoop> StaticTableConfig<E> {
             g_lagrange_opening_at_0,
         }
     }
+
+    /// Size of the tables this config can be used with
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub(crate) fn bytes_length(&self) -> usize {
+        4 + point_slice_byte_length(&self.g1_lagrange)
+            + point_slice_byte_length(&self.g_lagrange_opening_at_0)
+    }
+
+    /// Checks that the config matches the SRS with generator `g2` and secret `s_g2` on G2:
+    /// `g_lagrange_opening_at_0[i]` must be `[(L_i(x) - L_i(0)) / x]_1`, with `L_i(0) = 1 / size`
+    ///   e(opening_i, [x]_2) == e([L_i(x)]_1 - [1 / size]_1, [1]_2)
+    ///
+    /// Every index is covered at once with a random linear combination.
+    pub fn validate(&self, g2: E::G2Affine, s_g2: E::G2Affine) -> bool {
+        let n = self.size;
+        if n == 0
+            || !is_pow_2(n)
+            || self.g1_lagrange.len() != n
+            || self.g_lagrange_opening_at_0.len() != n
+        {
+            return false;
+        }
+
+        let r: Vec<E::Scalar> = (0..n).map(|_| E::Scalar::random(OsRng)).collect();
+        let r_sum = r.iter().fold(E::Scalar::zero(), |acc, r_i| acc + r_i);
+        let n_inv = E::Scalar::from(n as u64).invert().unwrap();
+
+        let openings = best_multiexp(&r, &self.g_lagrange_opening_at_0).to_affine();
+        let lagrange = (best_multiexp(&r, &self.g1_lagrange)
+            - E::G1Affine::generator() * (r_sum * n_inv))
+            .to_affine();
+
+        pairing_equal::<E>((openings, s_g2), (lagrange, g2))
+    }
+}
+
+impl<E: MultiMillerLoop> StaticTableConfig<E>
+where
+    E::G1Affine: SerdeCurveAffine,
+{
+    /// Writes the config to a buffer.
+    pub(crate) fn write<W: io::Write>(&self, writer: &mut W, format: SerdeFormat) {
+        writer.write_all(&(self.size as u32).to_be_bytes()).unwrap();
+        write_point_slice(&self.g1_lagrange, writer, format);
+        write_point_slice(&self.g_lagrange_opening_at_0, writer, format);
+    }
+
+    /// Reads a config written by [`Self::write`].
+    pub(crate) fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> io::Result<Self> {
+        let mut size = [0u8; 4];
+        reader.read_exact(&mut size)?;
+        let size = u32::from_be_bytes(size) as usize;
+        let g1_lagrange = read_point_vec(reader, format)?;
+        let g_lagrange_opening_at_0 = read_point_vec(reader, format)?;
+
+        Ok(Self {
+            size,
+            g1_lagrange,
+            g_lagrange_opening_at_0,
+        })
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -72,17 +247,47 @@ pub struct StaticTableValues<E: MultiMillerLoop> {
     value_index_mapping: BTreeMap<E::Scalar, usize>,
     // quotient commitments
     qs: Vec<E::G1>,
+    // domain of the table, reused across commitments
+    domain: EvaluationDomain<E::Scalar>,
+}
+
+// The quotient commitments and the domain are derived from the values
+impl<E: MultiMillerLoop> PartialEq for StaticTableValues<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.value_index_mapping == other.value_index_mapping
+    }
+}
+
+impl<E: MultiMillerLoop> Eq for StaticTableValues<E> {}
+
+// Consistent with `PartialEq`, so that identical tables can be committed once
+impl<E: MultiMillerLoop> Hash for StaticTableValues<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.size.hash(state);
+        for (value, index) in self.value_index_mapping.iter() {
+            value.to_repr().as_ref().hash(state);
+            index.hash(state);
+        }
+    }
 }
 
 impl<E: MultiMillerLoop> StaticTableValues<E> {
-    pub fn new(values: &[E::Scalar], srs_g1: &[E::G1Affine]) -> Self {
+    /// Opens a table of `values`, computing its quotient commitments with `srs_g1`
+    ///
+    /// Fails with [`Error::TableSizeNotPowerOfTwo`] unless the number of values is a
+    /// power of two, and with [`Error::DuplicateTableValue`] if a value repeats.
+    pub fn new(values: &[E::Scalar], srs_g1: &[E::G1Affine]) -> Result<Self, Error> {
         let size = values.len();
-        assert!(is_pow_2(size));
+        if size == 0 || !is_pow_2(size) {
+            return Err(Error::TableSizeNotPowerOfTwo(size));
+        }
 
-        let value_index_mapping: BTreeMap<E::Scalar, usize> =
-            values.iter().enumerate().map(|(i, &f)| (f, i)).collect();
-        let keys_len: usize = value_index_mapping.keys().len();
-        assert_eq!(size, keys_len); // check that table is all unique values
+        let mut value_index_mapping = BTreeMap::new();
+        for (i, &f) in values.iter().enumerate() {
+            if value_index_mapping.insert(f, i).is_some() {
+                return Err(Error::DuplicateTableValue(i));
+            }
+        }
 
         // compute all qs
         let domain = EvaluationDomain::<E::Scalar>::new(2, log2(size));
@@ -105,55 +310,259 @@ impl<E: MultiMillerLoop> StaticTableValues<E> {
         );
 
         // TODO: THIS SHOULD BE DONE WITH FK METHOD
-        let qs: Vec<E::G1> = roots_of_unity
-            .iter()
-            .map(|&g_i| {
-                let quotient = kate_division(&table_coeffs, g_i);
-                let quotient = quotient
-                    .iter()
-                    .map(|&v| v * g_i * n_inv)
-                    .collect::<Vec<_>>();
+        let qs: Vec<E::G1> =
+            kate_division_per_root(&table_coeffs, &roots_of_unity, |&g_i, mut quotient| {
+                let scale = g_i * n_inv;
+                for v in quotient.iter_mut() {
+                    *v *= scale;
+                }
 
                 best_multiexp(&quotient, &srs_g1[..quotient.len()])
-            })
-            .collect();
+            });
 
-        Self {
+        Ok(Self {
             size,
             value_index_mapping,
             qs,
-        }
+            domain,
+        })
+    }
+
+    /// Like [`Self::new`], but moves `default_value` to index 0 so that it becomes
+    /// the value looked up by inactive rows
+    ///
+    /// Returns [`Error::DefaultValueNotInTable`] if `default_value` is not one of `values`.
+    pub fn with_default(
+        values: &[E::Scalar],
+        default_value: E::Scalar,
+        srs_g1: &[E::G1Affine],
+    ) -> Result<Self, Error> {
+        let position = values
+            .iter()
+            .position(|&value| value == default_value)
+            .ok_or(Error::DefaultValueNotInTable)?;
+        let mut values = values.to_vec();
+        values.swap(0, position);
+        Self::new(&values, srs_g1)
+    }
+
+    /// Builds a table of `size` values where the value at index `i` is `f(i)`, see
+    /// [`Self::new`]
+    pub fn from_fn(
+        size: usize,
+        f: impl Fn(usize) -> E::Scalar,
+        srs_g1: &[E::G1Affine],
+    ) -> Result<Self, Error> {
+        let values: Vec<E::Scalar> = (0..size).map(f).collect();
+        Self::new(&values, srs_g1)
+    }
+
+    /// Number of values in the table
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Value at index 0, looked up by all inactive rows of the circuit
+    pub fn default_value(&self) -> E::Scalar {
+        self.value_index_mapping
+            .iter()
+            .find(|(_, &index)| index == 0)
+            .map(|(&value, _)| value)
+            .expect("Table is not empty")
+    }
+
+    /// Index at which `value` is stored in the table, if present
+    pub(crate) fn index_of(&self, value: &E::Scalar) -> Option<usize> {
+        self.value_index_mapping.get(value).copied()
+    }
+
+    pub(crate) fn bytes_length(&self) -> usize {
+        let scalar_len = E::Scalar::default().to_repr().as_ref().len();
+        4 + self.size * scalar_len
+            + 4
+            + self.qs.len() * E::G1Affine::default().to_bytes().as_ref().len()
     }
 
+    /// Commits the table in G2 for a circuit with `circuit_domain` rows, where `srs_g1_len`
+    /// is the number of G1 points of the SRS the table and the `b0_g1_bound` of the proving
+    /// key come from.
+    ///
+    /// Fails with [`Error::StaticTableDomain`] unless
+    /// `2 <= circuit_domain <= srs_g1_len`, the table fits in `srs_g1_len`, and `srs_g2`
+    /// has the powers `[x^size]_2` and `[x^{srs_g1_len - 1 - (circuit_domain - 2)}]_2`.
     pub fn commit(
         &self,
         srs_g1_len: usize,
         srs_g2: &[E::G2Affine],
         circuit_domain: usize,
-    ) -> StaticCommittedTable<E> {
-        let domain = EvaluationDomain::<E::Scalar>::new(2, log2(self.size));
-        // zv = x^n - 1
-        assert!(is_pow_2(self.size));
-        let zv = srs_g2[self.size] - srs_g2[0];
+    ) -> Result<StaticCommittedTable<E>, Error> {
+        // Tables read from a buffer were not checked by `Self::new`
+        if self.size == 0 || !is_pow_2(self.size) {
+            return Err(Error::TableSizeNotPowerOfTwo(self.size));
+        }
 
-        let mut table_coeffs: Vec<E::Scalar> = self.value_index_mapping.keys().cloned().collect();
-        EvaluationDomain::<E::Scalar>::ifft(
-            table_coeffs.as_mut_slice(),
-            domain.get_omega_inv(),
-            log2(self.size),
-            domain.ifft_divisor(),
-        );
-        let t = best_multiexp(&table_coeffs, &srs_g2[..table_coeffs.len()]);
         // NOTE: B0 bound is computed generically based on srs size instead of just table size SRS
-        // this allows using longer srs or just having multiple tables with different lengths
+        // this allows using longer srs or just having multiple tables with different lengths.
+        // B0 has degree at most `circuit_domain - 2`, which must fit below the top of the SRS.
+        let domain_error = Error::StaticTableDomain {
+            table_size: self.size,
+            circuit_domain,
+            srs_g1_len,
+            srs_g2_len: srs_g2.len(),
+        };
+        if circuit_domain < 2 || circuit_domain > srs_g1_len || self.size > srs_g1_len {
+            return Err(domain_error);
+        }
         let b0_bound_index = srs_g1_len - 1 - (circuit_domain - 2);
+        if srs_g2.len() <= self.size.max(b0_bound_index) {
+            return Err(domain_error);
+        }
 
-        StaticCommittedTable {
+        // zv = x^n - 1
+        let zv = srs_g2[self.size] - srs_g2[0];
+
+        let t = self.commit_t(srs_g2);
+
+        Ok(StaticCommittedTable {
             zv: zv.into(),
             t: t.into(),
             x_b0_bound: srs_g2[b0_bound_index],
             size: srs_g1_len,
+            default_value: self.default_value(),
+        })
+    }
+
+    /// Checks that `committed` was produced from these values: `t` and `zv` are
+    /// recomputed against `srs_g2`, and the quotient commitments `qs` are checked
+    /// to open the committed `t` at every root of unity, i.e.
+    /// `e(q_i, [x - w^i]_2) == e([w^i / n]_1, t - [t_i]_2)`, batched with random
+    /// coefficients into a single pairing check.
+    ///
+    /// `x_b0_bound` depends on the circuit domain and is not checked.
+    pub fn verify_commitment(
+        &self,
+        committed: &StaticCommittedTable<E>,
+        srs_g2: &[E::G2Affine],
+    ) -> bool {
+        if srs_g2.len() <= self.size || self.qs.len() != self.size {
+            return false;
+        }
+
+        let zv: E::G2Affine = (srs_g2[self.size] - srs_g2[0]).into();
+        let t: E::G2Affine = self.commit_t(srs_g2).into();
+        if committed.zv != zv || committed.t != t || committed.default_value != self.default_value()
+        {
+            return false;
+        }
+
+        let n_inv = E::Scalar::from(self.size as u64).invert().unwrap();
+        let values = self.values();
+        let (mut a, mut b) = (vec![], vec![]);
+        let (mut c, mut d) = (E::Scalar::zero(), E::Scalar::zero());
+        let mut w_i = E::Scalar::one();
+        for value in values.iter() {
+            let r = E::Scalar::random(OsRng);
+            a.push(r);
+            b.push(r * w_i);
+            c += r * w_i * n_inv;
+            d += r * w_i * n_inv * value;
+            w_i *= self.domain.get_omega();
+        }
+
+        let mut qs = vec![E::G1Affine::identity(); self.size];
+        E::G1::batch_normalize(&self.qs, &mut qs);
+        let a: E::G1Affine = best_multiexp(&a, &qs).into();
+        let g1 = E::G1Affine::generator();
+        let b: E::G1Affine = (g1 * d - best_multiexp(&b, &qs)).into();
+        let c: E::G1Affine = (-(g1 * c)).into();
+
+        // e(sum r_i q_i, [x]_2) * e([d]_1 - sum r_i w^i q_i, [1]_2) * e(-[c]_1, t) == 1
+        let x_g2 = E::G2Prepared::from(srs_g2[1]);
+        let one_g2 = E::G2Prepared::from(srs_g2[0]);
+        let t_g2 = E::G2Prepared::from(committed.t);
+        bool::from(
+            E::multi_miller_loop(&[(&a, &x_g2), (&b, &one_g2), (&c, &t_g2)])
+                .final_exponentiation()
+                .is_identity(),
+        )
+    }
+
+    /// Table values, ordered by their index
+    fn values(&self) -> Vec<E::Scalar> {
+        let mut values = vec![E::Scalar::zero(); self.size];
+        for (&value, &index) in self.value_index_mapping.iter() {
+            values[index] = value;
         }
+        values
+    }
+
+    /// Commitment to the table polynomial in G2
+    fn commit_t(&self, srs_g2: &[E::G2Affine]) -> E::G2 {
+        let mut table_coeffs = self.values();
+        EvaluationDomain::<E::Scalar>::ifft(
+            table_coeffs.as_mut_slice(),
+            self.domain.get_omega_inv(),
+            self.domain.k(),
+            self.domain.ifft_divisor(),
+        );
+        best_multiexp(&table_coeffs, &srs_g2[..table_coeffs.len()])
+    }
+}
+
+impl<E: MultiMillerLoop> StaticTableValues<E>
+where
+    E::Scalar: SerdePrimeField,
+    E::G1Affine: SerdeCurveAffine,
+{
+    /// Writes table values (ordered by their index) followed by the quotient commitments.
+    pub(crate) fn write<W: io::Write>(&self, writer: &mut W, format: SerdeFormat) {
+        writer.write_all(&(self.size as u32).to_be_bytes()).unwrap();
+
+        for value in self.values().iter() {
+            value.write(writer, format);
+        }
+
+        let mut qs = vec![E::G1Affine::identity(); self.qs.len()];
+        E::G1::batch_normalize(&self.qs, &mut qs);
+        write_point_slice(&qs, writer, format);
+    }
+
+    /// Reads table values written by [`Self::write`].
+    ///
+    /// Fails if the buffer ends early, or if the table size is not a power of two or its
+    /// values are not distinct.
+    pub(crate) fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> io::Result<Self> {
+        let mut size = [0u8; 4];
+        reader.read_exact(&mut size)?;
+        let size = u32::from_be_bytes(size) as usize;
+        if size == 0 || !is_pow_2(size) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("static table size {} is not a power of two", size),
+            ));
+        }
+
+        let value_index_mapping: BTreeMap<E::Scalar, usize> = (0..size)
+            .map(|i| Ok((<E::Scalar as SerdePrimeField>::read(reader, format)?, i)))
+            .collect::<io::Result<_>>()?;
+        if value_index_mapping.len() != size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "static table values are not distinct",
+            ));
+        }
+
+        let qs = read_point_vec::<_, E::G1Affine>(reader, format)?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Ok(Self {
+            size,
+            value_index_mapping,
+            qs,
+            domain: EvaluationDomain::new(2, log2(size)),
+        })
     }
 }
 
@@ -163,24 +572,142 @@ pub struct StaticCommittedTable<E: MultiMillerLoop> {
     pub t: E::G2Affine,
     pub x_b0_bound: E::G2Affine,
     pub size: usize,
+    pub default_value: E::Scalar,
+}
+
+impl<E: MultiMillerLoop> PartialEq for StaticCommittedTable<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.zv == other.zv
+            && self.t == other.t
+            && self.x_b0_bound == other.x_b0_bound
+            && self.size == other.size
+            && self.default_value == other.default_value
+    }
+}
+
+impl<E: MultiMillerLoop> Eq for StaticCommittedTable<E> {}
+
+impl<E: MultiMillerLoop> StaticCommittedTable<E> {
+    pub(crate) fn bytes_length(&self) -> usize {
+        let scalar_len = E::Scalar::default().to_repr().as_ref().len();
+        4 + scalar_len + 3 * E::G2Affine::default().to_bytes().as_ref().len()
+    }
+}
+
+impl<E: MultiMillerLoop> StaticCommittedTable<E>
+where
+    E::Scalar: SerdePrimeField,
+    E::G2Affine: SerdeCurveAffine,
+{
+    /// Writes the size and default value of the table followed by its commitments.
+    ///
+    /// With [`SerdeFormat::Json`] these are written as the fields of an object, without
+    /// its braces.
+    pub(crate) fn write<W: io::Write>(
+        &self,
+        writer: &mut W,
+        format: SerdeFormat,
+    ) -> io::Result<()> {
+        if let SerdeFormat::Json = format {
+            json::write_key(writer, "size")?;
+            write!(writer, "{},", self.size)?;
+            json::write_key(writer, "default_value")?;
+            self.default_value.write(writer, format);
+            for (key, point) in [
+                ("zv", &self.zv),
+                ("t", &self.t),
+                ("x_b0_bound", &self.x_b0_bound),
+            ] {
+                writer.write_all(b",")?;
+                json::write_key(writer, key)?;
+                point.write(writer, format);
+            }
+            return Ok(());
+        }
+
+        writer.write_all(&(self.size as u32).to_be_bytes())?;
+        self.default_value.write(writer, format);
+        self.zv.write(writer, format);
+        self.t.write(writer, format);
+        self.x_b0_bound.write(writer, format);
+        Ok(())
+    }
+
+    /// Reads a table written by [`Self::write`].
+    pub(crate) fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> io::Result<Self> {
+        let is_json = matches!(format, SerdeFormat::Json);
+
+        let size = if is_json {
+            json::read_key(reader, "size")?;
+            json::read_u32(reader, b',')?
+        } else {
+            let mut size = [0u8; 4];
+            reader.read_exact(&mut size)?;
+            u32::from_be_bytes(size)
+        };
+
+        if is_json {
+            json::read_key(reader, "default_value")?;
+        }
+        let default_value = <E::Scalar as SerdePrimeField>::read(reader, format)?;
+
+        let mut read_point = |key| -> io::Result<E::G2Affine> {
+            if is_json {
+                json::expect(reader, b',')?;
+                json::read_key(reader, key)?;
+            }
+            <E::G2Affine as SerdeCurveAffine>::read(reader, format)
+        };
+        let zv = read_point("zv")?;
+        let t = read_point("t")?;
+        let x_b0_bound = read_point("x_b0_bound")?;
+
+        Ok(Self {
+            zv,
+            t,
+            x_b0_bound,
+            size: size as usize,
+            default_value,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Argument<F: Field> {
+    name: &'static str,
     input: Vec<Expression<F>>,
     table_ids: Vec<StaticTableId<String>>,
 }
 
 impl<F: Field> Argument<F> {
-    pub fn new(_name: &'static str, table_map: Vec<(Expression<F>, StaticTableId<String>)>) -> Self {
+    pub fn new(name: &'static str, table_map: Vec<(Expression<F>, StaticTableId<String>)>) -> Self {
         let (input, table_ids) = table_map.into_iter().unzip();
 
-        Self { input, table_ids }
+        Self {
+            name,
+            input,
+            table_ids,
+        }
+    }
+
+    /// Returns the name of this lookup
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the input expressions, one per looked up table
+    pub fn input(&self) -> &[Expression<F>] {
+        &self.input
+    }
+
+    /// Returns the ids of the looked up tables, in the order of [`Self::input`]
+    pub fn table_ids(&self) -> &[StaticTableId<String>] {
+        &self.table_ids
     }
 
     pub(crate) fn required_degree(&self) -> usize {
         /*
-            B(X)(q(X) * f(X) - \beta) - 1
+            B(X)(q(X) * f(X) + (1 - q(X)) * t_0 + \beta) - 1
         */
         let mut input_degree = 1;
         for expr in self.input.iter() {
@@ -189,3 +716,345 @@ impl<F: Field> Argument<F> {
         std::cmp::max(3, 2 + input_degree)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{compress_with_theta, log2, StaticTable, StaticTableConfig, StaticTableValues};
+    use crate::arithmetic::eval_polynomial;
+    use crate::plonk::Error;
+    use crate::poly::kzg::commitment::{ParamsKZG, TableSRS};
+    use crate::poly::EvaluationDomain;
+    use crate::SerdeFormat;
+    use ff::Field;
+    use group::{prime::PrimeCurveAffine, Curve};
+    use halo2curves::bn256::{Bn256, Fr, G1Affine, G2Affine};
+    use rand_core::OsRng;
+    use std::time::Instant;
+
+    #[test]
+    fn compress_with_theta_weights_first_value_highest() {
+        let theta = Fr::random(OsRng);
+        let values: Vec<Fr> = (0..3).map(|_| Fr::random(OsRng)).collect();
+        assert_eq!(
+            compress_with_theta(values.iter().copied(), theta),
+            theta.square() * values[0] + theta * values[1] + values[2]
+        );
+        assert_eq!(compress_with_theta(None, theta), Fr::zero());
+
+        // Compressing the rows of the columns, as the prover does for `f`, and evaluating
+        // the result agrees with compressing the evaluations of the columns
+        let columns: Vec<Vec<Fr>> = (0..3)
+            .map(|_| (0..8).map(|_| Fr::random(OsRng)).collect())
+            .collect();
+        let rows: Vec<Fr> = (0..8)
+            .map(|row| compress_with_theta(columns.iter().map(|column| column[row]), theta))
+            .collect();
+        let x = Fr::random(OsRng);
+        assert_eq!(
+            eval_polynomial(&rows, x),
+            compress_with_theta(
+                columns.iter().map(|column| eval_polynomial(column, x)),
+                theta
+            )
+        );
+    }
+
+    #[test]
+    fn from_fn_matches_new() {
+        let size = 8;
+        let srs_g1: Vec<G1Affine> = (0..size)
+            .map(|i| (G1Affine::generator() * Fr::from(i as u64 + 1)).to_affine())
+            .collect();
+
+        let from_fn =
+            StaticTableValues::<Bn256>::from_fn(size, |i| Fr::from(i as u64), &srs_g1).unwrap();
+        let values: Vec<Fr> = (0..size).map(|i| Fr::from(i as u64)).collect();
+        let from_values = StaticTableValues::<Bn256>::new(&values, &srs_g1).unwrap();
+
+        assert_eq!(from_fn.size, from_values.size);
+        assert_eq!(from_fn.value_index_mapping, from_values.value_index_mapping);
+        assert_eq!(from_fn.qs, from_values.qs);
+    }
+
+    #[test]
+    fn with_default_moves_default_to_index_0() {
+        let size = 8;
+        let srs_g1: Vec<G1Affine> = (0..size)
+            .map(|i| (G1Affine::generator() * Fr::from(i as u64 + 1)).to_affine())
+            .collect();
+        let values: Vec<Fr> = (0..size).map(|i| Fr::from(2 * i as u64 + 5)).collect();
+
+        let table =
+            StaticTableValues::<Bn256>::with_default(&values, Fr::from(11), &srs_g1).unwrap();
+        assert_eq!(table.default_value(), Fr::from(11));
+        assert_eq!(table.index_of(&Fr::from(5)), Some(3));
+        assert_eq!(table.index_of(&Fr::from(7)), Some(1));
+
+        let table = StaticTable {
+            opened: Some(table),
+            committed: None,
+        };
+        assert_eq!(table.default_value(), Some(Fr::from(11)));
+    }
+
+    #[test]
+    fn with_default_rejects_missing_default() {
+        let size = 8;
+        let srs_g1: Vec<G1Affine> = (0..size)
+            .map(|i| (G1Affine::generator() * Fr::from(i as u64 + 1)).to_affine())
+            .collect();
+        let values: Vec<Fr> = (0..size).map(|i| Fr::from(2 * i as u64 + 5)).collect();
+
+        assert!(matches!(
+            StaticTableValues::<Bn256>::with_default(&values, Fr::from(6), &srs_g1),
+            Err(Error::DefaultValueNotInTable)
+        ));
+    }
+
+    #[test]
+    fn new_rejects_size_not_power_of_two() {
+        let srs_g1: Vec<G1Affine> = (0..32)
+            .map(|i| (G1Affine::generator() * Fr::from(i as u64 + 1)).to_affine())
+            .collect();
+
+        for size in [0, 17] {
+            let values: Vec<Fr> = (0..size).map(|i| Fr::from(i as u64)).collect();
+            assert!(matches!(
+                StaticTableValues::<Bn256>::new(&values, &srs_g1),
+                Err(Error::TableSizeNotPowerOfTwo(found)) if found == size
+            ));
+        }
+    }
+
+    #[test]
+    fn new_rejects_duplicate_values() {
+        let size = 8;
+        let srs_g1: Vec<G1Affine> = (0..size)
+            .map(|i| (G1Affine::generator() * Fr::from(i as u64 + 1)).to_affine())
+            .collect();
+        let mut values: Vec<Fr> = (0..size).map(|i| Fr::from(i as u64)).collect();
+        values[5] = values[2];
+
+        assert!(matches!(
+            StaticTableValues::<Bn256>::new(&values, &srs_g1),
+            Err(Error::DuplicateTableValue(5))
+        ));
+    }
+
+    #[test]
+    fn repeated_commit_is_deterministic() {
+        let size = 8;
+        let srs_g1: Vec<G1Affine> = (0..size)
+            .map(|i| (G1Affine::generator() * Fr::from(i as u64 + 1)).to_affine())
+            .collect();
+        let srs_g2: Vec<G2Affine> = (0..=size)
+            .map(|i| (G2Affine::generator() * Fr::from(i as u64 + 1)).to_affine())
+            .collect();
+
+        let table =
+            StaticTableValues::<Bn256>::from_fn(size, |i| Fr::from(i as u64), &srs_g1).unwrap();
+        let first = table.commit(size + 1, &srs_g2, 4).unwrap();
+        let second = table.commit(size + 1, &srs_g2, 4).unwrap();
+
+        assert_eq!(first.zv, second.zv);
+        assert_eq!(first.t, second.t);
+        assert_eq!(first.x_b0_bound, second.x_b0_bound);
+        assert_eq!(first.size, second.size);
+        assert_eq!(first.default_value, second.default_value);
+    }
+
+    // Timing test, run with `cargo test --release -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn cached_domain_speeds_up_commit() {
+        let size = 4096;
+        let srs_g2: Vec<G2Affine> = (0..=size)
+            .map(|i| (G2Affine::generator() * Fr::from(i as u64 + 1)).to_affine())
+            .collect();
+
+        // The quotient commitments are not used by `commit`, skip computing them
+        let table = StaticTableValues::<Bn256> {
+            size,
+            value_index_mapping: (0..size).map(|i| (Fr::from(i as u64), i)).collect(),
+            qs: vec![],
+            domain: EvaluationDomain::new(2, log2(size)),
+        };
+
+        // Without the cache, every commitment built the domain of the table first
+        let min_time = |commit: &dyn Fn()| {
+            (0..5)
+                .map(|_| {
+                    let start = Instant::now();
+                    commit();
+                    start.elapsed()
+                })
+                .min()
+                .unwrap()
+        };
+        let cached = min_time(&|| {
+            table.commit(size + 1, &srs_g2, 4).unwrap();
+        });
+        let uncached = min_time(&|| {
+            EvaluationDomain::<Fr>::new(2, log2(size));
+            table.commit(size + 1, &srs_g2, 4).unwrap();
+        });
+
+        println!(
+            "commit of a table of size {}: cached domain {:?}, rebuilt domain {:?}",
+            size, cached, uncached
+        );
+        assert!(cached < uncached);
+    }
+
+    #[test]
+    fn commit_all_fills_committed() {
+        let size = 8;
+        let srs_g1: Vec<G1Affine> = (0..size)
+            .map(|i| (G1Affine::generator() * Fr::from(i as u64 + 1)).to_affine())
+            .collect();
+        let srs_g2: Vec<G2Affine> = (0..=size)
+            .map(|i| (G2Affine::generator() * Fr::from(i as u64 + 1)).to_affine())
+            .collect();
+
+        let mut tables: Vec<_> = (1..4)
+            .map(|k| StaticTable::<Bn256> {
+                opened: Some(
+                    StaticTableValues::from_fn(size, |i| Fr::from((k * i) as u64), &srs_g1)
+                        .unwrap(),
+                ),
+                committed: None,
+            })
+            .collect();
+
+        StaticTable::commit_all(tables.iter_mut(), size + 1, &srs_g2, 4).unwrap();
+
+        assert!(tables.iter().all(|table| table.committed.is_some()));
+    }
+
+    #[test]
+    fn commit_checks_circuit_domain() {
+        let size = 8;
+        let srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, Fr::random(OsRng));
+        let srs_g1_len = srs.g1().len();
+        let table =
+            StaticTableValues::<Bn256>::from_fn(size, |i| Fr::from(i as u64), srs.g1()).unwrap();
+
+        // B0 of the largest circuit has degree `srs_g1_len - 2`, shifted by `x`
+        let committed = table.commit(srs_g1_len, srs.g2(), srs_g1_len).unwrap();
+        assert_eq!(committed.x_b0_bound, srs.g2()[1]);
+
+        let committed = table.commit(srs_g1_len, srs.g2(), 2).unwrap();
+        assert_eq!(committed.x_b0_bound, srs.g2()[srs_g1_len - 1]);
+
+        for domain in [0, 1, srs_g1_len + 1, 4 * srs_g1_len] {
+            assert!(matches!(
+                table.commit(srs_g1_len, srs.g2(), domain),
+                Err(Error::StaticTableDomain { circuit_domain, .. }) if circuit_domain == domain
+            ));
+        }
+
+        // `[x^size]_2` is missing
+        assert!(matches!(
+            table.commit(srs_g1_len, &srs.g2()[..size], 4),
+            Err(Error::StaticTableDomain { .. })
+        ));
+        // The table does not fit in the SRS
+        assert!(matches!(
+            table.commit(size - 1, srs.g2(), 4),
+            Err(Error::StaticTableDomain { .. })
+        ));
+    }
+
+    #[test]
+    fn verify_commitment_detects_wrong_srs() {
+        let size = 8;
+        let srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, Fr::random(OsRng));
+        let other_srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, Fr::random(OsRng));
+
+        let table = StaticTableValues::<Bn256>::from_fn(size, |i| Fr::from(3 * i as u64), srs.g1())
+            .unwrap();
+        let committed = table.commit(srs.g1().len(), srs.g2(), 8).unwrap();
+        assert!(table.verify_commitment(&committed, srs.g2()));
+
+        // `t` committed against another SRS
+        let committed_elsewhere = table
+            .commit(other_srs.g1().len(), other_srs.g2(), 8)
+            .unwrap();
+        assert!(!table.verify_commitment(&committed_elsewhere, srs.g2()));
+
+        // `qs` computed against another SRS
+        let mismatched =
+            StaticTableValues::<Bn256>::from_fn(size, |i| Fr::from(3 * i as u64), other_srs.g1())
+                .unwrap();
+        assert!(!mismatched.verify_commitment(&committed, srs.g2()));
+    }
+
+    #[test]
+    fn validate_config_against_srs() {
+        let s = Fr::random(OsRng);
+        let srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, s);
+        let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(4, s);
+
+        let config = StaticTableConfig::<Bn256>::new(
+            16,
+            srs.g1_lagrange().to_vec(),
+            srs.g_lagrange_opening_at_0().to_vec(),
+        );
+        assert!(config.validate(params.g2(), params.s_g2()));
+
+        let swapped = StaticTableConfig::<Bn256>::new(
+            16,
+            srs.g_lagrange_opening_at_0().to_vec(),
+            srs.g1_lagrange().to_vec(),
+        );
+        assert!(!swapped.validate(params.g2(), params.s_g2()));
+        assert_ne!(config, swapped);
+
+        // The same secret gives the same SRS, and so the same config
+        let srs_again = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, s);
+        assert_eq!(srs, srs_again);
+        let config_again = StaticTableConfig::<Bn256>::new(
+            16,
+            srs_again.g1_lagrange().to_vec(),
+            srs_again.g_lagrange_opening_at_0().to_vec(),
+        );
+        assert_eq!(config, config_again);
+
+        // Consistent on its own, but for another secret
+        let other_params = ParamsKZG::<Bn256>::setup_from_toxic_waste(4, Fr::random(OsRng));
+        assert!(!config.validate(other_params.g2(), other_params.s_g2()));
+    }
+
+    fn with_threads<T: Send>(threads: usize, f: impl FnOnce() -> T + Send) -> T {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap()
+            .install(f)
+    }
+
+    #[test]
+    fn setup_and_qs_do_not_depend_on_thread_count() {
+        let s = Fr::from(0x5eed_u64);
+        let run = || {
+            let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(8, s);
+            let srs = TableSRS::<Bn256>::setup_from_toxic_waste(255, 256, s);
+            let table =
+                StaticTableValues::<Bn256>::from_fn(64, |i| Fr::from(7 * i as u64 + 1), srs.g1())
+                    .unwrap();
+
+            let mut params_bytes = vec![];
+            params.write_custom(&mut params_bytes, SerdeFormat::RawBytes);
+            let mut table_bytes = vec![];
+            table.write(&mut table_bytes, SerdeFormat::RawBytes);
+            (params_bytes, srs.g1_lagrange().to_vec(), table_bytes)
+        };
+
+        let single = with_threads(1, run);
+        let many = with_threads(8, run);
+
+        assert_eq!(single.0, many.0, "g_lagrange differs across thread counts");
+        assert_eq!(single.1, many.1, "g1_lagrange differs across thread counts");
+        assert_eq!(single.2, many.2, "qs differ across thread counts");
+    }
+}
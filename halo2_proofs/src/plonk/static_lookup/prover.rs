@@ -9,9 +9,10 @@ use halo2curves::{
 // TODO: COMPUTE A(0) COMMITMENT FROM LAGRANGE AT 0 COMMITMENTS
 
 use crate::{
-    arithmetic::{best_multiexp, eval_polynomial},
+    arithmetic::{best_multiexp, eval_polynomial, parallelize, CurveAffine},
     plonk::{
-        evaluation::evaluate, ChallengeBeta, ChallengeTheta, ChallengeX, Expression, ProvingKey,
+        evaluation::evaluate_many, ChallengeBeta, ChallengeTheta, ChallengeX, Expression,
+        ProvingKey,
     },
     poly::{
         commitment::{Blind, Params, ParamsProver},
@@ -20,12 +21,21 @@ use crate::{
     },
     transcript::{EncodedChallenge, TranscriptWrite},
 };
-use std::{collections::BTreeMap, fmt::Debug, iter};
+use std::{collections::BTreeMap, fmt::Debug, iter, ops::Range};
 
 use crate::plonk::Error;
-use group::{prime::PrimeCurveAffine, Curve, Group};
+use group::{prime::PrimeCurveAffine, Curve, Group as _};
 
-use super::StaticTableId;
+use super::{compress_with_theta, StaticTableId};
+
+/// Enters a `tracing` span named `$name` until the end of the enclosing block,
+/// compiled out unless the `cq-tracing` feature is enabled
+macro_rules! cq_span {
+    ($name:literal) => {
+        #[cfg(feature = "cq-tracing")]
+        let _span = tracing::info_span!($name).entered();
+    };
+}
 
 #[derive(Debug)]
 pub struct Committed<E: MultiMillerLoop> {
@@ -33,6 +43,7 @@ pub struct Committed<E: MultiMillerLoop> {
     pub(in crate::plonk) m_sparse: BTreeMap<usize, E::Scalar>,
     pub(in crate::plonk) table_ids: Vec<StaticTableId<String>>,
     pub(in crate::plonk) table_index_value_mappings: Vec<BTreeMap<usize, E::Scalar>>,
+    pub(in crate::plonk) default_value: E::Scalar,
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +52,7 @@ pub struct CommittedLogDerivative<E: MultiMillerLoop> {
     pub(in crate::plonk) b0: Polynomial<E::Scalar, Coeff>,
     pub(in crate::plonk) f: Polynomial<E::Scalar, Coeff>,
     pub(in crate::plonk) a_at_zero: E::Scalar,
+    pub(in crate::plonk) default_value: E::Scalar,
 }
 
 pub(in crate::plonk) struct Evaluated<E: MultiMillerLoop> {
@@ -48,6 +60,17 @@ pub(in crate::plonk) struct Evaluated<E: MultiMillerLoop> {
 }
 
 impl<F: FieldExt> super::Argument<F> {
+    /// Splits a domain of `n` rows into the rows whose inputs are looked up and the
+    /// number of trailing rows (blinding factors and the last row) that are padded
+    /// with the default value of the tables
+    pub(in crate::plonk) fn usable_rows(
+        n: usize,
+        blinding_factors: usize,
+    ) -> (Range<usize>, usize) {
+        let padding = blinding_factors + 1;
+        (0..n - padding, padding)
+    }
+
     pub(in crate::plonk) fn commit<'a, E, EC, T>(
         &self,
         pk: &ProvingKey<E>,
@@ -67,6 +90,8 @@ impl<F: FieldExt> super::Argument<F> {
         EC: EncodedChallenge<E::G1Affine>,
         T: TranscriptWrite<E::G1Affine, EC>,
     {
+        cq_span!("cq.commit");
+
         // TODO: make nicer error
         let tables: Vec<_> = self
             .table_ids
@@ -85,33 +110,37 @@ impl<F: FieldExt> super::Argument<F> {
         let table_config = pk
             .static_table_configs
             .get(&tables[0].size)
-            .expect("Config does not exists");
+            .expect("Config existence is checked in keygen_pk");
 
         let evaluate_expressions = |expressions: &[Expression<E::Scalar>]| {
-            expressions
-                .iter()
-                .map(|expression| {
-                    pk.vk.domain.lagrange_from_vec(evaluate(
-                        expression,
-                        params.n() as usize,
-                        1,
-                        fixed_values,
-                        advice_values,
-                        instance_values,
-                        challenges,
-                    ))
-                })
-                .collect::<Vec<_>>()
+            // Expressions are evaluated over the n-th roots of unity, so a
+            // rotation by one row is a step of one in the Lagrange basis
+            evaluate_many(
+                expressions,
+                params.n() as usize,
+                1,
+                fixed_values,
+                advice_values,
+                instance_values,
+                challenges,
+            )
+            .into_iter()
+            .map(|values| pk.vk.domain.lagrange_from_vec(values))
+            .collect::<Vec<_>>()
         };
 
-        // Closure to get values of expressions and compress them
+        // Closure to compress the values of expressions row by row
         let compress_expressions =
             |evaluated_expressions: &[Polynomial<E::Scalar, LagrangeCoeff>]| {
-                let compressed_expression = evaluated_expressions
-                    .iter()
-                    .fold(domain.empty_lagrange(), |acc, expression| {
-                        acc * *theta + &expression
-                    });
+                let mut compressed_expression = domain.empty_lagrange();
+                parallelize(&mut compressed_expression.values, |compressed, start| {
+                    for (row, value) in (start..).zip(compressed.iter_mut()) {
+                        *value = compress_with_theta(
+                            evaluated_expressions.iter().map(|values| values[row]),
+                            *theta,
+                        );
+                    }
+                });
                 compressed_expression
             };
 
@@ -119,17 +148,17 @@ impl<F: FieldExt> super::Argument<F> {
         let evaluated_expressions = evaluate_expressions(&self.input);
         let f = compress_expressions(&evaluated_expressions);
 
-        // NOTE: For completeness we just ignore blinding rows
-        // make sure to add selector and change cq as in our hackmd for soundness
-        let blinding_factors = pk.vk.cs.blinding_factors();
-        let usable_rows = params.n() as usize - (blinding_factors + 1);
+        // Only active rows are looked up, inactive rows are constrained to the
+        // default value of the tables (the one at index 0)
+        let (usable_rows, padding) =
+            Self::usable_rows(params.n() as usize, pk.vk.cs.blinding_factors());
         let mut m_sparse = BTreeMap::<usize, E::Scalar>::default();
 
         let mut table_index_value_mappings: Vec<_> = (0..tables.len())
             .map(|_| BTreeMap::<usize, E::Scalar>::default())
             .collect();
 
-        for row in 0..usable_rows {
+        for row in usable_rows {
             let mut idx: Option<usize> = None;
             for (table_idx, (evals, table)) in
                 evaluated_expressions.iter().zip(tables.iter()).enumerate()
@@ -160,16 +189,22 @@ impl<F: FieldExt> super::Argument<F> {
             }
         }
 
+        *m_sparse.entry(0).or_insert(E::Scalar::zero()) += E::Scalar::from(padding as u64);
+        for (mapping, table) in table_index_value_mappings.iter_mut().zip(tables.iter()) {
+            mapping.insert(0, table.default_value());
+        }
+
+        let default_value =
+            compress_with_theta(tables.iter().map(|table| table.default_value()), *theta);
+
         // zk is not currently supported
         let blind = Blind(E::Scalar::zero());
         let f_cm: E::G1Affine = params.commit_lagrange(&f, blind).into();
 
-        let mut m_cm = E::G1::identity();
-        for (&index, &multiplicity) in m_sparse.iter() {
-            m_cm = table_config.g1_lagrange[index] * multiplicity + m_cm;
-        }
-
-        let m_cm: E::G1Affine = m_cm.into();
+        let (m_indices, multiplicities): (Vec<usize>, Vec<E::Scalar>) =
+            m_sparse.iter().map(|(&index, &m)| (index, m)).unzip();
+        let m_cm: E::G1Affine =
+            sparse_multiexp(&multiplicities, &m_indices, &table_config.g1_lagrange).into();
 
         transcript.write_point(f_cm)?;
         transcript.write_point(m_cm)?;
@@ -179,6 +214,7 @@ impl<F: FieldExt> super::Argument<F> {
             m_sparse,
             table_ids: self.table_ids.clone(),
             table_index_value_mappings,
+            default_value,
         })
     }
 }
@@ -200,6 +236,8 @@ impl<E: MultiMillerLoop> Committed<E> {
         EC: EncodedChallenge<E::G1Affine>,
         T: TranscriptWrite<E::G1Affine, EC>,
     {
+        cq_span!("cq.commit_log_derivatives");
+
         // TODO: make nicer error
         let tables: Vec<_> = self
             .table_ids
@@ -215,33 +253,30 @@ impl<E: MultiMillerLoop> Committed<E> {
         let table_config = pk
             .static_table_configs
             .get(&tables[0].size)
-            .expect("Config does not exists");
-
-        let mut a_cm = E::G1::identity();
-        let mut qa_cm = E::G1::identity();
-        let mut a0_cm = E::G1::identity();
+            .expect("Config existence is checked in keygen_pk");
 
+        // The compressed qs stay in projective form, they are normalized all at once below
         let compress_tables = |index: usize| {
-            tables.iter().enumerate().fold(
-                (E::Scalar::zero(), E::G1Affine::identity()),
-                |acc, (table_idx, &table)| {
-                    let (values, qs) = acc;
-
-                    let values = values * *theta
-                        + self.table_index_value_mappings[table_idx]
-                            .get(&index)
-                            .unwrap();
-                    let qs = qs * *theta + table.qs[index];
-
-                    // TODO: do this in projectiv
-                    (values, qs.into())
-                },
-            )
+            let values = compress_with_theta(
+                self.table_index_value_mappings
+                    .iter()
+                    .map(|mapping| *mapping.get(&index).unwrap()),
+                *theta,
+            );
+            let qs = tables.iter().fold(E::G1::identity(), |acc, table| {
+                acc * *theta + table.qs[index]
+            });
+
+            (values, qs)
         };
 
-        let f_set: std::collections::BTreeSet<E::Scalar> = self.f.iter().cloned().collect();
+        let mut f_set: std::collections::BTreeSet<E::Scalar> = self.f.iter().cloned().collect();
+        f_set.insert(self.default_value);
 
-        // step 2&3&4: computes A sparse representation, a commitment and qa commitment in single pass
+        // step 2&3&4: computes A sparse representation and the compressed qs bases in single pass
+        let mut a_indices = Vec::with_capacity(self.m_sparse.len());
+        let mut a_sparse = Vec::with_capacity(self.m_sparse.len());
+        let mut qs_bases = Vec::with_capacity(self.m_sparse.len());
         for (&index, &multiplicity) in self.m_sparse.iter() {
             let (table_values, table_qs) = compress_tables(index);
             let a_i = multiplicity * (table_values + *beta).invert().unwrap();
@@ -249,31 +284,46 @@ impl<E: MultiMillerLoop> Committed<E> {
             // sanity
             assert!(f_set.get(&table_values).is_some());
 
-            // a_cm = table_g1_lagrange * a_i + a_cm;
-            a_cm = table_config.g1_lagrange[index] * a_i + a_cm;
-            qa_cm = table_qs * a_i + qa_cm;
-            // a0_cm = table_lagrange_0 * a_i + a0_cm;
-            a0_cm = table_config.g_lagrange_opening_at_0[index] * a_i + a0_cm;
+            a_indices.push(index);
+            a_sparse.push(a_i);
+            qs_bases.push(table_qs);
         }
+        let qs_bases = {
+            let mut affine = vec![E::G1Affine::identity(); qs_bases.len()];
+            E::G1::batch_normalize(&qs_bases, &mut affine);
+            affine
+        };
+
+        // a_cm = sum table_g1_lagrange[i] * a_i
+        let a_cm = sparse_multiexp(&a_sparse, &a_indices, &table_config.g1_lagrange);
+        // qa_cm = sum table_qs[i] * a_i
+        let qa_cm = best_multiexp(&a_sparse, &qs_bases);
+        // a0_cm = sum table_lagrange_0[i] * a_i
+        let a0_cm = sparse_multiexp(&a_sparse, &a_indices, &table_config.g_lagrange_opening_at_0);
 
-        let blinding_factors = pk.vk.cs.blinding_factors();
-        let usable_rows = params.n() as usize - (blinding_factors + 1);
+        let (usable_rows, padding) = super::Argument::<E::Scalar>::usable_rows(
+            params.n() as usize,
+            pk.vk.cs.blinding_factors(),
+        );
         let mut bs: Vec<_> = self
             .f
             .iter()
-            .take(usable_rows)
+            .take(usable_rows.end)
             .map(|&fi| (fi + *beta).invert().unwrap())
             .collect();
 
-        let beta_inv = beta.invert().unwrap();
-        bs.extend_from_slice(&vec![beta_inv; blinding_factors + 1]);
+        let default_inv = (self.default_value + *beta).invert().unwrap();
+        bs.extend_from_slice(&vec![default_inv; padding]);
 
-        EvaluationDomain::ifft(
-            bs.as_mut_slice(),
-            domain.get_omega_inv(),
-            domain.k(),
-            domain.ifft_divisor(),
-        );
+        {
+            cq_span!("cq.ifft");
+            EvaluationDomain::ifft(
+                bs.as_mut_slice(),
+                domain.get_omega_inv(),
+                domain.k(),
+                domain.ifft_divisor(),
+            );
+        }
 
         // (b - b(0)) / X
         let mut b0_poly_coeffs: Vec<<E as Engine>::Scalar> = bs[1..].to_vec();
@@ -283,16 +333,20 @@ impl<E: MultiMillerLoop> Committed<E> {
 
         #[cfg(feature = "sanity-checks")]
         {
-            let mut selector = vec![E::Scalar::one(); usable_rows];
-            selector.extend_from_slice(&vec![E::Scalar::zero(); n - usable_rows]);
+            let mut selector = vec![E::Scalar::one(); usable_rows.len()];
+            selector.extend_from_slice(&vec![E::Scalar::zero(); padding]);
             assert_eq!(selector.len(), n);
             let root = domain.get_omega();
             for i in 0..n {
                 assert_eq!(
                     E::Scalar::zero(),
-                    eval_polynomial(&b_poly, root.pow(&[i as u64, 0, 0, 0]))
-                        * (selector[i] * self.f[i] + *beta)
-                        - E::Scalar::one()
+                    super::log_derivative_identity(
+                        eval_polynomial(&b_poly, root.pow(&[i as u64, 0, 0, 0])),
+                        self.f[i],
+                        selector[i],
+                        self.default_value,
+                        *beta,
+                    )
                 )
             }
         }
@@ -312,24 +366,19 @@ impl<E: MultiMillerLoop> Committed<E> {
         transcript.write_point(b0_cm.into())?;
         transcript.write_point(p_cm.into())?;
 
-        // Sumcheck identity:
-        //      n * B(0) = N * A(0)
-        //      A(0) = n * B(0) / N
         let b_at_zero = eval_polynomial(&b_poly, E::Scalar::zero());
-        let a_at_zero = {
-            let n_table_inv = E::Scalar::from(table_config.size as u64).invert().unwrap();
-            let n = E::Scalar::from(n as u64);
-            let blinding_factors = E::Scalar::from(blinding_factors as u64);
-            (b_at_zero * n - (blinding_factors + E::Scalar::one()) * beta_inv) * n_table_inv
-        };
+        let a_at_zero = a_at_zero(b_at_zero, n, table_config.size);
 
         let mut f = self.f.to_vec();
-        EvaluationDomain::ifft(
-            &mut f,
-            domain.get_omega_inv(),
-            domain.k(),
-            domain.ifft_divisor(),
-        );
+        {
+            cq_span!("cq.ifft");
+            EvaluationDomain::ifft(
+                &mut f,
+                domain.get_omega_inv(),
+                domain.k(),
+                domain.ifft_divisor(),
+            );
+        }
 
         let f = domain.coeff_from_vec(f);
 
@@ -338,10 +387,37 @@ impl<E: MultiMillerLoop> Committed<E> {
             b0: b0_poly,
             f,
             a_at_zero,
+            default_value: self.default_value,
         })
     }
 }
 
+/// Commits to a sparse vector given by `scalars` at positions `indices` with a
+/// single multiexp over the corresponding subset of `bases`
+pub(in crate::plonk) fn sparse_multiexp<C: CurveAffine>(
+    scalars: &[C::Scalar],
+    indices: &[usize],
+    bases: &[C],
+) -> C::Curve {
+    debug_assert_eq!(scalars.len(), indices.len());
+    let bases: Vec<C> = indices.iter().map(|&index| bases[index]).collect();
+    best_multiexp(scalars, &bases)
+}
+
+/// Derives `A(0)` from `B(0)` using the sumcheck identity
+///
+///      n * B(0) = N * A(0)
+///
+/// where `n` is the circuit size and `N` is the table size. Inactive rows of
+/// `B` look up the default value of the table and are accounted for in the
+/// multiplicity of index 0, so no correction is needed:
+///
+///      A(0) = n * B(0) / N
+pub(in crate::plonk) fn a_at_zero<F: FieldExt>(b_at_zero: F, n: usize, table_size: usize) -> F {
+    let n_table_inv = F::from(table_size as u64).invert().unwrap();
+    b_at_zero * F::from(n as u64) * n_table_inv
+}
+
 impl<E: MultiMillerLoop> CommittedLogDerivative<E> {
     pub(in crate::plonk) fn evaluate<
         EC: EncodedChallenge<E::G1Affine>,
@@ -357,6 +433,8 @@ impl<E: MultiMillerLoop> CommittedLogDerivative<E> {
         E::G1Affine: SerdeObject,
         E::G2Affine: SerdeObject,
     {
+        cq_span!("cq.evaluate");
+
         let b0_eval = eval_polynomial(&self.b0, *x);
         let f_eval = eval_polynomial(&self.f, *x);
 
@@ -398,3 +476,147 @@ impl<E: MultiMillerLoop> Evaluated<E> {
             }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{a_at_zero, sparse_multiexp};
+    use crate::plonk::static_lookup::{log_derivative_identity, Argument};
+    use ff::Field;
+    use group::{Curve, Group};
+    use halo2curves::bn256::{Fr, G1Affine, G1};
+    use std::collections::BTreeMap;
+
+    const N: usize = 8;
+    const BLINDING_FACTORS: usize = 2;
+
+    fn table() -> [Fr; 4] {
+        [Fr::from(3), Fr::from(5), Fr::from(7), Fr::from(11)]
+    }
+
+    /// Evaluations of `B` over the circuit domain with the given values on inactive rows
+    fn b_at_zero(f: &[Fr], inactive: Fr, beta: Fr) -> Fr {
+        let b_sum = f
+            .iter()
+            .map(|&fi| (fi + beta).invert().unwrap())
+            .chain(std::iter::repeat(inactive).take(BLINDING_FACTORS + 1))
+            .fold(Fr::zero(), |acc, bi| acc + bi);
+
+        // B(0) is the average of the evaluations of B over the domain
+        b_sum * Fr::from(N as u64).invert().unwrap()
+    }
+
+    /// A(0) is the average of the evaluations of A over the table domain
+    fn a_at_zero_from_multiplicities(m: &[Fr], beta: Fr) -> Fr {
+        let table = table();
+        let a_sum = table
+            .iter()
+            .zip(m.iter())
+            .map(|(&ti, &mi)| mi * (ti + beta).invert().unwrap())
+            .fold(Fr::zero(), |acc, ai| acc + ai);
+        a_sum * Fr::from(table.len() as u64).invert().unwrap()
+    }
+
+    #[test]
+    fn a_at_zero_satisfies_sumcheck() {
+        let f = [
+            Fr::from(5),
+            Fr::from(3),
+            Fr::from(5),
+            Fr::from(11),
+            Fr::from(5),
+        ];
+        assert_eq!(f.len(), N - (BLINDING_FACTORS + 1));
+        // Inactive rows look up the value at index 0
+        let m = [
+            Fr::from(1 + BLINDING_FACTORS as u64 + 1),
+            Fr::from(3),
+            Fr::from(0),
+            Fr::from(1),
+        ];
+
+        let beta = Fr::from(42);
+        let default_inv = (table()[0] + beta).invert().unwrap();
+
+        assert_eq!(
+            a_at_zero(b_at_zero(&f, default_inv, beta), N, table().len()),
+            a_at_zero_from_multiplicities(&m, beta)
+        );
+    }
+
+    #[test]
+    fn blinding_rows_of_f_look_up_the_default_value() {
+        // The blinding rows of `f` hold arbitrary values, here 13 is not in the table
+        let f = [
+            Fr::from(5),
+            Fr::from(3),
+            Fr::from(5),
+            Fr::from(11),
+            Fr::from(5),
+            Fr::from(13),
+            Fr::from(7),
+            Fr::from(3),
+        ];
+        let usable_rows = N - (BLINDING_FACTORS + 1);
+        let l_active = |row: usize| Fr::from((row < usable_rows) as u64);
+
+        let beta = Fr::from(42);
+        let default_value = table()[0];
+        let default_inv = (default_value + beta).invert().unwrap();
+
+        // An honest prover pads B with the default value, whatever `f` holds there
+        for (row, &fi) in f.iter().enumerate() {
+            let b = if row < usable_rows {
+                (fi + beta).invert().unwrap()
+            } else {
+                default_inv
+            };
+            assert_eq!(
+                log_derivative_identity(b, fi, l_active(row), default_value, beta),
+                Fr::zero()
+            );
+        }
+
+        // A prover that ignores the selector looks up the blinding rows of `f` as well,
+        // which breaks the identity on every blinding row holding another value
+        for (row, &fi) in f.iter().enumerate().skip(usable_rows) {
+            let b = (fi + beta).invert().unwrap();
+            assert_ne!(
+                log_derivative_identity(b, fi, l_active(row), default_value, beta),
+                Fr::zero()
+            );
+        }
+    }
+
+    #[test]
+    fn sparse_multiexp_matches_pointwise_accumulation() {
+        let bases: Vec<G1Affine> = (1..=16u64)
+            .map(|i| (G1::generator() * Fr::from(i)).to_affine())
+            .collect();
+        let m_sparse: BTreeMap<usize, Fr> = [(0, 4), (3, 1), (7, 2), (15, 9)]
+            .into_iter()
+            .map(|(index, m)| (index, Fr::from(m)))
+            .collect();
+
+        let mut expected = G1::identity();
+        for (&index, &multiplicity) in m_sparse.iter() {
+            expected = bases[index] * multiplicity + expected;
+        }
+
+        let (indices, scalars): (Vec<usize>, Vec<Fr>) =
+            m_sparse.iter().map(|(&index, &m)| (index, m)).unzip();
+        assert_eq!(sparse_multiexp(&scalars, &indices, &bases), expected);
+    }
+
+    #[test]
+    fn usable_rows_leave_out_blinding_rows_and_last_row() {
+        for k in 3..10 {
+            let n = 1 << k;
+            for blinding_factors in 0..6 {
+                let (usable_rows, padding) = Argument::<Fr>::usable_rows(n, blinding_factors);
+                assert_eq!(usable_rows, 0..n - (blinding_factors + 1));
+                assert_eq!(padding, blinding_factors + 1);
+                assert_eq!(usable_rows.len() + padding, n);
+            }
+        }
+    }
+}
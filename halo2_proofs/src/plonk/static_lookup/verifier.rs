@@ -3,7 +3,7 @@ use std::iter;
 use super::super::{
     circuit::Expression, ChallengeBeta, ChallengeGamma, ChallengeTheta, ChallengeX,
 };
-use super::{Argument, StaticTableId};
+use super::{compress_with_theta, log_derivative_identity, Argument, StaticTableId};
 use crate::poly::kzg::commitment::ParamsKZG;
 use crate::{
     arithmetic::{CurveAffine, FieldExt},
@@ -185,6 +185,7 @@ where
         l_last: E::Scalar,
         l_blind: E::Scalar,
         beta: ChallengeBeta<E::G1Affine>,
+        theta: ChallengeTheta<E::G1Affine>,
         x: ChallengeX<E::G1Affine>,
     ) -> impl Iterator<Item = E::Scalar> + 'a {
         let active_rows = E::Scalar::one() - (l_last + l_blind);
@@ -203,21 +204,27 @@ where
 
         let table_size = E::Scalar::from(tables[0].size as u64);
 
-        let blinding_factors = vk.cs.blinding_factors();
-        let unusable_rows = E::Scalar::from((blinding_factors + 1) as u64);
+        // Inactive rows look up the value at index 0 of each table
+        let default_value =
+            compress_with_theta(tables.iter().map(|table| table.default_value), *theta);
 
+        // B(0) is not read from the proof but derived from A(0) with the sumcheck
+        // identity n * B(0) = N * A(0), so a wrong A(0) makes this expression nonzero
+        // unless it also passes the pairing binding it to A in `register_pairings`
         let b_at_zero = {
-            let beta_inv = beta.invert().unwrap();
             let circuit_domain_inv = E::Scalar::from(vk.get_domain().n).invert().unwrap();
-            (table_size * self.a_at_zero + unusable_rows * beta_inv) * circuit_domain_inv
+            table_size * self.a_at_zero * circuit_domain_inv
         };
 
         let b_eval = self.b0_eval * *x + b_at_zero;
 
-        std::iter::empty().chain(Some(
-            // b(l_active * f + beta) - 1 = 0
-            b_eval * (active_rows * self.f_eval + *beta) - E::Scalar::one(),
-        ))
+        std::iter::empty().chain(Some(log_derivative_identity(
+            b_eval,
+            self.f_eval,
+            active_rows,
+            default_value,
+            *beta,
+        )))
     }
 
     pub(in crate::plonk) fn queries<'r, M: MSM<E::G1Affine> + 'r>(
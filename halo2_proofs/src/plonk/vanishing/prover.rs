@@ -7,7 +7,7 @@ use rand_core::RngCore;
 use super::Argument;
 use crate::{
     arithmetic::{eval_polynomial, CurveAffine, FieldExt},
-    plonk::{ChallengeX, ChallengeY, Error},
+    plonk::{BlindingStrategy, ChallengeX, ChallengeY, Error},
     poly::{
         self,
         commitment::{Blind, ParamsProver},
@@ -43,16 +43,23 @@ impl<C: CurveAffine> Argument<C> {
     >(
         params: &P,
         domain: &EvaluationDomain<C::Scalar>,
+        blinding: BlindingStrategy,
         mut rng: R,
         transcript: &mut T,
     ) -> Result<Committed<C>, Error> {
-        // Sample a random polynomial of degree n - 1
+        // Sample a random polynomial of degree n - 1, or take the constant
+        // polynomial one, whose commitment is not the identity, without blinding
         let mut random_poly = domain.empty_coeff();
-        for coeff in random_poly.iter_mut() {
-            *coeff = C::Scalar::random(&mut rng);
+        match blinding {
+            BlindingStrategy::Random => {
+                for coeff in random_poly.iter_mut() {
+                    *coeff = C::Scalar::random(&mut rng);
+                }
+            }
+            BlindingStrategy::Zero => random_poly[0] = C::Scalar::one(),
         }
-        // Sample a random blinding factor
-        let random_blind = Blind(C::Scalar::random(rng));
+        // Sample a blinding factor
+        let random_blind = blinding.blind(rng);
 
         // Commit
         let c = params.commit(&random_poly, random_blind).to_affine();
@@ -77,6 +84,7 @@ impl<C: CurveAffine> Committed<C> {
         params: &P,
         domain: &EvaluationDomain<C::Scalar>,
         h_poly: Polynomial<C::Scalar, ExtendedLagrangeCoeff>,
+        blinding: BlindingStrategy,
         mut rng: R,
         transcript: &mut T,
     ) -> Result<Constructed<C>, Error> {
@@ -92,10 +100,7 @@ impl<C: CurveAffine> Committed<C> {
             .map(|v| domain.coeff_from_vec(v.to_vec()))
             .collect::<Vec<_>>();
         drop(h_poly);
-        let h_blinds: Vec<_> = h_pieces
-            .iter()
-            .map(|_| Blind(C::Scalar::random(&mut rng)))
-            .collect();
+        let h_blinds: Vec<_> = h_pieces.iter().map(|_| blinding.blind(&mut rng)).collect();
 
         // Compute commitments to each h(X) piece
         let h_commitments_projective: Vec<_> = h_pieces
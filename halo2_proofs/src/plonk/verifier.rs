@@ -1,5 +1,5 @@
 use ff::Field;
-use group::Curve;
+use group::{Curve, Group as _};
 use halo2curves::batch_pairing::PairingBatcher;
 use rand_core::RngCore;
 use std::fmt::Debug;
@@ -20,7 +20,7 @@ use crate::poly::{
 use crate::transcript::{
     read_n_points, read_n_scalars, ChallengeScalar, EncodedChallenge, TranscriptRead,
 };
-use halo2curves::pairing::MultiMillerLoop;
+use halo2curves::pairing::{MillerLoopResult, MultiMillerLoop};
 use halo2curves::serde::SerdeObject;
 
 // #[cfg(feature = "batch")]
@@ -30,6 +30,60 @@ use halo2curves::serde::SerdeObject;
 
 use crate::poly::commitment::ParamsVerifier;
 
+/// Argument of a proof checked by its own pairing equations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProofArgument {
+    /// The static lookup at this index of [`ConstraintSystem::static_lookups`]
+    ///
+    /// [`ConstraintSystem::static_lookups`]: super::ConstraintSystem::static_lookups
+    StaticLookup(usize),
+    /// The multiopen argument. The gates, permutation, lookups and vanishing
+    /// argument are all checked through it and cannot be told apart.
+    Opening,
+}
+
+/// Collects the pairing equations registered while verifying a proof
+trait PairingSink<E: MultiMillerLoop> {
+    fn batcher(&mut self, argument: ProofArgument) -> &mut PairingBatcher<E>;
+}
+
+impl<E: MultiMillerLoop> PairingSink<E> for PairingBatcher<E> {
+    fn batcher(&mut self, _: ProofArgument) -> &mut PairingBatcher<E> {
+        self
+    }
+}
+
+/// Keeps the pairing equations of each argument in a separate batcher
+struct ArgumentPairings<E: MultiMillerLoop> {
+    challenge: E::Scalar,
+    batchers: Vec<(ProofArgument, PairingBatcher<E>)>,
+}
+
+impl<E: MultiMillerLoop> PairingSink<E> for ArgumentPairings<E> {
+    fn batcher(&mut self, argument: ProofArgument) -> &mut PairingBatcher<E> {
+        let index = match self.batchers.iter().position(|(a, _)| *a == argument) {
+            Some(index) => index,
+            None => {
+                self.batchers
+                    .push((argument, PairingBatcher::new(self.challenge)));
+                self.batchers.len() - 1
+            }
+        };
+        &mut self.batchers[index].1
+    }
+}
+
+fn pairings_hold<E: MultiMillerLoop>(pairing_batcher: PairingBatcher<E>) -> bool {
+    let terms = pairing_batcher.finalize();
+    let terms: Vec<_> = terms.iter().map(|(g1, g2)| (g1, g2)).collect();
+
+    bool::from(
+        E::multi_miller_loop(&terms)
+            .final_exponentiation()
+            .is_identity(),
+    )
+}
+
 /// Returns a boolean indicating whether or not the proof is valid
 pub fn verify_proof<
     'params,
@@ -45,6 +99,82 @@ pub fn verify_proof<
     instances: &[&[&[E::Scalar]]],
     transcript: &mut T,
 ) -> Result<PairingBatcher<E>, Error>
+where
+    E::G1Affine: SerdeObject,
+    E::G2Affine: SerdeObject,
+{
+    verify_proof_with_sink::<E, V, EC, T, Strategy, _>(
+        params,
+        vk,
+        strategy,
+        instances,
+        transcript,
+        PairingBatcher::new,
+    )
+}
+
+/// Verifies a proof and checks the pairing equations of each argument on
+/// their own, so that a failing proof reports which argument failed.
+///
+/// The static lookups are checked first, in order, and the multiopen argument
+/// last. This is slower than checking the [`PairingBatcher`] returned by
+/// [`verify_proof`] and is meant for debugging rejected proofs.
+pub fn verify_proof_per_argument<
+    'params,
+    E: MultiMillerLoop + Debug,
+    V: Verifier<'params, E>,
+    EC: EncodedChallenge<E::G1Affine>,
+    T: TranscriptRead<E::G1Affine, EC>,
+    Strategy: VerificationStrategy<'params, E, V, Output = Strategy>,
+>(
+    params: &'params <KZGCommitmentScheme<E> as CommitmentScheme>::ParamsVerifier,
+    vk: &VerifyingKey<E>,
+    strategy: Strategy,
+    instances: &[&[&[E::Scalar]]],
+    transcript: &mut T,
+) -> Result<(), Error>
+where
+    E::G1Affine: SerdeObject,
+    E::G2Affine: SerdeObject,
+{
+    let mut pairings = verify_proof_with_sink::<E, V, EC, T, Strategy, _>(
+        params,
+        vk,
+        strategy,
+        instances,
+        transcript,
+        |challenge| ArgumentPairings {
+            challenge,
+            batchers: vec![],
+        },
+    )?;
+
+    pairings.batchers.sort_by_key(|(argument, _)| *argument);
+    for (argument, pairing_batcher) in pairings.batchers {
+        if !pairings_hold(pairing_batcher) {
+            return Err(Error::ArgumentFailed(argument));
+        }
+    }
+
+    Ok(())
+}
+
+fn verify_proof_with_sink<
+    'params,
+    E: MultiMillerLoop + Debug,
+    V: Verifier<'params, E>,
+    EC: EncodedChallenge<E::G1Affine>,
+    T: TranscriptRead<E::G1Affine, EC>,
+    Strategy: VerificationStrategy<'params, E, V, Output = Strategy>,
+    S: PairingSink<E>,
+>(
+    params: &'params <KZGCommitmentScheme<E> as CommitmentScheme>::ParamsVerifier,
+    vk: &VerifyingKey<E>,
+    strategy: Strategy,
+    instances: &[&[&[E::Scalar]]],
+    transcript: &mut T,
+    new_sink: impl FnOnce(E::Scalar) -> S,
+) -> Result<S, Error>
 where
     E::G1Affine: SerdeObject,
     E::G2Affine: SerdeObject,
@@ -368,7 +498,9 @@ where
                         .chain(
                             static_lookups
                                 .iter()
-                                .flat_map(move |p| p.expressions(vk, l_last, l_blind, beta, x))
+                                .flat_map(move |p| {
+                                    p.expressions(vk, l_last, l_blind, beta, theta, x)
+                                })
                                 .into_iter(),
                         )
                 },
@@ -467,23 +599,64 @@ where
 
     // squeeze a challenge
     let pairing_batcher_challenge: ChallengeScalar<_, ()> = transcript.squeeze_challenge_scalar();
-    let mut pairing_batcher: PairingBatcher<E> =
-        PairingBatcher::<E>::new(*pairing_batcher_challenge);
+    let mut sink = new_sink(*pairing_batcher_challenge);
 
-    strategy.merge_with_pairing_batcher(&mut pairing_batcher);
+    strategy.merge_with_pairing_batcher(sink.batcher(ProofArgument::Opening));
 
     // now register all static lookups pairings
-    let _ = static_lookups
-        .into_iter()
-        .map(|static_lookups| -> Result<Vec<_>, _> {
-            // Hash each lookup permuted commitment
-            static_lookups
-                .iter()
-                .map(|lookup| {
-                    lookup.register_pairings(&vk, params, &mut pairing_batcher, beta, theta)
-                })
-                .collect::<Result<Vec<_>, _>>()
-        })
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(pairing_batcher)
+    for static_lookups in static_lookups.iter() {
+        for (index, lookup) in static_lookups.iter().enumerate() {
+            let pairing_batcher = sink.batcher(ProofArgument::StaticLookup(index));
+            lookup.register_pairings(vk, params, pairing_batcher, beta, theta)?;
+        }
+    }
+    Ok(sink)
+}
+
+/// Verifies several proofs created for the same verifying key and folds all
+/// of their pairing equations into a single [`PairingBatcher`], so that they
+/// can be checked with one `multi_miller_loop`.
+///
+/// Each proof's equations are scaled by an independent random scalar drawn
+/// from `rng`, so that a failing proof cannot be cancelled out by another.
+///
+/// Returns [`Error::NoProofs`] if `transcripts` is empty.
+pub fn verify_proofs<
+    'params,
+    E: MultiMillerLoop + Debug,
+    V: Verifier<'params, E>,
+    EC: EncodedChallenge<E::G1Affine>,
+    T: TranscriptRead<E::G1Affine, EC>,
+    Strategy: VerificationStrategy<'params, E, V, Output = Strategy>,
+    R: RngCore,
+>(
+    params: &'params <KZGCommitmentScheme<E> as CommitmentScheme>::ParamsVerifier,
+    vk: &VerifyingKey<E>,
+    instances: &[&[&[&[E::Scalar]]]],
+    transcripts: &mut [T],
+    mut rng: R,
+) -> Result<PairingBatcher<E>, Error>
+where
+    E::G1Affine: SerdeObject,
+    E::G2Affine: SerdeObject,
+{
+    if instances.len() != transcripts.len() {
+        return Err(Error::InvalidInstances);
+    }
+
+    let mut pairing_batcher: Option<PairingBatcher<E>> = None;
+    for (instances, transcript) in instances.iter().zip(transcripts.iter_mut()) {
+        let strategy = Strategy::new(params);
+        let proof_batcher =
+            verify_proof::<E, V, EC, T, Strategy>(params, vk, strategy, instances, transcript)?;
+
+        match pairing_batcher.as_mut() {
+            None => pairing_batcher = Some(proof_batcher),
+            Some(pairing_batcher) => {
+                pairing_batcher.merge(proof_batcher, E::Scalar::random(&mut rng))
+            }
+        }
+    }
+
+    pairing_batcher.ok_or(Error::NoProofs)
 }
@@ -13,7 +13,9 @@ use halo2curves::FieldExt;
 use std::fmt::Debug;
 use std::io;
 use std::marker::PhantomData;
-use std::ops::{Add, Deref, DerefMut, Index, IndexMut, Mul, RangeFrom, RangeFull, Sub};
+use std::ops::{
+    Add, AddAssign, Deref, DerefMut, Index, IndexMut, Mul, MulAssign, RangeFrom, RangeFull, Sub,
+};
 
 /// Generic commitment scheme structures
 pub mod commitment;
@@ -42,6 +44,20 @@ pub enum Error {
     OpeningError,
     /// Caller needs to re-sample a point
     SamplingError,
+    /// Parameters were requested for a `k` larger than the scalar field supports
+    KTooLarge {
+        /// The requested `k`.
+        k: u32,
+        /// The largest supported `k`.
+        max_k: u32,
+    },
+    /// A polynomial was committed with more coefficients than the parameters have points
+    PolynomialTooLong {
+        /// The length of the polynomial.
+        size: usize,
+        /// The number of points in the parameters.
+        n: u64,
+    },
 }
 
 /// The basis over which a polynomial is described.
@@ -149,14 +165,16 @@ impl<F, B> Polynomial<F, B> {
 
 impl<F: SerdePrimeField, B> Polynomial<F, B> {
     /// Reads polynomial from buffer using `SerdePrimeField::read`.  
-    pub(crate) fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> Self {
+    pub(crate) fn read<R: io::Read>(reader: &mut R, format: SerdeFormat) -> io::Result<Self> {
         let mut poly_len = [0u8; 4];
-        reader.read_exact(&mut poly_len).unwrap();
+        reader.read_exact(&mut poly_len)?;
         let poly_len = u32::from_be_bytes(poly_len);
-        Self {
-            values: (0..poly_len).map(|_| F::read(reader, format)).collect(),
+        Ok(Self {
+            values: (0..poly_len)
+                .map(|_| F::read(reader, format))
+                .collect::<io::Result<_>>()?,
             _marker: PhantomData,
-        }
+        })
     }
 
     /// Writes polynomial to buffer using `SerdePrimeField::write`.  
@@ -184,25 +202,33 @@ pub(crate) fn batch_invert_assigned_ref<F: FieldExt>(
         .flat_map(|f| f.iter().map(|value| value.denominator()))
         .collect();
 
-    assigned_denominators
-        .iter_mut()
-        // If the denominator is trivial, we can skip it, reducing the
-        // size of the batch inversion.
-        .filter_map(|d| d.as_mut())
-        .batch_invert();
+    // Every thread batch inverts its own chunk, at the cost of one field inversion each
+    parallelize(&mut assigned_denominators, |denominators, _| {
+        denominators
+            .iter_mut()
+            // If the denominator is trivial, we can skip it, reducing the
+            // size of the batch inversion.
+            .filter_map(|d| d.as_mut())
+            .batch_invert();
+    });
 
     assigned
         .iter()
         .zip(assigned_denominators.chunks(n))
         .map(|(poly, inv_denoms)| {
             debug_assert_eq!(inv_denoms.len(), poly.values.len());
+            let mut values = vec![F::zero(); n];
+            parallelize(&mut values, |values, start| {
+                for ((value, a), inv_den) in values
+                    .iter_mut()
+                    .zip(poly.values[start..].iter())
+                    .zip(inv_denoms[start..].iter())
+                {
+                    *value = a.numerator() * inv_den.unwrap_or(F::one());
+                }
+            });
             Polynomial {
-                values: poly
-                    .values
-                    .iter()
-                    .zip(inv_denoms.iter())
-                    .map(|(a, inv_den)| a.numerator() * inv_den.unwrap_or(F::one()))
-                    .collect(),
+                values,
                 _marker: poly._marker,
             }
         })
@@ -262,13 +288,23 @@ impl<'a, F: Field, B: Basis> Add<&'a Polynomial<F, B>> for Polynomial<F, B> {
     type Output = Polynomial<F, B>;
 
     fn add(mut self, rhs: &'a Polynomial<F, B>) -> Polynomial<F, B> {
+        self += rhs;
+        self
+    }
+}
+
+impl<'a, F: Field, B: Basis> AddAssign<&'a Polynomial<F, B>> for Polynomial<F, B> {
+    fn add_assign(&mut self, rhs: &'a Polynomial<F, B>) {
+        assert_eq!(
+            self.len(),
+            rhs.len(),
+            "polynomials must have the same length"
+        );
         parallelize(&mut self.values, |lhs, start| {
             for (lhs, rhs) in lhs.iter_mut().zip(rhs.values[start..].iter()) {
                 *lhs += *rhs;
             }
         });
-
-        self
     }
 }
 
@@ -276,6 +312,11 @@ impl<'a, F: Field, B: Basis> Sub<&'a Polynomial<F, B>> for Polynomial<F, B> {
     type Output = Polynomial<F, B>;
 
     fn sub(mut self, rhs: &'a Polynomial<F, B>) -> Polynomial<F, B> {
+        assert_eq!(
+            self.len(),
+            rhs.len(),
+            "polynomials must have the same length"
+        );
         parallelize(&mut self.values, |lhs, start| {
             for (lhs, rhs) in lhs.iter_mut().zip(rhs.values[start..].iter()) {
                 *lhs -= *rhs;
@@ -306,14 +347,19 @@ impl<F: Field, B: Basis> Mul<F> for Polynomial<F, B> {
     type Output = Polynomial<F, B>;
 
     fn mul(mut self, rhs: F) -> Polynomial<F, B> {
+        self *= rhs;
+        self
+    }
+}
+
+impl<F: Field, B: Basis> MulAssign<F> for Polynomial<F, B> {
+    fn mul_assign(&mut self, rhs: F) {
         if rhs == F::zero() {
-            return Polynomial {
-                values: vec![F::zero(); self.len()],
-                _marker: PhantomData,
-            };
+            self.values = vec![F::zero(); self.len()];
+            return;
         }
         if rhs == F::one() {
-            return self;
+            return;
         }
 
         parallelize(&mut self.values, |lhs, _| {
@@ -321,8 +367,6 @@ impl<F: Field, B: Basis> Mul<F> for Polynomial<F, B> {
                 *lhs *= rhs;
             }
         });
-
-        self
     }
 }
 
@@ -358,3 +402,116 @@ impl Rotation {
         Rotation(1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{batch_invert_assigned, batch_invert_assigned_ref, LagrangeCoeff, Polynomial};
+    use crate::plonk::Assigned;
+    use ff::Field;
+    use halo2curves::bn256::Fr;
+    use rand_core::OsRng;
+    use std::marker::PhantomData;
+
+    fn random_poly(len: usize) -> Polynomial<Fr, LagrangeCoeff> {
+        Polynomial {
+            values: (0..len).map(|_| Fr::random(OsRng)).collect(),
+            _marker: PhantomData,
+        }
+    }
+
+    #[test]
+    fn scalar_mul_and_add_assign_match_elementwise() {
+        let a = random_poly(64);
+        let b = random_poly(64);
+        let scalar = Fr::random(OsRng);
+
+        let mut acc = a.clone();
+        acc *= scalar;
+        acc += &b;
+        let expected: Vec<Fr> = a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| *a * scalar + b)
+            .collect();
+        assert_eq!(acc.values, expected);
+        assert_eq!((a.clone() * scalar + &b).values, expected);
+
+        let mut zero = a.clone();
+        zero *= Fr::zero();
+        assert!(zero.iter().all(|v| v.is_zero_vartime()));
+
+        let mut same = a.clone();
+        same *= Fr::one();
+        assert_eq!(same.values, a.values);
+    }
+
+    #[test]
+    fn batch_invert_assigned_ref_matches_serial() {
+        // Long enough to be split in several chunks across threads
+        let len = 1 << 10;
+        let assigned: Vec<Polynomial<Assigned<Fr>, LagrangeCoeff>> = (0..3)
+            .map(|_| Polynomial {
+                values: (0..len)
+                    .map(|i| match i % 4 {
+                        0 => Assigned::Zero,
+                        1 => Assigned::Trivial(Fr::random(OsRng)),
+                        2 => Assigned::Rational(Fr::random(OsRng), Fr::random(OsRng)),
+                        // Zero denominators evaluate to zero
+                        _ => Assigned::Rational(Fr::random(OsRng), Fr::zero()),
+                    })
+                    .collect(),
+                _marker: PhantomData,
+            })
+            .collect();
+
+        let parallel = batch_invert_assigned_ref(assigned.iter().collect());
+        let serial = batch_invert_assigned(assigned.clone());
+        assert_eq!(parallel.len(), serial.len());
+        for ((parallel, serial), assigned) in parallel.iter().zip(serial.iter()).zip(assigned) {
+            assert_eq!(parallel.values, serial.values);
+            let evaluated: Vec<Fr> = assigned.iter().map(|a| a.evaluate()).collect();
+            assert_eq!(parallel.values, evaluated);
+        }
+    }
+
+    #[test]
+    fn assigned_plus_constant_inverts_to_sum() {
+        let v = Fr::random(OsRng);
+        let k = Fr::random(OsRng);
+        let d = Fr::random(OsRng);
+
+        let mut compound = Assigned::Rational(v, d);
+        compound += k;
+        compound *= k;
+        compound -= k;
+
+        let assigned = Polynomial::<Assigned<Fr>, LagrangeCoeff> {
+            values: vec![
+                Assigned::from(v) + Assigned::from(k),
+                Assigned::from(v) + k,
+                Assigned::Rational(v, d) + k,
+                compound,
+                Assigned::Zero + k,
+            ],
+            _marker: PhantomData,
+        };
+        let inverted = batch_invert_assigned(vec![assigned]);
+        assert_eq!(
+            inverted[0].values,
+            vec![
+                v + k,
+                v + k,
+                v * d.invert().unwrap() + k,
+                (v * d.invert().unwrap() + k) * k - k,
+                k,
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "polynomials must have the same length")]
+    fn add_assign_rejects_length_mismatch() {
+        let mut a = random_poly(8);
+        a += &random_poly(4);
+    }
+}
@@ -2,13 +2,17 @@
 //! domain that is of a suitable size for the application.
 
 use crate::{
-    arithmetic::{best_fft, parallelize, FieldExt, Group},
+    arithmetic::{best_fft, best_multiexp, parallelize, CurveAffine, FieldExt, Group},
     plonk::Assigned,
 };
 
 use super::{Coeff, ExtendedLagrangeCoeff, LagrangeCoeff, Polynomial, Rotation};
 
-use group::ff::{BatchInvert, Field, PrimeField};
+use group::{
+    ff::{BatchInvert, Field, PrimeField},
+    prime::PrimeCurveAffine,
+    Curve, Group as _,
+};
 
 use std::marker::PhantomData;
 
@@ -477,6 +481,40 @@ impl<G: Group> EvaluationDomain<G> {
         results
     }
 
+    /// Computes the commitments $[(L_i(x) - L_i(0)) / x]_1$ used by cq, given the
+    /// commitments `g_lagrange` $= [L_i(x)]_1$ to the Lagrange basis of this domain.
+    ///
+    /// Since $L_i(0) = 1 / n$ we have
+    ///     $$\frac{L_i(X) - L_i(0)}{X} = \omega^{-i} L_i(X) - \frac{X^{n - 1}}{n},$$
+    /// and $X^{n - 1} = \sum_i \omega^{-i} L_i(X)$, so no other SRS elements are needed.
+    pub fn lagrange_opening_at_0<C: CurveAffine<ScalarExt = G::Scalar>>(
+        &self,
+        g_lagrange: &[C],
+    ) -> Vec<C> {
+        let n = self.n as usize;
+        assert_eq!(g_lagrange.len(), n);
+
+        let omega_inv_powers: Vec<G::Scalar> =
+            std::iter::successors(Some(G::Scalar::one()), |p| Some(*p * self.omega_inv))
+                .take(n)
+                .collect();
+
+        // [x^{n - 1}]_1 * (1 / n)
+        let last_power_scaled = best_multiexp(&omega_inv_powers, g_lagrange) * self.ifft_divisor;
+
+        let mut openings = vec![C::Curve::identity(); n];
+        parallelize(&mut openings, |openings, start| {
+            for (i, opening) in openings.iter_mut().enumerate() {
+                let idx = start + i;
+                *opening = g_lagrange[idx] * omega_inv_powers[idx] - last_power_scaled;
+            }
+        });
+
+        let mut openings_affine = vec![C::identity(); n];
+        C::Curve::batch_normalize(&openings, &mut openings_affine);
+        openings_affine
+    }
+
     /// Gets the quotient polynomial's degree (as a multiple of n)
     pub fn get_quotient_poly_degree(&self) -> usize {
         self.quotient_poly_degree as usize
@@ -1,14 +1,15 @@
 use crate::arithmetic::{
     best_fft, best_multiexp, g_to_lagrange, parallelize, CurveAffine, CurveExt, FieldExt, Group,
 };
-use crate::helpers::SerdeCurveAffine;
+use crate::helpers::{json, read_point_array_json, write_point_slice, SerdeCurveAffine};
 use crate::poly::commitment::{Blind, CommitmentScheme, Params, ParamsProver, ParamsVerifier, MSM};
-use crate::poly::{Coeff, LagrangeCoeff, Polynomial};
+use crate::poly::{Coeff, Error, EvaluationDomain, LagrangeCoeff, Polynomial};
 use crate::SerdeFormat;
 
-use ff::{BatchInvert, Field, PrimeField};
+use ff::{Field, PrimeField};
 use group::{prime::PrimeCurveAffine, Curve, Group as _};
-use halo2curves::pairing::Engine;
+use halo2curves::pairing::{Engine, MillerLoopResult, MultiMillerLoop};
+use halo2curves::serde::SerdeObject;
 use rand_core::{OsRng, RngCore};
 use std::fmt::Debug;
 use std::marker::PhantomData;
@@ -38,6 +39,20 @@ pub struct ParamsKZG<E: Engine> {
     pub(crate) s_g2: E::G2Affine,
 }
 
+// Not derived, as that would require `E: PartialEq`
+impl<E: Engine> PartialEq for ParamsKZG<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.k == other.k
+            && self.n == other.n
+            && self.g == other.g
+            && self.g_lagrange == other.g_lagrange
+            && self.g2 == other.g2
+            && self.s_g2 == other.s_g2
+    }
+}
+
+impl<E: Engine> Eq for ParamsKZG<E> {}
+
 #[derive(Debug, Clone)]
 pub struct TableSRS<E: Engine> {
     pub(crate) g1: Vec<E::G1Affine>,
@@ -46,6 +61,17 @@ pub struct TableSRS<E: Engine> {
     pub(crate) g2: Vec<E::G2Affine>,
 }
 
+impl<E: Engine> PartialEq for TableSRS<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.g1 == other.g1
+            && self.g1_lagrange == other.g1_lagrange
+            && self.g_lagrange_opening_at_0 == other.g_lagrange_opening_at_0
+            && self.g2 == other.g2
+    }
+}
+
+impl<E: Engine> Eq for TableSRS<E> {}
+
 impl<E: Engine> TableSRS<E> {
     /// Return G1
     pub fn g1(&self) -> &[E::G1Affine] {
@@ -66,6 +92,24 @@ impl<E: Engine> TableSRS<E> {
     pub fn g_lagrange_opening_at_0(&self) -> &[E::G1Affine] {
         &self.g_lagrange_opening_at_0
     }
+
+    /// Return the G1 powers that B0 is committed against for a circuit of `2^circuit_k` rows.
+    ///
+    /// B0 has degree at most `n - 2`, so it is shifted by `x^{|g1| - 1 - (n - 2)}` to hit the
+    /// top of the SRS, matching the `x_b0_bound` of [`StaticTableValues::commit`].
+    ///
+    /// Panics if `circuit_k` is zero or the circuit domain is larger than the SRS.
+    ///
+    /// [`StaticTableValues::commit`]: crate::plonk::static_lookup::StaticTableValues::commit
+    pub fn b0_g1_bound(&self, circuit_k: u32) -> Vec<E::G1Affine> {
+        let n = 1usize << circuit_k;
+        assert!(circuit_k >= 1, "circuit domain must have at least two rows");
+        assert!(
+            n <= self.g1.len(),
+            "circuit domain is larger than the table SRS"
+        );
+        self.g1[self.g1.len() - 1 - (n - 2)..].to_vec()
+    }
 }
 
 impl<E: Engine> TableSRS<E> {
@@ -80,28 +124,33 @@ impl<E: Engine> TableSRS<E> {
         let g2_gen = E::G2Affine::generator();
 
         let mut g_projective = vec![E::G1::group_zero(); g1_len as usize];
-
-        // TODO: consider to merge this two parallelize
-        parallelize(&mut g_projective, |g, start| {
-            let mut current_g: E::G1 = g1_gen.into();
-
-            current_g *= s.pow_vartime(&[start as u64]);
-            for g in g.iter_mut() {
-                *g = current_g;
-                current_g *= s;
-            }
-        });
-
         let mut g2_projective = vec![E::G2::group_zero(); g2_len as usize];
-        parallelize(&mut g2_projective, |g, start| {
-            let mut current_g: E::G2 = g2_gen.into();
 
-            current_g *= s.pow_vartime(&[start as u64]);
-            for g in g.iter_mut() {
-                *g = current_g;
-                current_g *= s;
-            }
-        });
+        // Powers in G1 and G2 are computed side by side in the same thread pool
+        rayon::join(
+            || {
+                parallelize(&mut g_projective, |g, start| {
+                    let mut current_g: E::G1 = g1_gen.into();
+
+                    current_g *= s.pow_vartime(&[start as u64]);
+                    for g in g.iter_mut() {
+                        *g = current_g;
+                        current_g *= s;
+                    }
+                })
+            },
+            || {
+                parallelize(&mut g2_projective, |g, start| {
+                    let mut current_g: E::G2 = g2_gen.into();
+
+                    current_g *= s.pow_vartime(&[start as u64]);
+                    for g in g.iter_mut() {
+                        *g = current_g;
+                        current_g *= s;
+                    }
+                })
+            },
+        );
 
         let g1 = {
             let mut g1 = vec![E::G1Affine::identity(); g1_len as usize];
@@ -153,21 +202,8 @@ impl<E: Engine> TableSRS<E> {
             g_lagrange
         };
 
-        //   [(L_i(x) - L_i(0)) / x]_1
-        // = omega^{-i} * [L_i(x)]_1 - (1 / N) * [x^{N-1}]_1
-        let mut roots_of_unity_inv: Vec<E::Scalar> =
-            std::iter::successors(Some(E::Scalar::one()), |p| Some(*p * root))
-                .take(g1_len)
-                .collect();
-        roots_of_unity_inv.iter_mut().batch_invert();
-
-        // [x^{N - 1}]_1 * (1 / N)
-        let last_power_scaled = *g1.last().unwrap() * n_inv;
-        let g_lagrange_opening_at_0: Vec<E::G1Affine> = g1_lagrange
-            .iter()
-            .zip(roots_of_unity_inv.iter())
-            .map(|(&l_i, w_inv_i)| (l_i * w_inv_i - last_power_scaled).into())
-            .collect();
+        let g_lagrange_opening_at_0 =
+            EvaluationDomain::<E::Scalar>::new(1, k).lagrange_opening_at_0(&g1_lagrange);
 
         Self {
             g1,
@@ -177,6 +213,118 @@ impl<E: Engine> TableSRS<E> {
         }
     }
 }
+impl<E: MultiMillerLoop> TableSRS<E> {
+    /// Checks that the SRS is consistent:
+    /// - `g1` and `g2` are chains of powers of the same secret
+    /// - `g1_lagrange` is the Lagrange basis of `g1`
+    /// - `g_lagrange_opening_at_0` matches `g1_lagrange` and the last power in `g1`
+    ///
+    /// Every index is covered at once with a random linear combination.
+    pub fn verify(&self) -> bool {
+        let n = self.g1.len();
+        if n < 2
+            || self.g2.len() < 2
+            || self.g1_lagrange.len() != n
+            || self.g_lagrange_opening_at_0.len() != n
+        {
+            return false;
+        }
+
+        // e([x^i]_1, [x]_2) == e([x^{i+1}]_1, [1]_2)
+        let (g1_current, g1_next) = shifted_combinations(&self.g1);
+        // e([1]_1, [x^{i+1}]_2) == e([x]_1, [x^i]_2)
+        let (g2_current, g2_next) = shifted_combinations(&self.g2);
+
+        if !pairing_equal::<E>((g1_current, self.g2[1]), (g1_next, self.g2[0]))
+            || !pairing_equal::<E>((self.g1[0], g2_next), (self.g1[1], g2_current))
+            || !lagrange_consistent(&self.g1, &self.g1_lagrange)
+        {
+            return false;
+        }
+
+        //   [(L_i(x) - L_i(0)) / x]_1
+        // = omega^{-i} * [L_i(x)]_1 - (1 / N) * [x^{N-1}]_1
+        let domain = EvaluationDomain::<E::Scalar>::new(1, log2(n));
+        let n_inv = E::Scalar::from(n as u64).invert().unwrap();
+        let r: Vec<E::Scalar> = (0..n).map(|_| E::Scalar::random(OsRng)).collect();
+        let r_omega: Vec<E::Scalar> = std::iter::successors(Some(E::Scalar::one()), |p| {
+            Some(*p * domain.get_omega_inv())
+        })
+        .zip(r.iter())
+        .map(|(omega_inv_i, r_i)| omega_inv_i * r_i)
+        .collect();
+        let r_sum = r.iter().fold(E::Scalar::zero(), |acc, r_i| acc + r_i);
+
+        let openings = best_multiexp(&r, &self.g_lagrange_opening_at_0);
+        let expected =
+            best_multiexp(&r_omega, &self.g1_lagrange) - self.g1[n - 1] * (r_sum * n_inv);
+
+        openings == expected
+    }
+}
+
+impl<E: MultiMillerLoop + Debug> ParamsKZG<E> {
+    /// Checks that the parameters are consistent:
+    /// - `g` is a chain of powers of the secret in `s_g2`
+    /// - `g_lagrange` is the Lagrange basis of `g`
+    ///
+    /// Every index is covered at once with a random linear combination.
+    pub fn verify(&self) -> bool {
+        if self.g.len() as u64 != self.n || self.g_lagrange.len() as u64 != self.n {
+            return false;
+        }
+
+        // e([x^i]_1, [x]_2) == e([x^{i+1}]_1, [1]_2)
+        let (current, next) = shifted_combinations(&self.g);
+
+        pairing_equal::<E>((current, self.s_g2), (next, self.g2))
+            && lagrange_consistent(&self.g, &self.g_lagrange)
+    }
+}
+
+/// Checks `e(lhs.0, lhs.1) == e(rhs.0, rhs.1)`
+pub(crate) fn pairing_equal<E: MultiMillerLoop>(
+    lhs: (E::G1Affine, E::G2Affine),
+    rhs: (E::G1Affine, E::G2Affine),
+) -> bool {
+    let lhs_g2 = E::G2Prepared::from(lhs.1);
+    let rhs_g2 = E::G2Prepared::from(-rhs.1);
+
+    bool::from(
+        E::multi_miller_loop(&[(&lhs.0, &lhs_g2), (&rhs.0, &rhs_g2)])
+            .final_exponentiation()
+            .is_identity(),
+    )
+}
+
+/// Returns `(sum r_i * P_i, sum r_i * P_{i+1})` for random `r_i`
+fn shifted_combinations<C: CurveAffine>(points: &[C]) -> (C, C) {
+    let r: Vec<C::Scalar> = (1..points.len())
+        .map(|_| C::Scalar::random(OsRng))
+        .collect();
+
+    (
+        best_multiexp(&r, &points[..points.len() - 1]).to_affine(),
+        best_multiexp(&r, &points[1..]).to_affine(),
+    )
+}
+
+/// Checks that `lagrange` is the Lagrange basis of `monomial` by committing
+/// to random evaluations in both bases
+fn lagrange_consistent<C: CurveAffine>(monomial: &[C], lagrange: &[C]) -> bool {
+    let n = monomial.len();
+    if lagrange.len() != n || !is_pow_2(n) {
+        return false;
+    }
+
+    let domain = EvaluationDomain::<C::Scalar>::new(1, log2(n));
+    let values: Vec<C::Scalar> = (0..n).map(|_| C::Scalar::random(OsRng)).collect();
+    let lagrange_cm = best_multiexp(&values, lagrange);
+    let coeffs = domain.lagrange_to_coeff(domain.lagrange_from_vec(values));
+
+    best_multiexp(&coeffs, monomial) == lagrange_cm
+}
+
 /// Umbrella commitment scheme construction for all KZG variants
 #[derive(Debug)]
 pub struct KZGCommitmentScheme<E: Engine> {
@@ -204,6 +352,53 @@ where
 }
 
 impl<E: Engine + Debug> ParamsKZG<E> {
+    /// Largest `k` parameters can be set up for, since FFTs are only supported
+    /// over domains of size up to `2^E::Scalar::S`.
+    pub const fn max_k() -> u32 {
+        E::Scalar::S
+    }
+
+    /// Same as [`Self::setup`], but returns an error instead of panicking when `k`
+    /// exceeds [`Self::max_k`].
+    pub fn try_setup<R: RngCore>(k: u32, rng: R) -> Result<Self, Error> {
+        if k > Self::max_k() {
+            return Err(Error::KTooLarge {
+                k,
+                max_k: Self::max_k(),
+            });
+        }
+        Ok(Self::setup(k, rng))
+    }
+
+    /// Same as [`Params::commit_lagrange`], but returns an error instead of panicking
+    /// when `poly` has more evaluations than the parameters have points.
+    pub fn try_commit_lagrange(
+        &self,
+        poly: &Polynomial<E::Scalar, LagrangeCoeff>,
+        _: Blind<E::Scalar>,
+    ) -> Result<E::G1, Error> {
+        let size = self.check_commit_size(poly.len())?;
+        Ok(best_multiexp(poly, &self.g_lagrange[0..size]))
+    }
+
+    /// Same as [`ParamsProver::commit`], but returns an error instead of panicking
+    /// when `poly` has more coefficients than the parameters have points.
+    pub fn try_commit(
+        &self,
+        poly: &Polynomial<E::Scalar, Coeff>,
+        _: Blind<E::Scalar>,
+    ) -> Result<E::G1, Error> {
+        let size = self.check_commit_size(poly.len())?;
+        Ok(best_multiexp(poly, &self.g[0..size]))
+    }
+
+    fn check_commit_size(&self, size: usize) -> Result<usize, Error> {
+        if size as u64 > self.n {
+            return Err(Error::PolynomialTooLong { size, n: self.n });
+        }
+        Ok(size)
+    }
+
     /// Initializes parameters for the curve, draws toxic secret from given rng.
     /// MUST NOT be used in production.
     pub fn setup_from_toxic_waste(k: u32, s: E::Scalar) -> Self {
@@ -362,12 +557,98 @@ impl<E: Engine + Debug> ParamsKZG<E> {
         &self.g
     }
 
-    /// Writes parameters to buffer
+    /// Commits to several polynomials in Lagrange form at once, in parallel
+    /// across polynomials. Points are returned in projective form so that the
+    /// caller can normalize them with a single `batch_normalize`.
+    pub fn commit_lagrange_batch(
+        &self,
+        polys: &[Polynomial<E::Scalar, LagrangeCoeff>],
+    ) -> Vec<E::G1> {
+        use rayon::prelude::*;
+
+        polys
+            .par_iter()
+            .map(|poly| {
+                let size = poly.len();
+                assert!(self.n >= size as u64);
+                best_multiexp(poly, &self.g_lagrange[0..size])
+            })
+            .collect()
+    }
+
+    /// Builds parameters for `k` from a stored SRS in monomial form holding at
+    /// least `2^k` points, recomputing the Lagrange basis.
+    pub fn from_srs(k: u32, srs_g1: &[E::G1Affine], g2: E::G2Affine, s_g2: E::G2Affine) -> Self {
+        assert!(k <= E::Scalar::S);
+        let n: u64 = 1 << k;
+        assert!(
+            srs_g1.len() as u64 >= n,
+            "SRS holds {} points, {} needed",
+            srs_g1.len(),
+            n
+        );
+
+        let g = srs_g1[..n as usize].to_vec();
+        let g_lagrange = g_to_lagrange(g.iter().map(|g| g.to_curve()).collect(), k);
+
+        Self {
+            k,
+            n,
+            g,
+            g_lagrange,
+            g2,
+            s_g2,
+        }
+    }
+
+    /// Builds parameters spanning the whole of a [`TableSRS`], reusing its Lagrange basis
+    /// instead of recomputing it.
+    pub fn from_table_srs(srs: &TableSRS<E>) -> Self {
+        let n = srs.g1.len();
+        assert!(
+            n > 0 && is_pow_2(n),
+            "SRS holds {} points, expected a power of two",
+            n
+        );
+        assert_eq!(srs.g1_lagrange.len(), n);
+        assert!(srs.g2.len() >= 2, "SRS holds fewer than two G2 points");
+        let k = log2(n);
+        assert!(k <= E::Scalar::S);
+
+        Self {
+            k,
+            n: n as u64,
+            g: srs.g1.clone(),
+            g_lagrange: srs.g1_lagrange.clone(),
+            g2: srs.g2[0],
+            s_g2: srs.g2[1],
+        }
+    }
+
+    /// Extends parameters to a larger `k` using a stored SRS in monomial form
+    /// which must start with the current `g`. Counterpart of `downsize`.
+    pub fn upsize(&mut self, k: u32, srs_g1: &[E::G1Affine]) {
+        assert!(k >= self.k);
+        assert!(
+            srs_g1.starts_with(&self.g),
+            "SRS does not extend the current parameters"
+        );
+
+        *self = Self::from_srs(k, srs_g1, self.g2, self.s_g2);
+    }
+
+    /// Writes parameters to buffer.
+    ///
+    /// With `SerdeFormat::Json` writes `{"k":..,"g":[..],"g_lagrange":[..],"g2":..,"s_g2":..}`.
     pub fn write_custom<W: io::Write>(&self, writer: &mut W, format: SerdeFormat)
     where
         E::G1Affine: SerdeCurveAffine,
         E::G2Affine: SerdeCurveAffine,
     {
+        if let SerdeFormat::Json = format {
+            return self.write_json(writer).unwrap();
+        }
+
         writer.write_all(&self.k.to_le_bytes()).unwrap();
         for el in self.g.iter() {
             el.write(writer, format);
@@ -380,25 +661,32 @@ impl<E: Engine + Debug> ParamsKZG<E> {
     }
 
     /// Reads params from a buffer.
-    pub fn read_custom<R: io::Read>(reader: &mut R, format: SerdeFormat) -> Self
+    ///
+    /// Fails if the buffer ends early, holds an invalid point encoding or a `k`
+    /// larger than the two-adicity of the scalar field.
+    pub fn read_custom<R: io::Read>(reader: &mut R, format: SerdeFormat) -> io::Result<Self>
     where
         E::G1Affine: SerdeCurveAffine,
         E::G2Affine: SerdeCurveAffine,
     {
+        if let SerdeFormat::Json = format {
+            return Self::read_json(reader);
+        }
+
         let mut k = [0u8; 4];
-        reader.read_exact(&mut k[..]).unwrap();
-        let k = u32::from_le_bytes(k);
+        reader.read_exact(&mut k[..])?;
+        let k = Self::check_stored_k(u32::from_le_bytes(k))?;
         let n = 1 << k;
 
         let (g, g_lagrange) = match format {
             SerdeFormat::Processed => {
                 use group::GroupEncoding;
                 let load_points_from_file_parallelly =
-                    |reader: &mut R| -> Vec<Option<E::G1Affine>> {
+                    |reader: &mut R| -> io::Result<Vec<E::G1Affine>> {
                         let mut points_compressed =
                             vec![<<E as Engine>::G1Affine as GroupEncoding>::Repr::default(); n];
                         for points_compressed in points_compressed.iter_mut() {
-                            reader.read_exact((*points_compressed).as_mut()).unwrap();
+                            reader.read_exact((*points_compressed).as_mut())?;
                         }
 
                         let mut points = vec![Option::<E::G1Affine>::None; n];
@@ -410,52 +698,206 @@ impl<E: Engine + Debug> ParamsKZG<E> {
                             }
                         });
                         points
+                            .into_iter()
+                            .map(|point| {
+                                point.ok_or_else(|| {
+                                    io::Error::new(
+                                        io::ErrorKind::InvalidData,
+                                        "invalid point encoding",
+                                    )
+                                })
+                            })
+                            .collect()
                     };
 
-                let g = load_points_from_file_parallelly(reader);
-                let g: Vec<<E as Engine>::G1Affine> = g
-                    .iter()
-                    .map(|point| point.unwrap_or_else(|| panic!("invalid point encoding")))
-                    .collect();
-                let g_lagrange = load_points_from_file_parallelly(reader);
-                let g_lagrange: Vec<<E as Engine>::G1Affine> = g_lagrange
-                    .iter()
-                    .map(|point| point.unwrap_or_else(|| panic!("invalid point encoding")))
-                    .collect();
+                let g = load_points_from_file_parallelly(reader)?;
+                let g_lagrange = load_points_from_file_parallelly(reader)?;
                 (g, g_lagrange)
             }
             SerdeFormat::RawBytes => {
                 let g = (0..n)
                     .map(|_| <E::G1Affine as SerdeCurveAffine>::read(reader, format))
-                    .collect();
+                    .collect::<io::Result<_>>()?;
                 let g_lagrange = (0..n)
                     .map(|_| <E::G1Affine as SerdeCurveAffine>::read(reader, format))
-                    .collect();
+                    .collect::<io::Result<_>>()?;
                 (g, g_lagrange)
             }
             SerdeFormat::RawBytesUnchecked => {
                 // avoid try branching for performance
                 let g = (0..n)
-                    .map(|_| <E::G1Affine as SerdeCurveAffine>::read(reader, format))
+                    .map(|_| <E::G1Affine as SerdeObject>::read_raw_unchecked(reader))
                     .collect::<Vec<_>>();
                 let g_lagrange = (0..n)
-                    .map(|_| <E::G1Affine as SerdeCurveAffine>::read(reader, format))
+                    .map(|_| <E::G1Affine as SerdeObject>::read_raw_unchecked(reader))
                     .collect::<Vec<_>>();
                 (g, g_lagrange)
             }
+            SerdeFormat::Json => unreachable!("handled by read_json"),
         };
 
-        let g2 = E::G2Affine::read(reader, format);
-        let s_g2 = E::G2Affine::read(reader, format);
+        let g2 = E::G2Affine::read(reader, format)?;
+        let s_g2 = E::G2Affine::read(reader, format)?;
 
-        Self {
+        Ok(Self {
             k,
             n: n as u64,
             g,
             g_lagrange,
             g2,
             s_g2,
+        })
+    }
+
+    fn write_json<W: io::Write>(&self, writer: &mut W) -> io::Result<()>
+    where
+        E::G1Affine: SerdeCurveAffine,
+        E::G2Affine: SerdeCurveAffine,
+    {
+        let format = SerdeFormat::Json;
+
+        writer.write_all(b"{")?;
+        json::write_key(writer, "k")?;
+        write!(writer, "{},", self.k)?;
+        json::write_key(writer, "g")?;
+        write_point_slice(&self.g, writer, format);
+        writer.write_all(b",")?;
+        json::write_key(writer, "g_lagrange")?;
+        write_point_slice(&self.g_lagrange, writer, format);
+        writer.write_all(b",")?;
+        json::write_key(writer, "g2")?;
+        self.g2.write(writer, format);
+        writer.write_all(b",")?;
+        json::write_key(writer, "s_g2")?;
+        self.s_g2.write(writer, format);
+        writer.write_all(b"}")
+    }
+
+    fn read_json<R: io::Read>(reader: &mut R) -> io::Result<Self>
+    where
+        E::G1Affine: SerdeCurveAffine,
+        E::G2Affine: SerdeCurveAffine,
+    {
+        json::expect(reader, b'{')?;
+        json::read_key(reader, "k")?;
+        let k = Self::check_stored_k(json::read_u32(reader, b',')?)?;
+        let n = 1u64 << k;
+
+        json::read_key(reader, "g")?;
+        let g: Vec<E::G1Affine> = read_point_array_json(reader)?;
+        json::expect(reader, b',')?;
+        json::read_key(reader, "g_lagrange")?;
+        let g_lagrange: Vec<E::G1Affine> = read_point_array_json(reader)?;
+        json::expect(reader, b',')?;
+        if g.len() as u64 != n || g_lagrange.len() as u64 != n {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected {} points for k = {}", n, k),
+            ));
         }
+
+        json::read_key(reader, "g2")?;
+        let g2 = E::G2Affine::read(reader, SerdeFormat::Json)?;
+        json::expect(reader, b',')?;
+        json::read_key(reader, "s_g2")?;
+        let s_g2 = E::G2Affine::read(reader, SerdeFormat::Json)?;
+        json::expect(reader, b'}')?;
+
+        Ok(Self {
+            k,
+            n,
+            g,
+            g_lagrange,
+            g2,
+            s_g2,
+        })
+    }
+
+    /// Rejects a `k` read from a buffer that no evaluation domain of the scalar
+    /// field can have, before it is used to size any allocation.
+    fn check_stored_k(k: u32) -> io::Result<u32> {
+        if k > E::Scalar::S {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("k = {} exceeds the two-adicity of the scalar field", k),
+            ));
+        }
+        Ok(k)
+    }
+
+    /// Reads params from a buffer written by `write_custom`, keeping at most
+    /// `2^k_max` points of `g`. The remaining points are skipped without being
+    /// decoded and the Lagrange basis is recomputed for the kept prefix, so the
+    /// memory needed is bounded by `k_max` rather than by the stored `k`.
+    /// `SerdeFormat::Json` parameters are read in full before being cut down.
+    ///
+    /// Fails if the buffer ends early, holds an invalid point encoding or a `k`
+    /// larger than the two-adicity of the scalar field.
+    pub fn read_custom_up_to<R: io::Read>(
+        reader: &mut R,
+        format: SerdeFormat,
+        k_max: u32,
+    ) -> io::Result<Self>
+    where
+        E::G1Affine: SerdeCurveAffine,
+        E::G2Affine: SerdeCurveAffine,
+    {
+        if let SerdeFormat::Json = format {
+            let params = Self::read_json(reader)?;
+            let k = std::cmp::min(params.k, k_max);
+            return Ok(Self::from_srs(k, &params.g, params.g2, params.s_g2));
+        }
+
+        let mut k = [0u8; 4];
+        reader.read_exact(&mut k[..])?;
+        let stored_k = Self::check_stored_k(u32::from_le_bytes(k))?;
+        let stored_n = 1usize << stored_k;
+
+        let k = std::cmp::min(stored_k, k_max);
+        let n = 1usize << k;
+
+        let point_len = {
+            let mut bytes = vec![];
+            E::G1Affine::generator().write(&mut bytes, format);
+            bytes.len() as u64
+        };
+        let skip_points = |reader: &mut R, count: usize| -> io::Result<()> {
+            let len = point_len * count as u64;
+            let skipped = io::copy(&mut reader.by_ref().take(len), &mut io::sink())?;
+            if skipped != len {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "unexpected end of params",
+                ));
+            }
+            Ok(())
+        };
+
+        let g = (0..n)
+            .map(|_| <E::G1Affine as SerdeCurveAffine>::read(reader, format))
+            .collect::<io::Result<Vec<_>>>()?;
+        skip_points(reader, stored_n - n)?;
+
+        let g_lagrange = if k == stored_k {
+            (0..n)
+                .map(|_| <E::G1Affine as SerdeCurveAffine>::read(reader, format))
+                .collect::<io::Result<_>>()?
+        } else {
+            skip_points(reader, stored_n)?;
+            g_to_lagrange(g.iter().map(|g| g.to_curve()).collect(), k)
+        };
+
+        let g2 = E::G2Affine::read(reader, format)?;
+        let s_g2 = E::G2Affine::read(reader, format)?;
+
+        Ok(Self {
+            k,
+            n: n as u64,
+            g,
+            g_lagrange,
+            g2,
+            s_g2,
+        })
     }
 }
 
@@ -493,14 +935,16 @@ where
         MSMKZG::new()
     }
 
+    // The blind is ignored: KZG openings do not carry a blinding term, so a
+    // blinded commitment could not be opened. Hiding comes from the random
+    // values the prover writes into the `blinding_factors()` unusable rows.
     fn commit_lagrange(
         &self,
         poly: &Polynomial<E::Scalar, LagrangeCoeff>,
-        _: Blind<E::Scalar>,
+        blind: Blind<E::Scalar>,
     ) -> E::G1 {
-        let size = poly.len();
-        assert!(self.n() >= size as u64);
-        best_multiexp(poly, &self.g_lagrange[0..size])
+        self.try_commit_lagrange(poly, blind)
+            .expect("polynomial is longer than the parameters")
     }
 
     /// Writes params to a buffer.
@@ -510,7 +954,7 @@ where
 
     /// Reads params from a buffer.
     fn read<R: io::Read>(reader: &mut R) -> io::Result<Self> {
-        Ok(Self::read_custom(reader, SerdeFormat::RawBytesUnchecked))
+        Self::read_custom(reader, SerdeFormat::RawBytesUnchecked)
     }
 }
 
@@ -536,10 +980,10 @@ where
         unreachable!()
     }
 
-    fn commit(&self, poly: &Polynomial<E::Scalar, Coeff>, _: Blind<E::Scalar>) -> E::G1 {
-        let size = poly.len();
-        assert!(self.n() >= size as u64);
-        best_multiexp(poly, &self.g[0..size])
+    // See `commit_lagrange` for why the blind is ignored.
+    fn commit(&self, poly: &Polynomial<E::Scalar, Coeff>, blind: Blind<E::Scalar>) -> E::G1 {
+        self.try_commit(poly, blind)
+            .expect("polynomial is longer than the parameters")
     }
 
     fn get_g(&self) -> &[E::G1Affine] {
@@ -592,6 +1036,125 @@ mod test {
         assert_eq!(params.commit(&b, alpha), params.commit_lagrange(&a, alpha));
     }
 
+    #[test]
+    fn test_commit_lagrange_batch() {
+        use crate::poly::EvaluationDomain;
+        use halo2curves::bn256::{Bn256, Fr};
+        use rand_core::OsRng;
+
+        const K: u32 = 5;
+        let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(K, Fr::random(OsRng));
+        let domain = EvaluationDomain::new(1, K);
+
+        let polys: Vec<_> = (0..4)
+            .map(|_| {
+                let mut poly = domain.empty_lagrange();
+                for value in poly.iter_mut() {
+                    *value = Fr::random(OsRng);
+                }
+                poly
+            })
+            .collect();
+
+        let expected: Vec<_> = polys
+            .iter()
+            .map(|poly| params.commit_lagrange(poly, Blind::default()))
+            .collect();
+
+        assert_eq!(params.commit_lagrange_batch(&polys), expected);
+    }
+
+    #[test]
+    fn test_upsize_from_stored_srs() {
+        use crate::poly::EvaluationDomain;
+        use halo2curves::bn256::{Bn256, Fr};
+        use rand_core::OsRng;
+
+        let s = Fr::random(OsRng);
+        let stored = ParamsKZG::<Bn256>::setup_from_toxic_waste(8, s);
+
+        let mut params = ParamsKZG::<Bn256>::setup_from_toxic_waste(4, s);
+        params.upsize(6, stored.g1_srs());
+
+        let expected = ParamsKZG::<Bn256>::setup_from_toxic_waste(6, s);
+        assert_eq!(params.k, 6);
+        assert_eq!(params.n, 64);
+        assert_eq!(params.g, expected.g);
+        assert_eq!(params.g_lagrange, expected.g_lagrange);
+        assert_eq!(params.s_g2, expected.s_g2);
+
+        let domain = EvaluationDomain::new(1, 6);
+        let mut a = domain.empty_lagrange();
+        for (i, a) in a.iter_mut().enumerate() {
+            *a = Fr::from(i as u64);
+        }
+        let b = domain.lagrange_to_coeff(a.clone());
+
+        let blind = Blind(Fr::zero());
+        assert_eq!(params.commit(&b, blind), params.commit_lagrange(&a, blind));
+    }
+
+    #[test]
+    fn test_params_from_table_srs() {
+        use crate::poly::kzg::commitment::TableSRS;
+        use crate::poly::EvaluationDomain;
+        use halo2curves::bn256::{Bn256, Fr};
+        use rand_core::OsRng;
+
+        let s = Fr::random(OsRng);
+        let srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, s);
+        let params = ParamsKZG::<Bn256>::from_table_srs(&srs);
+        let expected = ParamsKZG::<Bn256>::setup_from_toxic_waste(4, s);
+        assert!(params == expected);
+
+        let domain = EvaluationDomain::new(1, 4);
+        let mut a = domain.empty_lagrange();
+        for (i, a) in a.iter_mut().enumerate() {
+            *a = Fr::from(i as u64);
+        }
+        let b = domain.lagrange_to_coeff(a.clone());
+
+        let blind = Blind(Fr::zero());
+        assert_eq!(params.commit(&b, blind), expected.commit(&b, blind));
+        assert_eq!(
+            params.commit_lagrange(&a, blind),
+            expected.commit(&b, blind)
+        );
+    }
+
+    #[test]
+    fn test_verify_srs() {
+        use crate::poly::kzg::commitment::TableSRS;
+        use halo2curves::bn256::{Bn256, Fr};
+        use rand_core::OsRng;
+
+        let s = Fr::random(OsRng);
+
+        let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(4, s);
+        assert!(params.verify());
+
+        let mut corrupted = params.clone();
+        corrupted.g[3] = (corrupted.g[3].to_curve() + G1Affine::generator()).to_affine();
+        assert!(!corrupted.verify());
+
+        let mut corrupted = params;
+        corrupted.g_lagrange[2] =
+            (corrupted.g_lagrange[2].to_curve() + G1Affine::generator()).to_affine();
+        assert!(!corrupted.verify());
+
+        let table_srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, s);
+        assert!(table_srs.verify());
+
+        let mut corrupted = table_srs.clone();
+        corrupted.g1[5] = (corrupted.g1[5].to_curve() + G1Affine::generator()).to_affine();
+        assert!(!corrupted.verify());
+
+        let mut corrupted = table_srs;
+        corrupted.g_lagrange_opening_at_0[7] =
+            (corrupted.g_lagrange_opening_at_0[7].to_curve() + G1Affine::generator()).to_affine();
+        assert!(!corrupted.verify());
+    }
+
     #[test]
     fn test_parameter_serialisation_roundtrip() {
         const K: u32 = 4;
@@ -609,14 +1172,231 @@ mod test {
         <ParamsKZG<_> as Params<_>>::write(&params0, &mut data).unwrap();
         let params1: ParamsKZG<Bn256> = Params::read::<_>(&mut &data[..]).unwrap();
 
-        assert_eq!(params0.k, params1.k);
-        assert_eq!(params0.n, params1.n);
-        assert_eq!(params0.g.len(), params1.g.len());
-        assert_eq!(params0.g_lagrange.len(), params1.g_lagrange.len());
+        assert_eq!(params0, params1);
+
+        // Every field takes part in the comparison
+        let mut other = params1.clone();
+        other.s_g2 = other.g2;
+        assert_ne!(params0, other);
+        let mut other = params1.clone();
+        other.g_lagrange.swap(0, 1);
+        assert_ne!(params0, other);
+    }
+
+    #[test]
+    fn test_params_json_roundtrip() {
+        use crate::halo2curves::bn256::{Bn256, Fr};
+        use crate::SerdeFormat;
+
+        let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(3, Fr::from(42));
+        let mut data = vec![];
+        params.write_custom(&mut data, SerdeFormat::Json);
+
+        let json = String::from_utf8(data.clone()).unwrap();
+        assert!(json.starts_with("{\"k\":3,\"g\":[\"0x"));
+
+        let reloaded = ParamsKZG::<Bn256>::read_custom(&mut &data[..], SerdeFormat::Json).unwrap();
+        assert_eq!(reloaded, params);
+    }
+
+    #[test]
+    fn test_read_params_rejects_malformed_input() {
+        use crate::halo2curves::bn256::{Bn256, Fr};
+        use crate::SerdeFormat;
+
+        let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(3, Fr::from(42));
+
+        let mut data = vec![];
+        params.write_custom(&mut data, SerdeFormat::Json);
+        let json = String::from_utf8(data).unwrap();
+        for malformed in [
+            json.replace("\"s_g2\":\"0x", "\"s_g2\":\"0y"),
+            json.replace("\"g2\"", "\"h2\""),
+            json[..json.len() - 10].to_string(),
+        ] {
+            assert!(
+                ParamsKZG::<Bn256>::read_custom(&mut malformed.as_bytes(), SerdeFormat::Json)
+                    .is_err()
+            );
+        }
+
+        for format in [SerdeFormat::Processed, SerdeFormat::RawBytes] {
+            let mut data = vec![];
+            params.write_custom(&mut data, format);
+            let truncated = &data[..data.len() - 1];
+            let err = ParamsKZG::<Bn256>::read_custom(&mut &truncated[..], format).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+            let err =
+                ParamsKZG::<Bn256>::read_custom_up_to(&mut &truncated[..], format, 2).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+            // a corrupt k is rejected before it sizes anything
+            let mut corrupt = data.clone();
+            corrupt[..4].copy_from_slice(&64u32.to_le_bytes());
+            let err = ParamsKZG::<Bn256>::read_custom(&mut &corrupt[..], format).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+            let err =
+                ParamsKZG::<Bn256>::read_custom_up_to(&mut &corrupt[..], format, 2).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn test_read_params_up_to() {
+        use crate::halo2curves::bn256::{Bn256, Fr};
+        use crate::SerdeFormat;
+
+        const K: u32 = 4;
+        const K_MAX: u32 = 2;
+
+        let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(K, Fr::from(42));
+        let mut downsized = params.clone();
+        downsized.downsize(K_MAX);
+
+        for format in [
+            SerdeFormat::Processed,
+            SerdeFormat::RawBytes,
+            SerdeFormat::RawBytesUnchecked,
+            SerdeFormat::Json,
+        ] {
+            let mut data = vec![];
+            params.write_custom(&mut data, format);
+            // trailing data must be left untouched by the reader
+            data.extend_from_slice(&[1, 2, 3]);
+
+            let mut reader = &data[..];
+            let lazy = ParamsKZG::<Bn256>::read_custom_up_to(&mut reader, format, K_MAX).unwrap();
+            assert_eq!(reader, &[1, 2, 3]);
+
+            assert_eq!(lazy, downsized);
+
+            // a bound above the stored size reads everything
+            let full =
+                ParamsKZG::<Bn256>::read_custom_up_to(&mut &data[..], format, K + 1).unwrap();
+            assert_eq!(full, params);
+        }
+    }
+
+    #[test]
+    fn test_b0_g1_bound() {
+        use super::TableSRS;
+        use halo2curves::bn256::{Bn256, Fr};
+        use rand_core::OsRng;
+
+        const K: u32 = 3;
+        let srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, Fr::random(OsRng));
+
+        // A 16 element SRS places B0 right after the circuit domain
+        assert_eq!(srs.b0_g1_bound(K), srs.g1()[((1 << K) + 1)..].to_vec());
+        assert_eq!(srs.b0_g1_bound(K).len(), (1 << K) - 1);
+
+        let larger_srs = TableSRS::<Bn256>::setup_from_toxic_waste(31, 32, Fr::random(OsRng));
+        assert_eq!(larger_srs.b0_g1_bound(K), larger_srs.g1()[25..].to_vec());
+    }
+
+    #[test]
+    #[should_panic(expected = "circuit domain must have at least two rows")]
+    fn test_b0_g1_bound_rejects_single_row_domain() {
+        use super::TableSRS;
+        use halo2curves::bn256::{Bn256, Fr};
+
+        let srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, Fr::from(7));
+        srs.b0_g1_bound(0);
+    }
+
+    #[test]
+    fn test_try_setup_bounds_k() {
+        use super::ParamsKZG;
+        use crate::poly::Error;
+        use halo2curves::bn256::{Bn256, Fr};
+        use rand_core::OsRng;
+
+        assert_eq!(ParamsKZG::<Bn256>::max_k(), Fr::S);
+
+        let max_k = ParamsKZG::<Bn256>::max_k();
+        assert!(matches!(
+            ParamsKZG::<Bn256>::try_setup(max_k + 1, OsRng),
+            Err(Error::KTooLarge { k, max_k: bound }) if k == max_k + 1 && bound == max_k
+        ));
+
+        // A full size setup is too expensive for a test, any k in range takes the same path
+        let params = ParamsKZG::<Bn256>::try_setup(4, OsRng).unwrap();
+        assert_eq!(params.k(), 4);
+    }
+
+    #[test]
+    fn test_try_commit_rejects_oversize_polynomial() {
+        use super::ParamsKZG;
+        use crate::poly::{Error, EvaluationDomain};
+        use halo2curves::bn256::{Bn256, Fr};
+        use rand_core::OsRng;
+
+        let params = ParamsKZG::<Bn256>::new(3, OsRng);
+        let domain = EvaluationDomain::<Fr>::new(1, 4);
+
+        let lagrange = domain.empty_lagrange();
+        assert!(matches!(
+            params.try_commit_lagrange(&lagrange, Blind::default()),
+            Err(Error::PolynomialTooLong { size: 16, n: 8 })
+        ));
+        let coeff = domain.empty_coeff();
+        assert!(matches!(
+            params.try_commit(&coeff, Blind::default()),
+            Err(Error::PolynomialTooLong { size: 16, n: 8 })
+        ));
+
+        // Polynomials that fit commit the same as through the trait method
+        let domain = EvaluationDomain::<Fr>::new(1, 3);
+        let poly = domain.lagrange_from_vec((1..=8u64).map(Fr::from).collect());
+        assert_eq!(
+            params.try_commit_lagrange(&poly, Blind::default()).unwrap(),
+            params.commit_lagrange(&poly, Blind::default())
+        );
+    }
+
+    #[test]
+    fn test_table_srs_powers() {
+        use super::TableSRS;
+        use halo2curves::bn256::{Bn256, Fr, G2Affine};
+
+        let s = Fr::from(0x5eed_u64);
+        let srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 17, s);
+
+        let mut power = Fr::one();
+        let mut expected_g1 = vec![];
+        let mut expected_g2 = vec![];
+        for _ in 0..18 {
+            expected_g1.push((G1Affine::generator() * power).to_affine());
+            expected_g2.push((G2Affine::generator() * power).to_affine());
+            power *= s;
+        }
+
+        assert_eq!(srs.g1(), &expected_g1[..16]);
+        assert_eq!(srs.g2(), &expected_g2[..]);
+    }
+
+    #[test]
+    fn test_lagrange_opening_at_0() {
+        use super::TableSRS;
+        use crate::poly::EvaluationDomain;
+        use halo2curves::bn256::{Bn256, Fr};
+
+        let s = Fr::from(0x5eed_u64);
+        let srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, s);
+        let domain = EvaluationDomain::<Fr>::new(1, 4);
+
+        //   [(L_i(x) - L_i(0)) / x]_1
+        // = omega^{-i} * [L_i(x)]_1 - (1 / N) * [x^{N-1}]_1
+        let n_inv = Fr::from(16u64).invert().unwrap();
+        let last_power_scaled = srs.g1()[15] * n_inv;
+        let mut omega_inv_i = Fr::one();
+        let mut expected = vec![];
+        for l_i in srs.g1_lagrange() {
+            expected.push((*l_i * omega_inv_i - last_power_scaled).to_affine());
+            omega_inv_i *= domain.get_omega_inv();
+        }
 
-        assert_eq!(params0.g, params1.g);
-        assert_eq!(params0.g_lagrange, params1.g_lagrange);
-        assert_eq!(params0.g2, params1.g2);
-        assert_eq!(params0.s_g2, params1.s_g2);
+        assert_eq!(domain.lagrange_opening_at_0(srs.g1_lagrange()), expected);
+        assert_eq!(srs.g_lagrange_opening_at_0(), &expected[..]);
     }
 }
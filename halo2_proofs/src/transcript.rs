@@ -32,6 +32,14 @@ pub trait Transcript<C: CurveAffine, E: EncodedChallenge<C>> {
         }
     }
 
+    /// Squeeze `n` challenges (in the scalar field) from the transcript, in
+    /// the same order as `n` calls to `squeeze_challenge_scalar` would.
+    fn squeeze_n_challenges(&mut self, n: usize) -> Vec<C::Scalar> {
+        (0..n)
+            .map(|_| self.squeeze_challenge().get_scalar())
+            .collect()
+    }
+
     /// Writing the point to the transcript without writing it to the proof,
     /// treating it as a common input.
     fn common_point(&mut self, point: C) -> io::Result<()>;
@@ -80,6 +88,11 @@ pub trait TranscriptWriterBuffer<W: Write, C: CurveAffine, E: EncodedChallenge<C
     fn finalize(self) -> W;
 }
 
+/// A snapshot of the hash state of a Blake2b transcript, taken with
+/// `checkpoint` and rewound to with `restore`.
+#[derive(Debug, Clone)]
+pub struct TranscriptState(Blake2bState);
+
 /// We will replace BLAKE2b with an algebraic hash function in a later version.
 #[derive(Debug, Clone)]
 pub struct Blake2bRead<R: Read, C: CurveAffine, E: EncodedChallenge<C>> {
@@ -88,6 +101,19 @@ pub struct Blake2bRead<R: Read, C: CurveAffine, E: EncodedChallenge<C>> {
     _marker: PhantomData<(C, E)>,
 }
 
+impl<R: Read, C: CurveAffine, E: EncodedChallenge<C>> Blake2bRead<R, C, E> {
+    /// Captures the current hash state so that it can be restored later.
+    pub fn checkpoint(&self) -> TranscriptState {
+        TranscriptState(self.state.clone())
+    }
+
+    /// Rewinds the hash state to `state`. The reader is not rewound, so data
+    /// read since the checkpoint is not read again.
+    pub fn restore(&mut self, state: TranscriptState) {
+        self.state = state.0;
+    }
+}
+
 impl<R: Read, C: CurveAffine> TranscriptReadBuffer<R, C, Challenge255<C>>
     for Blake2bRead<R, C, Challenge255<C>>
 {
@@ -143,6 +169,10 @@ impl<R: Read, C: CurveAffine> Transcript<C, Challenge255<C>>
         Challenge255::<C>::new(&result)
     }
 
+    fn squeeze_n_challenges(&mut self, n: usize) -> Vec<C::Scalar> {
+        blake2b_squeeze_n_scalars::<C>(&mut self.state, n)
+    }
+
     fn common_point(&mut self, point: C) -> io::Result<()> {
         self.state.update(&[BLAKE2B_PREFIX_POINT]);
         let coords: Coordinates<C> = Option::from(point.coordinates()).ok_or_else(|| {
@@ -173,6 +203,19 @@ pub struct Blake2bWrite<W: Write, C: CurveAffine, E: EncodedChallenge<C>> {
     _marker: PhantomData<(C, E)>,
 }
 
+impl<W: Write, C: CurveAffine, E: EncodedChallenge<C>> Blake2bWrite<W, C, E> {
+    /// Captures the current hash state so that it can be restored later.
+    pub fn checkpoint(&self) -> TranscriptState {
+        TranscriptState(self.state.clone())
+    }
+
+    /// Rewinds the hash state to `state`. Data already written to the proof
+    /// since the checkpoint is kept.
+    pub fn restore(&mut self, state: TranscriptState) {
+        self.state = state.0;
+    }
+}
+
 impl<W: Write, C: CurveAffine> TranscriptWriterBuffer<W, C, Challenge255<C>>
     for Blake2bWrite<W, C, Challenge255<C>>
 {
@@ -218,6 +261,10 @@ impl<W: Write, C: CurveAffine> Transcript<C, Challenge255<C>>
         Challenge255::<C>::new(&result)
     }
 
+    fn squeeze_n_challenges(&mut self, n: usize) -> Vec<C::Scalar> {
+        blake2b_squeeze_n_scalars::<C>(&mut self.state, n)
+    }
+
     fn common_point(&mut self, point: C) -> io::Result<()> {
         self.state.update(&[BLAKE2B_PREFIX_POINT]);
         let coords: Coordinates<C> = Option::from(point.coordinates()).ok_or_else(|| {
@@ -314,6 +361,18 @@ impl<C: CurveAffine> EncodedChallenge<C> for Challenge255<C> {
     }
 }
 
+/// Squeezes `n` challenges from a BLAKE2b state as `squeeze_challenge` does, but
+/// reduces each digest straight to a scalar rather than through its 255-bit encoding
+fn blake2b_squeeze_n_scalars<C: CurveAffine>(state: &mut Blake2bState, n: usize) -> Vec<C::Scalar> {
+    (0..n)
+        .map(|_| {
+            state.update(&[BLAKE2B_PREFIX_CHALLENGE]);
+            let result: [u8; 64] = state.clone().finalize().as_bytes().try_into().unwrap();
+            C::Scalar::from_bytes_wide(&result)
+        })
+        .collect()
+}
+
 pub(crate) fn read_n_points<C: CurveAffine, E: EncodedChallenge<C>, T: TranscriptRead<C, E>>(
     transcript: &mut T,
     n: usize,
@@ -327,3 +386,60 @@ pub(crate) fn read_n_scalars<C: CurveAffine, E: EncodedChallenge<C>, T: Transcri
 ) -> io::Result<Vec<C::Scalar>> {
     (0..n).map(|_| transcript.read_scalar()).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use halo2curves::bn256::{Fr, G1Affine};
+
+    use super::{
+        Blake2bRead, Blake2bWrite, Challenge255, Transcript, TranscriptRead, TranscriptReadBuffer,
+        TranscriptWrite, TranscriptWriterBuffer,
+    };
+
+    #[test]
+    fn squeeze_n_challenges_matches_individual_squeezes() {
+        let mut batched = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+        let mut single = batched.clone();
+
+        let challenges = batched.squeeze_n_challenges(3);
+        let expected: Vec<_> = (0..3)
+            .map(|_| *single.squeeze_challenge_scalar::<()>())
+            .collect();
+        assert_eq!(challenges, expected);
+
+        // The transcripts must also be left in the same state.
+        assert_eq!(
+            *batched.squeeze_challenge_scalar::<()>(),
+            *single.squeeze_challenge_scalar::<()>()
+        );
+
+        let mut reader = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(&[][..]);
+        assert_eq!(reader.squeeze_n_challenges(3), expected);
+        assert!(reader.squeeze_n_challenges(0).is_empty());
+    }
+
+    #[test]
+    fn restore_reproduces_challenge_after_fork() {
+        let mut writer = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+        writer.write_scalar(Fr::from(1)).unwrap();
+
+        let checkpoint = writer.checkpoint();
+        let before = *writer.squeeze_challenge_scalar::<()>();
+        writer.write_scalar(Fr::from(2)).unwrap();
+        assert_ne!(*writer.squeeze_challenge_scalar::<()>(), before);
+
+        writer.restore(checkpoint);
+        assert_eq!(*writer.squeeze_challenge_scalar::<()>(), before);
+
+        let proof = writer.finalize();
+        let mut reader = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(&proof[..]);
+        assert_eq!(reader.read_scalar().unwrap(), Fr::from(1));
+
+        let checkpoint = reader.checkpoint();
+        assert_eq!(*reader.squeeze_challenge_scalar::<()>(), before);
+        assert_eq!(reader.read_scalar().unwrap(), Fr::from(2));
+
+        reader.restore(checkpoint);
+        assert_eq!(*reader.squeeze_challenge_scalar::<()>(), before);
+    }
+}
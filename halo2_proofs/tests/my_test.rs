@@ -1,17 +1,24 @@
 use group::{Curve, Group};
 use rand::{Rng, SeedableRng};
-use std::{collections::BTreeMap, fmt::Debug, marker::PhantomData};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    marker::PhantomData,
+};
 
 use ff::{Field, PrimeField};
 use halo2_proofs::{
     circuit::{SimpleFloorPlanner, Value},
     dev::MockProver,
     plonk::{
-        create_proof, keygen_pk, keygen_vk,
+        create_proof, create_proof_returning_instances, create_proof_with_blinding, keygen_pk,
+        keygen_vk,
         static_lookup::{
             StaticCommittedTable, StaticTable, StaticTableConfig, StaticTableId, StaticTableValues,
         },
-        verify_proof, Advice, Circuit, Column, Selector,
+        verify_proof, verify_proof_per_argument, verify_proofs, Advice, BlindingStrategy,
+        Challenge, Circuit, Column, Expression, FirstPhase, Fixed, Instance, ProvingKey,
+        SecondPhase, Selector, TableColumn,
     },
     poly::{
         commitment::ParamsProver,
@@ -23,11 +30,12 @@ use halo2_proofs::{
         Rotation, VerificationStrategy,
     },
     transcript::{
-        Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+        Blake2bRead, Blake2bWrite, Challenge255, Transcript, TranscriptRead, TranscriptReadBuffer,
+        TranscriptWriterBuffer,
     },
 };
 use halo2curves::{
-    bn256::{Bn256, Fq2Bytes},
+    bn256::{Bn256, Fq2Bytes, Fr, G1Affine},
     pairing::{Engine, MillerLoopResult, MultiMillerLoop},
     serde::SerdeObject,
     CurveAffine, FieldExt,
@@ -115,53 +123,28 @@ static SEED: [u8; 32] = [
     0,
 ];
 
-fn generate_table(params: &TableSRS<Bn256>, k: usize) -> (StaticTable<Bn256>, StaticTable<Bn256>) {
-    use halo2curves::bn256::Fr;
-
+fn generate_table<E: MultiMillerLoop>(
+    params: &TableSRS<E>,
+    k: usize,
+) -> (StaticTable<E>, StaticTable<E>) {
     let table_values = [
-        Fr::from(0),
-        Fr::from(1),
-        Fr::from(6),
-        Fr::from(8),
-        Fr::from(10),
-        Fr::from(12),
-        Fr::from(14),
-        Fr::from(16),
-        Fr::from(18),
-        Fr::from(20),
-        Fr::from(22),
-        Fr::from(24),
-        Fr::from(26),
-        Fr::from(28),
-        Fr::from(30),
-        Fr::from(32),
-    ];
-
-    let table_2_values = [
-        Fr::from(0),
-        Fr::from(2),
-        Fr::from(3),
-        Fr::from(4),
-        Fr::from(5),
-        Fr::from(6),
-        Fr::from(7),
-        Fr::from(8),
-        Fr::from(9),
-        Fr::from(10),
-        Fr::from(11),
-        Fr::from(12),
-        Fr::from(13),
-        Fr::from(14),
-        Fr::from(15),
-        Fr::from(16),
-    ];
+        0u64, 1, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30, 32,
+    ]
+    .map(E::Scalar::from);
+
+    let table_2_values =
+        [0u64, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16].map(E::Scalar::from);
 
     let n = 1 << k;
-    let table = StaticTableValues::new(&table_values, &params.g1());
-    let table_2 = StaticTableValues::new(&table_2_values, &params.g1());
+    let table = StaticTableValues::new(&table_values, &params.g1()).unwrap();
+    let table_2 = StaticTableValues::new(&table_2_values, &params.g1()).unwrap();
 
-    let committed = table.commit(params.g1().len(), params.g2(), n);
-    let committed_2 = table_2.commit(params.g1().len(), params.g2(), n);
+    let committed = table
+        .commit(params.g1().len(), params.g2(), n)
+        .expect("committing the table should not fail");
+    let committed_2 = table_2
+        .commit(params.g1().len(), params.g2(), n)
+        .expect("committing the table should not fail");
 
     let t1 = StaticTable {
         opened: Some(table),
@@ -176,23 +159,31 @@ fn generate_table(params: &TableSRS<Bn256>, k: usize) -> (StaticTable<Bn256>, St
     (t1, t2)
 }
 
-#[test]
-fn my_test_e2e() {
+/// Runs the mock prover, then keygen, proving and verification of [`MyCircuit`] over `E`.
+///
+/// Nothing here is specific to an engine except the size of the tables: `E::Scalar` needs
+/// roots of unity of order 16 for the table domain.
+fn e2e<E>()
+where
+    E: MultiMillerLoop + Debug + Sync,
+    E::G1Affine: SerdeObject,
+    E::G2Affine: SerdeObject,
+    E::Scalar: SerdeObject,
+{
     const K: u32 = 3;
     let mut rng = rand_chacha::ChaCha8Rng::from_seed(SEED);
-    let s = <Bn256 as Engine>::Scalar::random(&mut rng);
+    let s = E::Scalar::random(&mut rng);
 
     let table_16_size = 16;
 
-    let table_16_srs =
-        TableSRS::<Bn256>::setup_from_toxic_waste(table_16_size - 1, table_16_size, s);
+    let table_16_srs = TableSRS::<E>::setup_from_toxic_waste(table_16_size - 1, table_16_size, s);
     let (table, table_2) = generate_table(&table_16_srs, K as usize);
     let circuit = MyCircuit { table, table_2 };
 
     let prover = MockProver::run(K, &circuit, vec![]).unwrap();
     prover.assert_satisfied();
 
-    let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(K, s);
+    let params = ParamsKZG::<E>::setup_from_toxic_waste(K, s);
 
     let config = StaticTableConfig::new(
         table_16_size,
@@ -202,7 +193,7 @@ fn my_test_e2e() {
     let mut configs = BTreeMap::new();
     configs.insert(table_16_size, config);
 
-    let b0_g1_bound = table_16_srs.g1()[((1 << K) + 1)..].to_vec();
+    let b0_g1_bound = table_16_srs.b0_g1_bound(K);
 
     // Initialize keys
     let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
@@ -213,7 +204,7 @@ fn my_test_e2e() {
     let proof = {
         let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
         // Create a proof
-        create_proof::<Bn256, ProverGWC<_>, _, _, _, _>(
+        create_proof::<E, ProverGWC<_>, _, _, _, _>(
             &params,
             &pk,
             &[circuit],
@@ -228,6 +219,97 @@ fn my_test_e2e() {
 
     let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(&proof[..]);
 
+    let verifier_params = params.verifier_params();
+    let strategy = VerificationStrategy::<E, VerifierGWC<_>>::new(verifier_params);
+
+    let p_batcher = verify_proof::<
+        E,
+        VerifierGWC<_>,
+        _,
+        Blake2bRead<_, _, Challenge255<_>>,
+        AccumulatorStrategy<_>,
+    >(
+        verifier_params,
+        pk.get_vk(),
+        strategy,
+        &[&[]],
+        &mut transcript,
+    )
+    .unwrap();
+
+    let batched_tuples = p_batcher.finalize();
+    let result = E::multi_miller_loop(
+        &batched_tuples
+            .iter()
+            .map(|(g1, g2)| (g1, g2))
+            .collect::<Vec<_>>(),
+    );
+
+    let pairing_result = result.final_exponentiation();
+    assert!(bool::from(pairing_result.is_identity()));
+}
+
+// Bn256 is the only pairing-friendly engine in halo2curves; pasta and secp256k1 have no pairing.
+#[test]
+fn my_test_e2e() {
+    e2e::<Bn256>();
+}
+
+#[test]
+fn my_test_pk_serialization_roundtrip() {
+    use halo2_proofs::SerdeFormat;
+
+    const K: u32 = 3;
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed(SEED);
+    let s = <Bn256 as Engine>::Scalar::random(&mut rng);
+
+    let table_16_size = 16;
+
+    let table_16_srs =
+        TableSRS::<Bn256>::setup_from_toxic_waste(table_16_size - 1, table_16_size, s);
+    let (table, table_2) = generate_table(&table_16_srs, K as usize);
+    let circuit = MyCircuit { table, table_2 };
+
+    let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(K, s);
+
+    let config = StaticTableConfig::new(
+        table_16_size,
+        table_16_srs.g1_lagrange().to_vec(),
+        table_16_srs.g_lagrange_opening_at_0().to_vec(),
+    );
+    let mut configs = BTreeMap::new();
+    configs.insert(table_16_size, config);
+
+    let b0_g1_bound = table_16_srs.b0_g1_bound(K);
+
+    let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
+    let pk =
+        keygen_pk(&params, configs, b0_g1_bound, vk, &circuit).expect("keygen_pk should not fail");
+
+    let pk_bytes = pk.to_bytes(SerdeFormat::RawBytes);
+    let reloaded_pk =
+        ProvingKey::<Bn256>::from_bytes::<MyCircuit<Bn256>>(&pk_bytes, SerdeFormat::RawBytes)
+            .expect("reading the proving key should not fail");
+    assert_eq!(pk_bytes, reloaded_pk.to_bytes(SerdeFormat::RawBytes));
+
+    // Create proof with the reloaded key
+    let proof = {
+        let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+        create_proof::<Bn256, ProverGWC<_>, _, _, _, _>(
+            &params,
+            &reloaded_pk,
+            &[circuit],
+            &[&[]],
+            OsRng,
+            &mut transcript,
+        )
+        .unwrap();
+
+        transcript.finalize()
+    };
+
+    let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(&proof[..]);
+
     let verifier_params = params.verifier_params();
     let strategy = VerificationStrategy::<Bn256, VerifierGWC<_>>::new(verifier_params);
 
@@ -257,3 +339,1623 @@ fn my_test_e2e() {
     let pairing_result = result.final_exponentiation();
     assert!(bool::from(pairing_result.is_identity()));
 }
+
+#[test]
+fn my_test_vk_json_roundtrip() {
+    use halo2_proofs::{plonk::VerifyingKey, SerdeFormat};
+
+    const K: u32 = 3;
+    let (_, pk, _) = setup_keys(K, |table, table_2| MyCircuit { table, table_2 });
+    let vk = pk.get_vk();
+
+    let json = vk.to_bytes(SerdeFormat::Json);
+    assert!(String::from_utf8(json.clone())
+        .unwrap()
+        .starts_with("{\"k\":3,\"fixed_commitments\":["));
+
+    let reloaded = VerifyingKey::<Bn256>::from_bytes::<MyCircuit<Bn256>>(&json, SerdeFormat::Json)
+        .expect("reading the JSON verifying key should not fail");
+    assert_eq!(json, reloaded.to_bytes(SerdeFormat::Json));
+    assert_eq!(
+        vk.to_bytes(SerdeFormat::RawBytes),
+        reloaded.to_bytes(SerdeFormat::RawBytes)
+    );
+
+    // Malformed points are rejected rather than panicking
+    let malformed = String::from_utf8(json)
+        .unwrap()
+        .replacen("\"zv\":\"0x", "\"zv\":\"0y", 1);
+    assert!(VerifyingKey::<Bn256>::from_bytes::<MyCircuit<Bn256>>(
+        malformed.as_bytes(),
+        SerdeFormat::Json
+    )
+    .is_err());
+
+    // Proving keys have no JSON form
+    let mut bytes = vec![];
+    assert!(pk.write(&mut bytes, SerdeFormat::Json).is_err());
+}
+
+#[test]
+fn my_test_vk_reload_keeps_static_commitments() {
+    use halo2_proofs::{plonk::VerifyingKey, SerdeFormat};
+
+    const K: u32 = 3;
+    let (_, pk, _) = setup_keys(K, |table, table_2| MyCircuit { table, table_2 });
+    let vk = pk.get_vk();
+    assert_eq!(vk.static_commitments().len(), 2);
+
+    for format in [
+        SerdeFormat::Processed,
+        SerdeFormat::RawBytes,
+        SerdeFormat::Json,
+    ] {
+        let reloaded =
+            VerifyingKey::<Bn256>::from_bytes::<MyCircuit<Bn256>>(&vk.to_bytes(format), format)
+                .expect("reading the verifying key should not fail");
+        assert_eq!(vk.static_commitments(), reloaded.static_commitments());
+    }
+
+    // Keys of circuits with different tables are told apart
+    let (_, other_pk, _) = setup_keys(K, |table_2, table| MyCircuit { table, table_2 });
+    assert_ne!(
+        vk.static_commitments(),
+        other_pk.get_vk().static_commitments()
+    );
+}
+
+#[test]
+fn my_test_pk_read_rejects_truncated_key() {
+    use halo2_proofs::SerdeFormat;
+
+    const K: u32 = 3;
+    let (_, pk, _) = setup_keys(K, |table, table_2| MyCircuit { table, table_2 });
+    let format = SerdeFormat::RawBytes;
+    let pk_bytes = pk.to_bytes(format);
+    let vk_len = pk.get_vk().to_bytes(format).len();
+
+    // Cut the key in the middle of `l0`
+    let truncated = &pk_bytes[..vk_len + 4 + 10];
+    let err = ProvingKey::<Bn256>::from_bytes::<MyCircuit<Bn256>>(truncated, format)
+        .expect_err("a truncated proving key should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    // Shorten `l0` by one value, so that it no longer spans the extended domain
+    let extended_len = pk.get_vk().get_domain().extended_len() as u32;
+    let mut shortened = pk_bytes.clone();
+    shortened[vk_len..vk_len + 4].copy_from_slice(&(extended_len - 1).to_be_bytes());
+    let err = ProvingKey::<Bn256>::from_bytes::<MyCircuit<Bn256>>(&shortened, format)
+        .expect_err("a proving key with a short polynomial should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    // The last occurrence of the length-prefixed id "table" is in the static tables of the
+    // proving key, followed by the size of the table and its values
+    let id = b"\0\0\0\x05table";
+    let size_at = pk_bytes
+        .windows(id.len())
+        .rposition(|window| window == id)
+        .expect("the proving key should contain the static table")
+        + id.len();
+
+    let truncated = &pk_bytes[..size_at + 4 + 10];
+    let err = ProvingKey::<Bn256>::from_bytes::<MyCircuit<Bn256>>(truncated, format)
+        .expect_err("a truncated static table should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    let mut resized = pk_bytes.clone();
+    resized[size_at..size_at + 4].copy_from_slice(&3u32.to_be_bytes());
+    let err = ProvingKey::<Bn256>::from_bytes::<MyCircuit<Bn256>>(&resized, format)
+        .expect_err("a static table size that is not a power of two should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+fn setup_keys<C: Circuit<Bn256>>(
+    k: u32,
+    make_circuit: impl FnOnce(StaticTable<Bn256>, StaticTable<Bn256>) -> C,
+) -> (ParamsKZG<Bn256>, ProvingKey<Bn256>, C) {
+    setup_keys_with_b0_bound(k, make_circuit, |srs| srs.b0_g1_bound(k))
+}
+
+fn setup_keys_with_b0_bound<C: Circuit<Bn256>>(
+    k: u32,
+    make_circuit: impl FnOnce(StaticTable<Bn256>, StaticTable<Bn256>) -> C,
+    b0_g1_bound: impl FnOnce(&TableSRS<Bn256>) -> Vec<G1Affine>,
+) -> (ParamsKZG<Bn256>, ProvingKey<Bn256>, C) {
+    setup_keys_with_tables(k, generate_table, make_circuit, b0_g1_bound)
+}
+
+fn setup_keys_with_tables<C: Circuit<Bn256>>(
+    k: u32,
+    make_tables: impl FnOnce(&TableSRS<Bn256>, usize) -> (StaticTable<Bn256>, StaticTable<Bn256>),
+    make_circuit: impl FnOnce(StaticTable<Bn256>, StaticTable<Bn256>) -> C,
+    b0_g1_bound: impl FnOnce(&TableSRS<Bn256>) -> Vec<G1Affine>,
+) -> (ParamsKZG<Bn256>, ProvingKey<Bn256>, C) {
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed(SEED);
+    let s = <Bn256 as Engine>::Scalar::random(&mut rng);
+
+    let table_16_size = 16;
+
+    let table_16_srs =
+        TableSRS::<Bn256>::setup_from_toxic_waste(table_16_size - 1, table_16_size, s);
+    let (table, table_2) = make_tables(&table_16_srs, k as usize);
+    let circuit = make_circuit(table, table_2);
+
+    let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(k, s);
+
+    let config = StaticTableConfig::new(
+        table_16_size,
+        table_16_srs.g1_lagrange().to_vec(),
+        table_16_srs.g_lagrange_opening_at_0().to_vec(),
+    );
+    let mut configs = BTreeMap::new();
+    configs.insert(table_16_size, config);
+
+    let b0_g1_bound = b0_g1_bound(&table_16_srs);
+
+    let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
+    let pk =
+        keygen_pk(&params, configs, b0_g1_bound, vk, &circuit).expect("keygen_pk should not fail");
+
+    (params, pk, circuit)
+}
+
+fn prove<C: Circuit<Bn256> + Clone>(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<Bn256>,
+    circuit: &C,
+) -> Vec<u8> {
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+    create_proof::<Bn256, ProverGWC<_>, _, _, _, _>(
+        params,
+        pk,
+        &[circuit.clone()],
+        &[&[]],
+        OsRng,
+        &mut transcript,
+    )
+    .unwrap();
+
+    transcript.finalize()
+}
+
+fn prove_with_blinding<C: Circuit<Bn256> + Clone>(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<Bn256>,
+    circuit: &C,
+    blinding: BlindingStrategy,
+    rng: impl RngCore,
+) -> Vec<u8> {
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+    create_proof_with_blinding::<Bn256, ProverGWC<_>, _, _, _, _>(
+        params,
+        pk,
+        &[circuit.clone()],
+        &[&[]],
+        blinding,
+        rng,
+        &mut transcript,
+    )
+    .unwrap();
+
+    transcript.finalize()
+}
+
+fn pairing_check(p_batcher: halo2curves::batch_pairing::PairingBatcher<Bn256>) -> bool {
+    let batched_tuples = p_batcher.finalize();
+    let result = Bn256::multi_miller_loop(
+        &batched_tuples
+            .iter()
+            .map(|(g1, g2)| (g1, g2))
+            .collect::<Vec<_>>(),
+    );
+
+    bool::from(result.final_exponentiation().is_identity())
+}
+
+fn batch_verify(params: &ParamsKZG<Bn256>, pk: &ProvingKey<Bn256>, proofs: &[Vec<u8>]) -> bool {
+    let mut transcripts: Vec<_> = proofs
+        .iter()
+        .map(|proof| Blake2bRead::<_, _, Challenge255<_>>::init(&proof[..]))
+        .collect();
+    let instances: Vec<&[&[&[<Bn256 as Engine>::Scalar]]]> = vec![&[&[]]; proofs.len()];
+
+    let p_batcher = verify_proofs::<
+        Bn256,
+        VerifierGWC<_>,
+        _,
+        Blake2bRead<_, _, Challenge255<_>>,
+        AccumulatorStrategy<_>,
+        _,
+    >(
+        params.verifier_params(),
+        pk.get_vk(),
+        &instances,
+        &mut transcripts,
+        OsRng,
+    );
+
+    match p_batcher {
+        Ok(p_batcher) => pairing_check(p_batcher),
+        Err(_) => false,
+    }
+}
+
+#[test]
+fn my_test_batch_verification() {
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys(K, |table, table_2| MyCircuit { table, table_2 });
+
+    let proofs: Vec<_> = (0..3).map(|_| prove(&params, &pk, &circuit)).collect();
+    assert!(batch_verify(&params, &pk, &proofs));
+
+    // Corrupt the last opening witness of one of the proofs
+    let mut tampered = proofs.clone();
+    let last = tampered[1].len() - 1;
+    tampered[1][last] ^= 1;
+    assert!(!batch_verify(&params, &pk, &tampered));
+}
+
+#[test]
+fn my_test_batch_verification_without_proofs() {
+    const K: u32 = 3;
+    let (params, pk, _) = setup_keys(K, |table, table_2| MyCircuit { table, table_2 });
+
+    let mut transcripts: Vec<Blake2bRead<&[u8], _, Challenge255<_>>> = vec![];
+    let result = verify_proofs::<
+        Bn256,
+        VerifierGWC<_>,
+        _,
+        Blake2bRead<_, _, Challenge255<_>>,
+        AccumulatorStrategy<_>,
+        _,
+    >(
+        params.verifier_params(),
+        pk.get_vk(),
+        &[],
+        &mut transcripts,
+        OsRng,
+    );
+    assert!(matches!(result, Err(halo2_proofs::plonk::Error::NoProofs)));
+}
+
+#[test]
+fn my_test_zero_blinding_is_reproducible() {
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys(K, |table, table_2| MyCircuit { table, table_2 });
+
+    let seeded = |seed: u8| rand_chacha::ChaCha8Rng::from_seed([seed; 32]);
+    let prove_with =
+        |blinding, seed| prove_with_blinding(&params, &pk, &circuit, blinding, seeded(seed));
+
+    // The other arguments still draw their blinding from the rng
+    let deterministic = prove_with(BlindingStrategy::Zero, 1);
+    assert_eq!(deterministic, prove_with(BlindingStrategy::Zero, 1));
+    assert_ne!(deterministic, prove_with(BlindingStrategy::Zero, 2));
+
+    let randomized = prove_with(BlindingStrategy::Random, 1);
+    assert_ne!(randomized, deterministic);
+
+    assert!(batch_verify(&params, &pk, &[deterministic, randomized]));
+}
+
+#[test]
+fn my_test_minimum_k() {
+    let mut cs = halo2_proofs::plonk::ConstraintSystem::<Fr>::default();
+    <MyCircuit<Bn256> as Circuit<Bn256>>::configure(&mut cs);
+    let k = cs.minimum_k();
+    assert!(1 << k >= cs.minimum_rows());
+    assert!(1 << (k - 1) < cs.minimum_rows());
+
+    let (_, _, circuit) = setup_keys(k, |table, table_2| MyCircuit { table, table_2 });
+    let s = Fr::random(OsRng);
+    let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(k, s);
+    assert!(keygen_vk(&params, &circuit).is_ok());
+
+    let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(k - 1, s);
+    assert!(matches!(
+        keygen_vk(&params, &circuit),
+        Err(halo2_proofs::plonk::Error::NotEnoughRowsAvailable { current_k }) if current_k == k - 1
+    ));
+}
+
+/// Looks up one advice cell and multiplies it with an advice column that is never
+/// assigned, so that column is zero on all usable rows
+#[derive(Clone)]
+struct ZeroColumnCircuit<E: MultiMillerLoop> {
+    table: StaticTable<E>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E> for ZeroColumnCircuit<E> {
+    type Config = (Selector, Column<Advice>, Column<Advice>);
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let q = meta.selector();
+        let value = meta.advice_column();
+        let zero = meta.advice_column();
+
+        meta.lookup_static("lookup_value", |meta| {
+            vec![(
+                meta.query_advice(value, Rotation::cur()),
+                StaticTableId(String::from("table")),
+            )]
+        });
+        meta.create_gate("times_zero", |meta| {
+            let q = meta.query_selector(q);
+            let value = meta.query_advice(value, Rotation::cur());
+            let zero = meta.query_advice(zero, Rotation::cur());
+            vec![q * value * zero]
+        });
+
+        (q, value, zero)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.register_static_table(StaticTableId(String::from("table")), self.table.clone());
+
+        layouter.assign_region(
+            || "value",
+            |mut region| {
+                config.0.enable(&mut region, 0)?;
+                region.assign_advice(config.1, 0, Value::known(F::from(6)))?;
+                Ok(())
+            },
+        )
+    }
+}
+
+#[test]
+fn my_test_zero_blinding_with_zero_advice_column() {
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys(K, |table, _| ZeroColumnCircuit { table });
+
+    let prove_seeded = || {
+        let rng = rand_chacha::ChaCha8Rng::from_seed(SEED);
+        prove_with_blinding(&params, &pk, &circuit, BlindingStrategy::Zero, rng)
+    };
+    let deterministic = prove_seeded();
+    assert_eq!(deterministic, prove_seeded());
+    assert!(batch_verify(&params, &pk, &[deterministic]));
+}
+
+/// Looks up the advice cell on the next row whenever the selector is enabled
+#[derive(Clone)]
+struct RotatedLookupCircuit<E: MultiMillerLoop> {
+    table: StaticTable<E>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E> for RotatedLookupCircuit<E> {
+    type Config = (Selector, Column<Advice>);
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let q = meta.complex_selector();
+        let advice = meta.advice_column();
+        meta.lookup_static("lookup_next", |meta| {
+            let q = meta.query_selector(q);
+            let next = meta.query_advice(advice, Rotation::next());
+            vec![(q * next, StaticTableId(String::from("table")))]
+        });
+
+        (q, advice)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.register_static_table(StaticTableId(String::from("table")), self.table.clone());
+
+        layouter.assign_region(
+            || "",
+            |mut region| {
+                config.0.enable(&mut region, 0)?;
+                // Not in the table, only the next row is looked up
+                region.assign_advice(
+                    config.1,
+                    0,
+                    Value::known(<E as Engine>::Scalar::from_u128(7)),
+                )?;
+                region.assign_advice(
+                    config.1,
+                    1,
+                    Value::known(<E as Engine>::Scalar::from_u128(6)),
+                )?;
+
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn my_test_off_by_one_b0_bound() {
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys_with_b0_bound(
+        K,
+        |table, table_2| MyCircuit { table, table_2 },
+        |srs| srs.g1()[(1 << K)..(srs.g1().len() - 1)].to_vec(),
+    );
+
+    let proof = prove(&params, &pk, &circuit);
+    assert!(!batch_verify(&params, &pk, &[proof]));
+}
+
+#[test]
+fn my_test_rotated_lookup() {
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys(K, |table, _| RotatedLookupCircuit { table });
+
+    let proof = prove(&params, &pk, &circuit);
+    assert!(batch_verify(&params, &pk, &[proof]));
+}
+
+/// Looks up a table under a misspelled id
+#[derive(Clone)]
+struct MisspelledTableCircuit<E: MultiMillerLoop> {
+    table: StaticTable<E>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E> for MisspelledTableCircuit<E> {
+    type Config = Column<Advice>;
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        meta.lookup_static("lookup_typo", |meta| {
+            vec![(
+                meta.query_advice(advice, Rotation::cur()),
+                StaticTableId(String::from("tabel")),
+            )]
+        });
+
+        advice
+    }
+
+    fn synthesize(
+        &self,
+        _config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.register_static_table(StaticTableId(String::from("table")), self.table.clone());
+
+        Ok(())
+    }
+}
+
+#[test]
+fn my_test_unregistered_table_id() {
+    const K: u32 = 3;
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed(SEED);
+    let s = <Bn256 as Engine>::Scalar::random(&mut rng);
+
+    let table_16_srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, s);
+    let (table, _) = generate_table(&table_16_srs, K as usize);
+    let circuit = MisspelledTableCircuit { table };
+
+    let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(K, s);
+
+    match keygen_vk(&params, &circuit) {
+        Err(halo2_proofs::plonk::Error::UnregisteredStaticTable(id)) => {
+            assert_eq!(id, StaticTableId(String::from("tabel")))
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("keygen_vk should reject unregistered table ids"),
+    }
+}
+
+/// Registers `tables` one after the other, all under the id of its lookup
+#[derive(Clone)]
+struct ReregisteredTableCircuit<E: MultiMillerLoop> {
+    tables: Vec<StaticTable<E>>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E>
+    for ReregisteredTableCircuit<E>
+{
+    type Config = Column<Advice>;
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        meta.lookup_static("lookup", |meta| {
+            vec![(
+                meta.query_advice(advice, Rotation::cur()),
+                StaticTableId(String::from("table")),
+            )]
+        });
+
+        advice
+    }
+
+    fn synthesize(
+        &self,
+        _config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        for table in self.tables.iter() {
+            layouter.register_static_table(StaticTableId(String::from("table")), table.clone());
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn my_test_conflicting_table_registration() {
+    const K: u32 = 3;
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed(SEED);
+    let s = <Bn256 as Engine>::Scalar::random(&mut rng);
+
+    let table_16_srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, s);
+    let (table, _) = generate_table(&table_16_srs, K as usize);
+    let (padded, _) = generate_padded_tables(&table_16_srs, K as usize);
+    let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(K, s);
+
+    // The same table twice is not a conflict
+    let circuit = ReregisteredTableCircuit {
+        tables: vec![table.clone(), table.clone()],
+    };
+    assert!(keygen_vk(&params, &circuit).is_ok());
+    assert!(MockProver::run(K, &circuit, vec![]).is_ok());
+
+    let circuit = ReregisteredTableCircuit {
+        tables: vec![table, padded],
+    };
+    match keygen_vk(&params, &circuit) {
+        Err(halo2_proofs::plonk::Error::ConflictingStaticTable(id)) => {
+            assert_eq!(id, StaticTableId(String::from("table")))
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("keygen_vk should reject conflicting tables"),
+    }
+    assert!(matches!(
+        MockProver::run(K, &circuit, vec![]),
+        Err(halo2_proofs::plonk::Error::ConflictingStaticTable(_))
+    ));
+}
+
+#[test]
+fn my_test_static_lookup_introspection() {
+    let mut cs = halo2_proofs::plonk::ConstraintSystem::default();
+    <MyCircuit<Bn256> as Circuit<Bn256>>::configure(&mut cs);
+
+    let lookups = cs.static_lookups();
+    assert_eq!(lookups.len(), 1);
+    assert_eq!(lookups[0].name(), "lookup_bits");
+    assert_eq!(lookups[0].input().len(), 2);
+    assert!(lookups[0].input().iter().all(|input| input.degree() == 1));
+    assert_eq!(
+        lookups[0].table_ids(),
+        &[
+            StaticTableId(String::from("table")),
+            StaticTableId(String::from("table_2")),
+        ]
+    );
+}
+
+#[test]
+fn my_test_static_table_sizes() {
+    const K: u32 = 3;
+    let (_, pk, _) = setup_keys(K, |table, table_2| MyCircuit { table, table_2 });
+
+    // `table` and `table_2` are both of size 16
+    assert_eq!(pk.static_table_sizes(), vec![16]);
+    assert_eq!(pk.get_vk().static_table_sizes(), vec![16]);
+}
+
+#[test]
+fn my_test_missing_static_table_config() {
+    const K: u32 = 3;
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed(SEED);
+    let s = <Bn256 as Engine>::Scalar::random(&mut rng);
+
+    let table_16_srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, s);
+    let (table, table_2) = generate_table(&table_16_srs, K as usize);
+    let circuit = MyCircuit { table, table_2 };
+
+    let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(K, s);
+    let b0_g1_bound = table_16_srs.b0_g1_bound(K);
+
+    let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
+    match keygen_pk(&params, BTreeMap::new(), b0_g1_bound, vk, &circuit) {
+        Err(halo2_proofs::plonk::Error::StaticLookupConfig(size)) => assert_eq!(size, 16),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("keygen_pk should reject a missing static table config"),
+    }
+}
+
+#[test]
+fn my_test_swapped_static_table_config() {
+    const K: u32 = 3;
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed(SEED);
+    let s = <Bn256 as Engine>::Scalar::random(&mut rng);
+
+    let table_16_srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, s);
+    let (table, table_2) = generate_table(&table_16_srs, K as usize);
+    let circuit = MyCircuit { table, table_2 };
+
+    let params = ParamsKZG::<Bn256>::setup_from_toxic_waste(K, s);
+    let b0_g1_bound = table_16_srs.b0_g1_bound(K);
+
+    let swapped = StaticTableConfig::new(
+        16,
+        table_16_srs.g_lagrange_opening_at_0().to_vec(),
+        table_16_srs.g1_lagrange().to_vec(),
+    );
+    let mut configs = BTreeMap::new();
+    configs.insert(16, swapped);
+
+    let vk = keygen_vk(&params, &circuit).expect("keygen_vk should not fail");
+    match keygen_pk(&params, configs, b0_g1_bound, vk, &circuit) {
+        Err(halo2_proofs::plonk::Error::InvalidStaticTableConfig(size)) => assert_eq!(size, 16),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("keygen_pk should reject a config that does not match the SRS"),
+    }
+}
+
+/// Reads a proof honestly but replaces the scalar read at `tamper_at` with a
+/// different value, so that every challenge stays the same as for the honest
+/// proof and only the checks using that scalar fail
+struct TamperingTranscript<'a> {
+    inner: Blake2bRead<&'a [u8], G1Affine, Challenge255<G1Affine>>,
+    scalars_read: usize,
+    tamper_at: usize,
+}
+
+impl<'a> Transcript<G1Affine, Challenge255<G1Affine>> for TamperingTranscript<'a> {
+    fn squeeze_challenge(&mut self) -> Challenge255<G1Affine> {
+        self.inner.squeeze_challenge()
+    }
+
+    fn common_point(&mut self, point: G1Affine) -> std::io::Result<()> {
+        self.inner.common_point(point)
+    }
+
+    fn common_scalar(&mut self, scalar: Fr) -> std::io::Result<()> {
+        self.inner.common_scalar(scalar)
+    }
+}
+
+impl<'a> TranscriptRead<G1Affine, Challenge255<G1Affine>> for TamperingTranscript<'a> {
+    fn read_point(&mut self) -> std::io::Result<G1Affine> {
+        self.inner.read_point()
+    }
+
+    fn read_scalar(&mut self) -> std::io::Result<Fr> {
+        let scalar = self.inner.read_scalar()?;
+        self.scalars_read += 1;
+        if self.scalars_read - 1 == self.tamper_at {
+            Ok(scalar + Fr::one())
+        } else {
+            Ok(scalar)
+        }
+    }
+}
+
+fn verify_per_argument(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<Bn256>,
+    proof: &[u8],
+    tamper_at: usize,
+) -> (Result<(), halo2_proofs::plonk::Error>, usize) {
+    let mut transcript = TamperingTranscript {
+        inner: Blake2bRead::init(proof),
+        scalars_read: 0,
+        tamper_at,
+    };
+
+    let verifier_params = params.verifier_params();
+    let strategy = VerificationStrategy::<Bn256, VerifierGWC<_>>::new(verifier_params);
+    let result = verify_proof_per_argument::<
+        Bn256,
+        VerifierGWC<_>,
+        _,
+        TamperingTranscript,
+        AccumulatorStrategy<_>,
+    >(
+        verifier_params,
+        pk.get_vk(),
+        strategy,
+        &[&[]],
+        &mut transcript,
+    );
+
+    (result, transcript.scalars_read)
+}
+
+#[test]
+fn my_test_per_argument_failure() {
+    use halo2_proofs::plonk::{Error, ProofArgument};
+
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys(K, |table, table_2| MyCircuit { table, table_2 });
+    let proof = prove(&params, &pk, &circuit);
+
+    let (result, scalars_read) = verify_per_argument(&params, &pk, &proof, usize::MAX);
+    assert!(result.is_ok());
+
+    // Advice evaluations are the first scalars of the proof
+    let (result, _) = verify_per_argument(&params, &pk, &proof, 0);
+    assert!(matches!(
+        result,
+        Err(Error::ArgumentFailed(ProofArgument::Opening))
+    ));
+
+    // A(0) of the static lookup is the last scalar of the proof
+    let (result, _) = verify_per_argument(&params, &pk, &proof, scalars_read - 1);
+    assert!(matches!(
+        result,
+        Err(Error::ArgumentFailed(ProofArgument::StaticLookup(0)))
+    ));
+}
+
+/// Looks up each witness pair with cq, in `table` and `table_2` at a common index
+#[derive(Clone)]
+struct StaticPairsCircuit<E: MultiMillerLoop> {
+    table: StaticTable<E>,
+    table_2: StaticTable<E>,
+    witness: Vec<(u64, u64)>,
+}
+
+fn assign_pairs<F: FieldExt>(
+    config: (Column<Advice>, Column<Advice>),
+    witness: &[(u64, u64)],
+    mut layouter: impl halo2_proofs::circuit::Layouter<F>,
+) -> Result<(), halo2_proofs::plonk::Error> {
+    layouter.assign_region(
+        || "pairs",
+        |mut region| {
+            for (offset, &(a, b)) in witness.iter().enumerate() {
+                region.assign_advice(config.0, offset, Value::known(F::from(a)))?;
+                region.assign_advice(config.1, offset, Value::known(F::from(b)))?;
+            }
+
+            Ok(())
+        },
+    )
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E> for StaticPairsCircuit<E> {
+    type Config = (Column<Advice>, Column<Advice>);
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        <MyCircuit<E> as Circuit<E>>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.register_static_table(StaticTableId(String::from("table")), self.table.clone());
+        layouter
+            .register_static_table(StaticTableId(String::from("table_2")), self.table_2.clone());
+
+        assign_pairs(config, &self.witness, layouter)
+    }
+}
+
+/// Looks up each witness pair with a classic lookup into two fixed table columns
+#[derive(Clone)]
+struct DynamicPairsCircuit<E: MultiMillerLoop> {
+    values: Vec<(u64, u64)>,
+    witness: Vec<(u64, u64)>,
+    _marker: PhantomData<E>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E> for DynamicPairsCircuit<E> {
+    type Config = (Column<Advice>, Column<Advice>, TableColumn, TableColumn);
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let advice_2 = meta.advice_column();
+        let table = meta.lookup_table_column();
+        let table_2 = meta.lookup_table_column();
+        meta.lookup("lookup_bits", |meta| {
+            vec![
+                (meta.query_advice(advice, Rotation::cur()), table),
+                (meta.query_advice(advice_2, Rotation::cur()), table_2),
+            ]
+        });
+
+        (advice, advice_2, table, table_2)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.assign_table(
+            || "pairs table",
+            |mut table| {
+                for (index, &(a, b)) in self.values.iter().enumerate() {
+                    table.assign_cell(|| "table", config.2, index, || Value::known(F::from(a)))?;
+                    table.assign_cell(
+                        || "table_2",
+                        config.3,
+                        index,
+                        || Value::known(F::from(b)),
+                    )?;
+                }
+
+                Ok(())
+            },
+        )?;
+
+        assign_pairs((config.0, config.1), &self.witness, layouter)
+    }
+}
+
+/// Runs the same witness through cq and classic lookups over equal tables and
+/// checks that `MockProver` reaches the same verdict for both.
+fn assert_lookups_agree(witness: Vec<(u64, u64)>) -> bool {
+    // The classic lookup table has to fit in the usable rows
+    const K: u32 = 5;
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed(SEED);
+    let s = <Bn256 as Engine>::Scalar::random(&mut rng);
+    let table_16_srs = TableSRS::<Bn256>::setup_from_toxic_waste(15, 16, s);
+
+    let values: Vec<(u64, u64)> = (0..16).map(|i| (2 * i, i)).collect();
+    let opened = |f: fn(&(u64, u64)) -> u64| StaticTable {
+        opened: Some(
+            StaticTableValues::new(
+                &values.iter().map(|v| Fr::from(f(v))).collect::<Vec<_>>(),
+                table_16_srs.g1(),
+            )
+            .unwrap(),
+        ),
+        committed: None,
+    };
+
+    let static_circuit = StaticPairsCircuit::<Bn256> {
+        table: opened(|v| v.0),
+        table_2: opened(|v| v.1),
+        witness: witness.clone(),
+    };
+    let dynamic_circuit = DynamicPairsCircuit::<Bn256> {
+        values: values.clone(),
+        witness,
+        _marker: PhantomData,
+    };
+
+    let static_result = MockProver::run(K, &static_circuit, vec![])
+        .unwrap()
+        .verify()
+        .map_err(|errors| errors.len());
+    let dynamic_result = MockProver::run(K, &dynamic_circuit, vec![])
+        .unwrap()
+        .verify()
+        .map_err(|errors| errors.len());
+    assert_eq!(static_result, dynamic_result);
+
+    static_result.is_ok()
+}
+
+#[test]
+fn my_test_static_and_dynamic_lookups_agree() {
+    assert!(assert_lookups_agree(vec![(2, 1), (30, 15), (6, 3)]));
+
+    // 6 and 4 are both in their tables, but at different indices
+    assert!(!assert_lookups_agree(vec![(2, 1), (6, 4)]));
+
+    // 7 is in neither table
+    assert!(!assert_lookups_agree(vec![(7, 3), (7, 7), (8, 4)]));
+}
+
+/// Leaves the first advice cell unknown, as if synthesized without witnesses
+#[derive(Clone)]
+struct UnknownAdviceCircuit<E: MultiMillerLoop> {
+    table: StaticTable<E>,
+    table_2: StaticTable<E>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E> for UnknownAdviceCircuit<E> {
+    type Config = (Column<Advice>, Column<Advice>);
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        <MyCircuit<E> as Circuit<E>>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.register_static_table(StaticTableId(String::from("table")), self.table.clone());
+        layouter
+            .register_static_table(StaticTableId(String::from("table_2")), self.table_2.clone());
+
+        layouter.assign_region(
+            || "",
+            |mut region| {
+                region.assign_advice(config.0, 0, Value::unknown())?;
+                region.assign_advice(config.1, 0, Value::known(F::from(1)))?;
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[test]
+fn my_test_unknown_advice_is_an_error() {
+    const K: u32 = 3;
+    let (params, pk, circuit) =
+        setup_keys(K, |table, table_2| UnknownAdviceCircuit { table, table_2 });
+
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+    let result = create_proof::<Bn256, ProverGWC<_>, _, _, _, _>(
+        &params,
+        &pk,
+        &[circuit],
+        &[&[]],
+        OsRng,
+        &mut transcript,
+    );
+
+    match result {
+        Err(halo2_proofs::plonk::Error::UnknownAdvice { column, row }) => {
+            assert!(matches!(
+                column.column_type(),
+                halo2_proofs::plonk::Any::Advice(_)
+            ));
+            assert_eq!(column.index(), 0);
+            assert_eq!(row, 0);
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("create_proof should reject unknown advice"),
+    }
+}
+
+#[cfg(feature = "cq-tracing")]
+#[test]
+fn my_test_cq_tracing_spans() {
+    use std::sync::{Arc, Mutex};
+    use tracing::{span, Event, Metadata, Subscriber};
+
+    /// Records the name of every span created
+    struct SpanNames(Arc<Mutex<Vec<&'static str>>>);
+
+    impl Subscriber for SpanNames {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name());
+            span::Id::from_u64(names.len() as u64)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys(K, |table, table_2| MyCircuit { table, table_2 });
+
+    let names = Arc::new(Mutex::new(vec![]));
+    tracing::subscriber::with_default(SpanNames(names.clone()), || {
+        prove(&params, &pk, &circuit);
+    });
+
+    let names = names.lock().unwrap();
+    for expected in [
+        "cq.commit",
+        "cq.commit_log_derivatives",
+        "cq.ifft",
+        "cq.evaluate",
+    ] {
+        assert!(names.contains(&expected), "missing span {}", expected);
+    }
+}
+
+/// Looks up a second phase advice column, assigned once the first phase challenge is known
+#[derive(Clone)]
+struct SecondPhaseLookupCircuit<E: MultiMillerLoop> {
+    table: StaticTable<E>,
+    table_2: StaticTable<E>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E>
+    for SecondPhaseLookupCircuit<E>
+{
+    type Config = (Column<Advice>, Column<Advice>, Challenge);
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let challenge = meta.challenge_usable_after(FirstPhase);
+        let advice_2 = meta.advice_column_in(SecondPhase);
+        meta.lookup_static("lookup_second_phase", |meta| {
+            vec![
+                (
+                    meta.query_advice(advice, Rotation::cur()),
+                    StaticTableId(String::from("table")),
+                ),
+                (
+                    meta.query_advice(advice_2, Rotation::cur()),
+                    StaticTableId(String::from("table_2")),
+                ),
+            ]
+        });
+
+        (advice, advice_2, challenge)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.register_static_table(StaticTableId(String::from("table")), self.table.clone());
+        layouter
+            .register_static_table(StaticTableId(String::from("table_2")), self.table_2.clone());
+
+        layouter.assign_region(
+            || "first phase",
+            |mut region| {
+                region.assign_advice(config.0, 0, Value::known(F::from(30)))?;
+                region.assign_advice(config.0, 1, Value::known(F::from(6)))?;
+
+                Ok(())
+            },
+        )?;
+
+        layouter.assign_region_in_phase(
+            SecondPhase,
+            || "second phase",
+            |mut region| {
+                // Only known once the first phase has been committed
+                let challenge = region.get_challenge(config.2);
+                region.assign_advice(config.1, 0, challenge.map(|_| F::from(15)))?;
+                region.assign_advice(config.1, 1, challenge.map(|_| F::from(3)))?;
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[test]
+fn my_test_second_phase_lookup() {
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys(K, |table, table_2| SecondPhaseLookupCircuit {
+        table,
+        table_2,
+    });
+
+    let prover = MockProver::run(K, &circuit, vec![]).unwrap();
+    prover.assert_satisfied();
+
+    let proof = prove(&params, &pk, &circuit);
+    assert!(batch_verify(&params, &pk, &[proof]));
+}
+
+// Default value of the tables of `PaddedLookupCircuit`, zero is not in them
+const PADDING_DEFAULT: u64 = 21;
+
+/// Odd values from 7 to 37, with [`PADDING_DEFAULT`] moved to index 0
+fn generate_padded_tables(
+    params: &TableSRS<Bn256>,
+    k: usize,
+) -> (StaticTable<Bn256>, StaticTable<Bn256>) {
+    let values: Vec<Fr> = (0..16).map(|i| Fr::from(2 * i + 7)).collect();
+    let table = || {
+        let opened =
+            StaticTableValues::with_default(&values, Fr::from(PADDING_DEFAULT), params.g1())
+                .unwrap();
+        let committed = opened
+            .commit(params.g1().len(), params.g2(), 1 << k)
+            .expect("committing the table should not fail");
+        StaticTable {
+            opened: Some(opened),
+            committed: Some(committed),
+        }
+    };
+
+    (table(), table())
+}
+
+/// Uses the lookup in its first two rows only, every other row looks up the default value
+/// through `q * a + (1 - q) * default`
+#[derive(Clone)]
+struct PaddedLookupCircuit<E: MultiMillerLoop> {
+    table: StaticTable<E>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E> for PaddedLookupCircuit<E> {
+    type Config = (Column<Advice>, Column<Fixed>);
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let q = meta.fixed_column();
+        meta.lookup_static("lookup_padded", |meta| {
+            let a = meta.query_advice(advice, Rotation::cur());
+            let q = meta.query_fixed(q, Rotation::cur());
+            let default = Expression::Constant(F::from(PADDING_DEFAULT));
+            vec![(
+                q.clone() * a + (Expression::Constant(F::one()) - q) * default,
+                StaticTableId(String::from("table")),
+            )]
+        });
+
+        (advice, q)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.register_static_table(StaticTableId(String::from("table")), self.table.clone());
+
+        layouter.assign_region(
+            || "short witness",
+            |mut region| {
+                for (row, value) in [9u64, 37].into_iter().enumerate() {
+                    region.assign_fixed(config.1, row, F::one());
+                    region.assign_advice(config.0, row, Value::known(F::from(value)))?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[test]
+fn my_test_short_witness_pads_with_default() {
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys_with_tables(
+        K,
+        generate_padded_tables,
+        |table, _| PaddedLookupCircuit { table },
+        |srs| srs.b0_g1_bound(K),
+    );
+    assert_eq!(
+        circuit.table.default_value(),
+        Some(Fr::from(PADDING_DEFAULT))
+    );
+
+    let prover = MockProver::run(K, &circuit, vec![]).unwrap();
+    prover.assert_satisfied();
+
+    let proof = prove(&params, &pk, &circuit);
+    assert!(batch_verify(&params, &pk, &[proof]));
+}
+
+#[test]
+fn my_test_static_tables_bind_first_challenge() {
+    const K: u32 = 3;
+    let first_challenge =
+        |make_tables: fn(&TableSRS<Bn256>, usize) -> (StaticTable<Bn256>, StaticTable<Bn256>)| {
+            let (_, pk, _) = setup_keys_with_tables(
+                K,
+                make_tables,
+                |table, _| PaddedLookupCircuit { table },
+                |srs| srs.b0_g1_bound(K),
+            );
+            let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+            pk.get_vk().hash_into(&mut transcript).unwrap();
+            *transcript.squeeze_challenge_scalar::<()>()
+        };
+
+    // Same constraint system and fixed columns, only the looked up values differ
+    assert_ne!(
+        first_challenge(generate_table),
+        first_challenge(generate_padded_tables)
+    );
+}
+
+/// Exposes the sum of two looked up advice cells as an instance, which the prover
+/// only learns while synthesizing
+#[derive(Clone)]
+struct InstanceFromAdviceCircuit<E: MultiMillerLoop> {
+    table: StaticTable<E>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E>
+    for InstanceFromAdviceCircuit<E>
+{
+    type Config = (Selector, Column<Advice>, Column<Advice>, Column<Instance>);
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let q = meta.selector();
+        let advice = meta.advice_column();
+        let sum = meta.advice_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(sum);
+        meta.enable_equality(instance);
+
+        meta.lookup_static("lookup_summands", |meta| {
+            vec![(
+                meta.query_advice(advice, Rotation::cur()),
+                StaticTableId(String::from("table")),
+            )]
+        });
+        meta.create_gate("sum", |meta| {
+            let q = meta.query_selector(q);
+            let cur = meta.query_advice(advice, Rotation::cur());
+            let next = meta.query_advice(advice, Rotation::next());
+            let sum = meta.query_advice(sum, Rotation::cur());
+            vec![q * (cur + next - sum)]
+        });
+
+        (q, advice, sum, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.register_static_table(StaticTableId(String::from("table")), self.table.clone());
+
+        let sum = layouter.assign_region(
+            || "sum",
+            |mut region| {
+                config.0.enable(&mut region, 0)?;
+                region.assign_advice(config.1, 0, Value::known(F::from(30)))?;
+                region.assign_advice(config.1, 1, Value::known(F::from(6)))?;
+                region.assign_advice(config.2, 0, Value::known(F::from(36)))
+            },
+        )?;
+
+        layouter.assign_instance(config.3, 0, sum.value().map(|sum| sum.evaluate()))?;
+        layouter.constrain_instance(*sum.cell(), config.3, 0);
+
+        Ok(())
+    }
+}
+
+fn verify_with_instances(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<Bn256>,
+    proof: &[u8],
+    instances: &[&[Fr]],
+) -> bool {
+    let mut transcripts = vec![Blake2bRead::<_, _, Challenge255<_>>::init(proof)];
+
+    let p_batcher = verify_proofs::<
+        Bn256,
+        VerifierGWC<_>,
+        _,
+        Blake2bRead<_, _, Challenge255<_>>,
+        AccumulatorStrategy<_>,
+        _,
+    >(
+        params.verifier_params(),
+        pk.get_vk(),
+        &[instances],
+        &mut transcripts,
+        OsRng,
+    );
+
+    match p_batcher {
+        Ok(p_batcher) => pairing_check(p_batcher),
+        Err(_) => false,
+    }
+}
+
+#[test]
+fn my_test_instance_assigned_in_first_phase() {
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys(K, |table, _| InstanceFromAdviceCircuit { table });
+
+    // The instance is a placeholder, overwritten by the circuit
+    let placeholder = [Fr::zero()];
+
+    let prover = MockProver::run(K, &circuit, vec![placeholder.to_vec()]).unwrap();
+    prover.assert_satisfied();
+
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+    let instances = create_proof_returning_instances::<Bn256, ProverGWC<_>, _, _, _, _>(
+        &params,
+        &pk,
+        &[circuit],
+        &[&[&placeholder]],
+        OsRng,
+        &mut transcript,
+    )
+    .expect("proof generation should not fail");
+    let proof = transcript.finalize();
+
+    assert_eq!(instances, vec![vec![vec![Fr::from(36u64)]]]);
+    assert!(verify_with_instances(
+        &params,
+        &pk,
+        &proof,
+        &[&instances[0][0]]
+    ));
+    assert!(!verify_with_instances(
+        &params,
+        &pk,
+        &proof,
+        &[&placeholder]
+    ));
+}
+
+/// Values `2 * a + b` for `a` in `0..8` and `b` in `0..2`, the table of
+/// `LinearCombinationLookupCircuit`
+fn generate_combination_tables(
+    params: &TableSRS<Bn256>,
+    k: usize,
+) -> (StaticTable<Bn256>, StaticTable<Bn256>) {
+    let values: Vec<Fr> = (0..8u64)
+        .flat_map(|a| (0..2u64).map(move |b| Fr::from(2 * a + b)))
+        .collect();
+    let table = || {
+        let opened = StaticTableValues::new(&values, params.g1()).unwrap();
+        let committed = opened
+            .commit(params.g1().len(), params.g2(), 1 << k)
+            .expect("committing the table should not fail");
+        StaticTable {
+            opened: Some(opened),
+            committed: Some(committed),
+        }
+    };
+
+    (table(), table())
+}
+
+/// Looks up `2 * a + b` over two advice columns, rather than a single query
+#[derive(Clone)]
+struct LinearCombinationLookupCircuit<E: MultiMillerLoop> {
+    table: StaticTable<E>,
+    rows: Vec<(u64, u64)>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E>
+    for LinearCombinationLookupCircuit<E>
+{
+    type Config = (Selector, Column<Advice>, Column<Advice>);
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let q = meta.complex_selector();
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        meta.lookup_static("lookup_combination", |meta| {
+            let q = meta.query_selector(q);
+            let a = meta.query_advice(a, Rotation::cur());
+            let b = meta.query_advice(b, Rotation::cur());
+            vec![(
+                q * (Expression::Constant(F::from(2)) * a + b),
+                StaticTableId(String::from("table")),
+            )]
+        });
+
+        (q, a, b)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.register_static_table(StaticTableId(String::from("table")), self.table.clone());
+
+        layouter.assign_region(
+            || "combinations",
+            |mut region| {
+                for (row, &(a, b)) in self.rows.iter().enumerate() {
+                    config.0.enable(&mut region, row)?;
+                    region.assign_advice(config.1, row, Value::known(F::from(a)))?;
+                    region.assign_advice(config.2, row, Value::known(F::from(b)))?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[test]
+fn my_test_linear_combination_lookup() {
+    const K: u32 = 3;
+    let (params, pk, circuit) = setup_keys_with_tables(
+        K,
+        generate_combination_tables,
+        |table, _| LinearCombinationLookupCircuit {
+            table,
+            // Neither `a` nor `b` alone has to be in the table, only `2 * a + b`
+            rows: vec![(3, 1), (0, 15)],
+        },
+        |srs| srs.b0_g1_bound(K),
+    );
+
+    let prover = MockProver::run(K, &circuit, vec![]).unwrap();
+    prover.assert_satisfied();
+
+    let proof = prove(&params, &pk, &circuit);
+    assert!(batch_verify(&params, &pk, &[proof]));
+
+    // 2 * 5 + 7 = 17 is past the end of the table
+    let outside = LinearCombinationLookupCircuit {
+        rows: vec![(5, 7)],
+        ..circuit
+    };
+    let prover = MockProver::run(K, &outside, vec![]).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+/// Looks up each of two advice columns in a table of its own
+#[derive(Clone)]
+struct TwoLookupsCircuit<E: MultiMillerLoop> {
+    table: StaticTable<E>,
+    table_2: StaticTable<E>,
+}
+
+impl<E: MultiMillerLoop<Scalar = F>, F: Field + FieldExt> Circuit<E> for TwoLookupsCircuit<E> {
+    type Config = (Column<Advice>, Column<Advice>);
+
+    type FloorPlanner = SimpleFloorPlanner<E>;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut halo2_proofs::plonk::ConstraintSystem<F>) -> Self::Config {
+        let advice = meta.advice_column();
+        let advice_2 = meta.advice_column();
+        meta.lookup_static("lookup_first", |meta| {
+            vec![(
+                meta.query_advice(advice, Rotation::cur()),
+                StaticTableId(String::from("table")),
+            )]
+        });
+        meta.lookup_static("lookup_second", |meta| {
+            vec![(
+                meta.query_advice(advice_2, Rotation::cur()),
+                StaticTableId(String::from("table_2")),
+            )]
+        });
+
+        (advice, advice_2)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl halo2_proofs::circuit::Layouter<F, E = E>,
+    ) -> Result<(), halo2_proofs::plonk::Error> {
+        layouter.register_static_table(StaticTableId(String::from("table")), self.table.clone());
+        layouter
+            .register_static_table(StaticTableId(String::from("table_2")), self.table_2.clone());
+
+        layouter.assign_region(
+            || "",
+            |mut region| {
+                for (row, (a, b)) in [(30u64, 8u64), (6, 1)].into_iter().enumerate() {
+                    region.assign_advice(config.0, row, Value::known(F::from(a)))?;
+                    region.assign_advice(config.1, row, Value::known(F::from(b)))?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+}
+
+#[test]
+fn my_test_deduplicated_tables_share_commitment() {
+    const K: u32 = 3;
+    let mut commits = 0;
+    let (params, pk, circuit) = setup_keys_with_tables(
+        K,
+        |srs, k| {
+            let values = [
+                0u64, 1, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30, 32,
+            ]
+            .map(Fr::from);
+            let mut committed = HashMap::new();
+            let mut tables = (0..2).map(|_| {
+                let opened = StaticTableValues::<Bn256>::new(&values, srs.g1()).unwrap();
+                committed
+                    .entry(opened.clone())
+                    .or_insert_with(|| {
+                        commits += 1;
+                        StaticTable {
+                            committed: Some(
+                                opened
+                                    .commit(srs.g1().len(), srs.g2(), 1 << k)
+                                    .expect("committing the table should not fail"),
+                            ),
+                            opened: Some(opened),
+                        }
+                    })
+                    .clone()
+            });
+            (tables.next().unwrap(), tables.next().unwrap())
+        },
+        |table, table_2| TwoLookupsCircuit { table, table_2 },
+        |srs| srs.b0_g1_bound(K),
+    );
+    assert_eq!(commits, 1);
+
+    let commitments = pk.get_vk().static_commitments();
+    assert_eq!(
+        commitments[&StaticTableId(String::from("table"))],
+        commitments[&StaticTableId(String::from("table_2"))]
+    );
+
+    let proof = prove(&params, &pk, &circuit);
+    assert!(batch_verify(&params, &pk, &[proof]));
+}
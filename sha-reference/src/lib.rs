@@ -1,6 +1,15 @@
+#![no_std]
+
+#[cfg(test)]
+extern crate std;
+
+pub use crate::sha256::sha256;
 pub use crate::word::Word;
 use crate::word::{choose, majority};
+use core::fmt;
+use core::ops::Index;
 
+mod sha256;
 mod word;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -15,6 +24,64 @@ pub struct Octet<const L: usize> {
     h: Word<L>,
 }
 
+impl<const L: usize> Octet<L> {
+    /// Returns the words in order `a, b, c, d, e, f, g, h`
+    pub fn words(&self) -> [Word<L>; 8] {
+        [
+            self.a, self.b, self.c, self.d, self.e, self.f, self.g, self.h,
+        ]
+    }
+}
+
+impl<const L: usize> From<[Word<L>; 8]> for Octet<L> {
+    fn from([a, b, c, d, e, f, g, h]: [Word<L>; 8]) -> Self {
+        Self {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+            h,
+        }
+    }
+}
+
+impl<const L: usize> Index<usize> for Octet<L> {
+    type Output = Word<L>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.a,
+            1 => &self.b,
+            2 => &self.c,
+            3 => &self.d,
+            4 => &self.e,
+            5 => &self.f,
+            6 => &self.g,
+            7 => &self.h,
+            _ => panic!("Octet index out of range: {index}"),
+        }
+    }
+}
+
+/// Renders the eight words as a concatenated big-endian hex digest
+impl<const L: usize> fmt::LowerHex for Octet<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for word in self.words() {
+            write!(f, "{word:x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<const L: usize> fmt::Display for Octet<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:x}")
+    }
+}
+
 pub fn sha_round<const L: usize>(input: Octet<L>) -> Octet<L> {
     let mut output = input;
 
@@ -46,6 +113,7 @@ pub fn sha<const L: usize>(input: Octet<L>) -> Octet<L> {
 mod tests{
     use crate::{Octet, Word};
     use crate::word::Bit::{One, Zero};
+    use std::string::ToString;
 
     /// Test input on 2-bit words:
     /// a: 00, b: 01, c: 10, d: 11, e: 00, f: 01, g: 10, h: 11
@@ -77,4 +145,25 @@ mod tests{
         let output = super::sha_round(input);
         assert_eq!(output, expected);
     }
+
+    #[test]
+    fn test_display_abc_digest() {
+        let digest = Octet::<32>::from([
+            Word::from(0xba7816bf),
+            Word::from(0x8f01cfea),
+            Word::from(0x414140de),
+            Word::from(0x5dae2223),
+            Word::from(0xb00361a3),
+            Word::from(0x96177a9c),
+            Word::from(0xb410ff61),
+            Word::from(0xf20015ad),
+        ]);
+
+        assert_eq!(
+            digest.to_string(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(digest[7], Word::from(0xf20015ad));
+        assert_eq!(digest.words()[0], digest[0]);
+    }
 }
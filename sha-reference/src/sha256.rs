@@ -0,0 +1,141 @@
+//! Full SHA-256 over byte messages, built from the `Word` primitives.
+
+use crate::word::{choose, majority};
+use crate::{Octet, Word};
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const BLOCK_LEN: usize = 64;
+
+/// Computes the SHA-256 digest of `message`
+pub fn sha256(message: &[u8]) -> Octet<32> {
+    let mut state = Octet::from(INITIAL_STATE.map(Word::from));
+
+    let mut blocks = message.chunks_exact(BLOCK_LEN);
+    for block in blocks.by_ref() {
+        state = compress(state, block);
+    }
+
+    // Padding: a single one bit, zeros, then the message length in bits
+    let remainder = blocks.remainder();
+    let mut tail = [0u8; 2 * BLOCK_LEN];
+    tail[..remainder.len()].copy_from_slice(remainder);
+    tail[remainder.len()] = 0x80;
+    let tail_len = if remainder.len() + 9 <= BLOCK_LEN {
+        BLOCK_LEN
+    } else {
+        2 * BLOCK_LEN
+    };
+    let bit_len = (message.len() as u64).wrapping_mul(8);
+    tail[tail_len - 8..tail_len].copy_from_slice(&bit_len.to_be_bytes());
+
+    for block in tail[..tail_len].chunks_exact(BLOCK_LEN) {
+        state = compress(state, block);
+    }
+
+    state
+}
+
+fn compress(state: Octet<32>, block: &[u8]) -> Octet<32> {
+    let mut schedule = [Word::<32>::zero(); 64];
+    for (word, bytes) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+        *word = Word::from(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
+    }
+    for t in 16..64 {
+        schedule[t] = schedule[t - 2]
+            .sigma_1()
+            .wrapping_add(schedule[t - 7])
+            .wrapping_add(schedule[t - 15].sigma_0())
+            .wrapping_add(schedule[t - 16]);
+    }
+
+    let mut working = state;
+    for (&constant, &word) in ROUND_CONSTANTS.iter().zip(schedule.iter()) {
+        let Octet {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+            h,
+        } = working;
+
+        let temp_1 = h
+            .wrapping_add(e.rot_1())
+            .wrapping_add(choose(&e, &f, &g))
+            .wrapping_add(Word::from(constant))
+            .wrapping_add(word);
+        let temp_2 = a.rot_0().wrapping_add(majority(&a, &b, &c));
+
+        working = Octet {
+            a: temp_1.wrapping_add(temp_2),
+            b: a,
+            c: b,
+            d: c,
+            e: d.wrapping_add(temp_1),
+            f: e,
+            g: f,
+            h: g,
+        };
+    }
+
+    let mut words = state.words();
+    for (word, update) in words.iter_mut().zip(working.words()) {
+        *word = word.wrapping_add(update);
+    }
+    Octet::from(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sha256;
+    use proptest::{collection::vec, prelude::*};
+    use sha2::{Digest, Sha256};
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_known_vectors() {
+        assert_eq!(
+            sha256(b"").to_string(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256(b"abc").to_string(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_string(),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_against_sha2(message in vec(any::<u8>(), 0..200)) {
+            let expected: Vec<u8> = Sha256::digest(&message).to_vec();
+            let digest: Vec<u8> = sha256(&message)
+                .words()
+                .iter()
+                .flat_map(|&word| u32::from(word).to_be_bytes())
+                .collect();
+
+            prop_assert_eq!(digest, expected);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(unused)]
 
-use std::ops::{Add, BitXor, Index, IndexMut};
+use core::fmt;
+use core::ops::{Add, BitXor, Index, IndexMut};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Default)]
 pub enum Bit {
@@ -49,6 +50,42 @@ impl<const L: usize> Word<L> {
     pub fn rot_1(&self) -> Self {
         self.right_rotation(6) ^ self.right_rotation(11) ^ self.right_rotation(25)
     }
+
+    fn right_shift(&self, n: usize) -> Self {
+        let mut result = Self::zero();
+        for i in n..L {
+            result[i] = self[i - n];
+        }
+        result
+    }
+
+    /// Message schedule function `σ_0`
+    pub fn sigma_0(&self) -> Self {
+        self.right_rotation(7) ^ self.right_rotation(18) ^ self.right_shift(3)
+    }
+
+    /// Message schedule function `σ_1`
+    pub fn sigma_1(&self) -> Self {
+        self.right_rotation(17) ^ self.right_rotation(19) ^ self.right_shift(10)
+    }
+
+    /// Addition modulo `2^L`, as opposed to the bitwise `Add`
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        let mut result = Self::zero();
+        let mut carry = Bit::Zero;
+        for i in (0..L).rev() {
+            result[i] = self[i] + rhs[i] + carry;
+            carry = majority_bit(self[i], rhs[i], carry);
+        }
+        result
+    }
+}
+
+fn majority_bit(a: Bit, b: Bit, c: Bit) -> Bit {
+    match (a, b, c) {
+        (Bit::One, Bit::One, _) | (Bit::One, _, Bit::One) | (_, Bit::One, Bit::One) => Bit::One,
+        _ => Bit::Zero,
+    }
 }
 
 impl<const L: usize> From<[Bit; L]> for Word<L> {
@@ -57,6 +94,40 @@ impl<const L: usize> From<[Bit; L]> for Word<L> {
     }
 }
 
+/// Bits are taken from the most significant one
+impl From<u32> for Word<32> {
+    fn from(value: u32) -> Self {
+        let mut result = Self::zero();
+        for i in 0..32 {
+            if (value >> (31 - i)) & 1 == 1 {
+                result[i] = Bit::One;
+            }
+        }
+        result
+    }
+}
+
+impl From<Word<32>> for u32 {
+    fn from(word: Word<32>) -> Self {
+        word.bits
+            .iter()
+            .fold(0, |acc, &bit| (acc << 1) | (bit == Bit::One) as u32)
+    }
+}
+
+/// Renders the word as big-endian hex digits, `L` must be a multiple of 4
+impl<const L: usize> fmt::LowerHex for Word<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for nibble in self.bits.chunks(4) {
+            let digit = nibble
+                .iter()
+                .fold(0u32, |acc, &bit| (acc << 1) | (bit == Bit::One) as u32);
+            write!(f, "{digit:x}")?;
+        }
+        Ok(())
+    }
+}
+
 pub fn majority<const L: usize>(a: &Word<L>, b: &Word<L>, c: &Word<L>) -> Word<L> {
     let mut result = Word::zero();
     for i in 0..L {
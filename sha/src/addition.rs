@@ -0,0 +1,243 @@
+//! Addition gates modulo 2^32:
+//!     - two operands: a + b = result + carry * 2^32
+//!     - three operands: a + b + c = result + carry * 2^32
+//!
+//! Both are configured by `configure_addition_gate`, parameterized by the number of operands. All
+//! operands, the result and the carry live in a single row, so the two-operand case does not need
+//! a dummy third cell. The carry is range checked with a static lookup into a carry table (see
+//! `create_carry_table`), which the caller registers with `AdditionConfig::register_carry_table`.
+//! Range checks of the result are left to the caller.
+
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{Layouter, Region, Value};
+use halo2_proofs::halo2curves::pairing::MultiMillerLoop;
+use halo2_proofs::plonk::static_lookup::{StaticTable, StaticTableId};
+use halo2_proofs::plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector};
+use halo2_proofs::poly::Rotation;
+
+const WORD_MODULUS: u64 = 1 << 32;
+
+#[derive(Clone, Debug)]
+pub struct AdditionConfig<const N: usize> {
+    operands: [Column<Advice>; N],
+    result: Column<Advice>,
+    carry: Column<Advice>,
+    carry_table: StaticTableId<String>,
+    selector: Selector,
+}
+
+impl<const N: usize> AdditionConfig<N> {
+    /// Registers `table` as the carry table looked up by this gate.
+    ///
+    /// Inactive rows look up a zero carry, so `table` must contain 0.
+    pub fn register_carry_table<E: MultiMillerLoop>(
+        &self,
+        layouter: &mut impl Layouter<E::Scalar, E = E>,
+        table: StaticTable<E>,
+    ) {
+        layouter.register_static_table(self.carry_table.clone(), table);
+    }
+
+    /// Assigns one addition at `offset` and enables the gate there.
+    pub fn assign<F: FieldExt>(
+        &self,
+        region: &mut Region<F>,
+        offset: usize,
+        operands: [Value<F>; N],
+        result: Value<F>,
+        carry: Value<F>,
+    ) -> Result<(), Error> {
+        self.selector.enable(region, offset)?;
+        for (&column, value) in self.operands.iter().zip(operands) {
+            region.assign_advice(column, offset, value)?;
+        }
+        region.assign_advice(self.result, offset, result)?;
+        region.assign_advice(self.carry, offset, carry)?;
+        Ok(())
+    }
+}
+
+/// Configures the addition of `N` operands, for `N` of 2 or 3 when computing SHA-256.
+///
+/// The carry of `N` 32-bit operands is at most `N - 1`. Static tables have power-of-two sizes, so
+/// `carry_table` can hold `create_carry_table(N.next_power_of_two() - 1)`: larger carries cannot
+/// satisfy the gate when the operands and the result are 32-bit.
+pub fn configure_addition_gate<F: FieldExt, const N: usize>(
+    meta: &mut ConstraintSystem<F>,
+    operands: [Column<Advice>; N],
+    result: Column<Advice>,
+    carry: Column<Advice>,
+    carry_table: StaticTableId<String>,
+) -> AdditionConfig<N> {
+    // Static lookup inputs cannot contain simple selectors
+    let selector = meta.complex_selector();
+
+    meta.create_gate("addition", |vc| {
+        let s = vc.query_selector(selector);
+        let sum = operands
+            .iter()
+            .map(|&column| vc.query_advice(column, Rotation::cur()))
+            .reduce(|acc, operand| acc + operand)
+            .expect("at least one operand");
+        let result = vc.query_advice(result, Rotation::cur());
+        let carry = vc.query_advice(carry, Rotation::cur());
+
+        vec![s * (sum - result - carry * Expression::Constant(F::from(WORD_MODULUS)))]
+    });
+
+    meta.lookup_static("addition carry", |vc| {
+        let s = vc.query_selector(selector);
+        let carry = vc.query_advice(carry, Rotation::cur());
+
+        vec![(s * carry, carry_table.clone())]
+    });
+
+    AdditionConfig {
+        operands,
+        result,
+        carry,
+        carry_table,
+        selector,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{configure_addition_gate, AdditionConfig};
+    use crate::tables::create_carry_table;
+    use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+    use halo2_proofs::dev::{MockProver, VerifyFailure};
+    use halo2_proofs::halo2curves::bn256::{Bn256, Fr};
+    use halo2_proofs::halo2curves::pairing::MultiMillerLoop;
+    use halo2_proofs::plonk::static_lookup::{StaticTable, StaticTableId, StaticTableValues};
+    use halo2_proofs::plonk::{Circuit, ConstraintSystem, Error};
+    use halo2_proofs::poly::kzg::commitment::TableSRS;
+
+    #[derive(Clone)]
+    struct AdditionCircuit<E: MultiMillerLoop, const N: usize> {
+        operands: [E::Scalar; N],
+        result: E::Scalar,
+        carry: E::Scalar,
+        carry_table: StaticTable<E>,
+    }
+
+    /// Table of the carries `0..N.next_power_of_two()`, which covers every carry of `N` operands
+    fn carry_table<const N: usize>() -> StaticTable<Bn256> {
+        let max_carry = N.next_power_of_two() as u64 - 1;
+        let srs = TableSRS::<Bn256>::setup_from_toxic_waste(max_carry as usize, 1, Fr::from(7));
+        let values: Vec<Fr> = create_carry_table(max_carry)
+            .into_iter()
+            .map(Fr::from)
+            .collect();
+
+        StaticTable {
+            opened: Some(
+                StaticTableValues::new(&values, &srs.g1()).expect("table size is a power of two"),
+            ),
+            committed: None,
+        }
+    }
+
+    impl<const N: usize> AdditionCircuit<Bn256, N> {
+        fn new(operands: [u64; N], result: u64, carry: u64) -> Self {
+            Self {
+                operands: operands.map(Fr::from),
+                result: Fr::from(result),
+                carry: Fr::from(carry),
+                carry_table: carry_table::<N>(),
+            }
+        }
+    }
+
+    impl<E: MultiMillerLoop, const N: usize> Circuit<E> for AdditionCircuit<E, N> {
+        type Config = AdditionConfig<N>;
+        type FloorPlanner = SimpleFloorPlanner<E>;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<E::Scalar>) -> Self::Config {
+            let operands = [(); N].map(|_| meta.advice_column());
+            let result = meta.advice_column();
+            let carry = meta.advice_column();
+            let carry_table = StaticTableId(String::from("carry"));
+            configure_addition_gate(meta, operands, result, carry, carry_table)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<E::Scalar, E = E>,
+        ) -> Result<(), Error> {
+            config.register_carry_table(&mut layouter, self.carry_table.clone());
+
+            layouter.assign_region(
+                || "addition",
+                |mut region| {
+                    config.assign(
+                        &mut region,
+                        0,
+                        self.operands.map(Value::known),
+                        Value::known(self.result),
+                        Value::known(self.carry),
+                    )
+                },
+            )
+        }
+    }
+
+    fn run<const N: usize>(circuit: AdditionCircuit<Bn256, N>) -> Result<(), Vec<VerifyFailure>> {
+        MockProver::run(4, &circuit, vec![])
+            .expect("proving should not fail")
+            .verify()
+    }
+
+    fn verify<const N: usize>(circuit: AdditionCircuit<Bn256, N>) -> bool {
+        run(circuit).is_ok()
+    }
+
+    #[test]
+    fn add2_accepts_correct_sum() {
+        assert!(verify(AdditionCircuit::new([0xffff_ffff, 2], 1, 1)));
+    }
+
+    #[test]
+    fn add2_rejects_incorrect_sum() {
+        assert!(!verify(AdditionCircuit::new([0xffff_ffff, 2], 1, 0)));
+    }
+
+    #[test]
+    fn add3_accepts_correct_sum() {
+        assert!(verify(AdditionCircuit::new(
+            [0xffff_ffff, 0xffff_ffff, 3],
+            1,
+            2
+        )));
+    }
+
+    #[test]
+    fn add3_rejects_incorrect_sum() {
+        assert!(!verify(AdditionCircuit::new(
+            [0xffff_ffff, 0xffff_ffff, 3],
+            2,
+            2
+        )));
+    }
+
+    #[test]
+    fn add2_rejects_out_of_range_carry() {
+        // 0xffff_ffff + 2 = -(2^32 - 1) + 2 * 2^32 holds in the field, only the carry lookup fails
+        let circuit = AdditionCircuit {
+            result: -Fr::from(0xffff_ffff),
+            carry: Fr::from(2),
+            ..AdditionCircuit::new([0xffff_ffff, 2], 0, 0)
+        };
+
+        let failures = run(circuit).unwrap_err();
+        assert!(!failures.is_empty());
+        assert!(failures
+            .iter()
+            .all(|failure| matches!(failure, VerifyFailure::StaticLookup { .. })));
+    }
+}
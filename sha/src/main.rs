@@ -4,6 +4,7 @@ use halo2_proofs::halo2curves::pairing::MultiMillerLoop;
 use halo2_proofs::plonk::Circuit;
 use crate::simple_circuit::SimpleCircuit;
 
+mod addition;
 mod simple_circuit;
 mod tables;
 
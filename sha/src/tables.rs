@@ -1,6 +1,8 @@
-use crate::tables::limbs::{Bits, Limbs};
+use halo2_proofs::arithmetic::FieldExt;
 
-type Table = Vec<(u64, u64, u64, u64)>;
+pub use crate::tables::limbs::{BalancedLimbs, Bits, Limbs, LongLimbs, ShortLimbs, TinyLimbs};
+
+pub type Table = Vec<(u64, u64, u64, u64)>;
 
 mod limbs {
     use std::ops::{BitAnd, BitOr, BitXor, Range, Shl, Shr};
@@ -31,10 +33,10 @@ mod limbs {
         const BITS_LEN: usize = 8;
     }
     impl Bits for u16 {
-        const BITS_LEN: usize = 8;
+        const BITS_LEN: usize = 16;
     }
     impl Bits for u32 {
-        const BITS_LEN: usize = 8;
+        const BITS_LEN: usize = 32;
     }
 
     pub trait Limbs {
@@ -46,6 +48,7 @@ mod limbs {
             + TryInto<u64>;
         const FIRST_LIMB_LEN: u8;
         const SECOND_LIMB_LEN: u8;
+        const THIRD_LIMB_LEN: u8;
 
         fn first_limb_rg() -> Range<u64> {
             0..(1 << Self::FIRST_LIMB_LEN)
@@ -55,16 +58,30 @@ mod limbs {
             0..(1 << Self::SECOND_LIMB_LEN)
         }
 
+        fn third_limb_rg() -> Range<u64> {
+            0..(1 << Self::THIRD_LIMB_LEN)
+        }
+
         fn full_word_len() -> u8 {
-            Self::FIRST_LIMB_LEN + Self::SECOND_LIMB_LEN + Self::SECOND_LIMB_LEN
+            Self::FIRST_LIMB_LEN + Self::SECOND_LIMB_LEN + Self::THIRD_LIMB_LEN
         }
     }
 
+    /// 8-bit words split as 4/2/2, small enough for exhaustive tables in tests
+    pub struct TinyLimbs;
+    impl Limbs for TinyLimbs {
+        type FullWord = u8;
+        const FIRST_LIMB_LEN: u8 = 4;
+        const SECOND_LIMB_LEN: u8 = 2;
+        const THIRD_LIMB_LEN: u8 = 2;
+    }
+
     pub struct ShortLimbs;
     impl Limbs for ShortLimbs {
         type FullWord = u16;
         const FIRST_LIMB_LEN: u8 = 6;
         const SECOND_LIMB_LEN: u8 = 5;
+        const THIRD_LIMB_LEN: u8 = 5;
     }
 
     pub struct LongLimbs;
@@ -72,6 +89,16 @@ mod limbs {
         type FullWord = u32;
         const FIRST_LIMB_LEN: u8 = 12;
         const SECOND_LIMB_LEN: u8 = 10;
+        const THIRD_LIMB_LEN: u8 = 10;
+    }
+
+    /// 32-bit words split as 11/11/10, so that no limb table is much larger than the others
+    pub struct BalancedLimbs;
+    impl Limbs for BalancedLimbs {
+        type FullWord = u32;
+        const FIRST_LIMB_LEN: u8 = 11;
+        const SECOND_LIMB_LEN: u8 = 11;
+        const THIRD_LIMB_LEN: u8 = 10;
     }
 }
 
@@ -79,7 +106,7 @@ fn create_synthesis_table<L: Limbs>(f: impl Fn(u64, u64, u64) -> u64) -> Table {
     let mut table = vec![];
     for x in L::first_limb_rg() {
         for y in L::second_limb_rg() {
-            for z in L::second_limb_rg() {
+            for z in L::third_limb_rg() {
                 table.push((x, y, z, f(x, y, z)));
             }
         }
@@ -88,8 +115,33 @@ fn create_synthesis_table<L: Limbs>(f: impl Fn(u64, u64, u64) -> u64) -> Table {
     table
 }
 
+/// Like [`create_synthesis_table`], but writes every row straight into four field-element columns
+pub fn create_synthesis_table_f<F: FieldExt, L: Limbs>(
+    f: impl Fn(u64, u64, u64) -> u64,
+) -> (Vec<F>, Vec<F>, Vec<F>, Vec<F>) {
+    let size = L::first_limb_rg().count() * L::second_limb_rg().count() * L::third_limb_rg().count();
+    let mut columns = (
+        Vec::with_capacity(size),
+        Vec::with_capacity(size),
+        Vec::with_capacity(size),
+        Vec::with_capacity(size),
+    );
+    for x in L::first_limb_rg() {
+        for y in L::second_limb_rg() {
+            for z in L::third_limb_rg() {
+                columns.0.push(F::from(x));
+                columns.1.push(F::from(y));
+                columns.2.push(F::from(z));
+                columns.3.push(F::from(f(x, y, z)));
+            }
+        }
+    }
+
+    columns
+}
+
 fn combine<L: Limbs>(x: u64, y: u64, z: u64) -> L::FullWord {
-    ((x << (L::SECOND_LIMB_LEN + L::SECOND_LIMB_LEN)) | (y << L::SECOND_LIMB_LEN) | z)
+    ((x << (L::SECOND_LIMB_LEN + L::THIRD_LIMB_LEN)) | (y << L::THIRD_LIMB_LEN) | z)
         .try_into()
         .map_err(|_| ())
         .unwrap()
@@ -102,20 +154,36 @@ fn rotation<L: Limbs, const N: usize>(word: L::FullWord) -> L::FullWord {
     L::FullWord::from_bits(bits)
 }
 
-fn create_rotation_table<L: Limbs, const R1: usize, const R2: usize, const R3: usize>() -> Table {
-    create_synthesis_table::<L>(|x, y, z| {
-        let xyz = combine::<L>(x, y, z);
-        let rot0 = rotation::<L, R1>(xyz) ^ rotation::<L, R2>(xyz) ^ rotation::<L, R3>(xyz);
-        rot0.try_into().map_err(|_| ()).unwrap()
-    })
+/// The rotation functions of the SHA-256 compression round
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RotationType {
+    /// `Σ_0`, rotating by 2, 13 and 22 bits
+    Rot0,
+    /// `Σ_1`, rotating by 6, 11 and 25 bits
+    Rot1,
+}
+
+/// Reference value of `which` rotation of the word with limbs `x`, `y` and `z`, used both
+/// by the rotation tables and to compute rotation witnesses
+pub fn rotate_word<L: Limbs>(x: u64, y: u64, z: u64, which: RotationType) -> u64 {
+    let xyz = combine::<L>(x, y, z);
+    let rotated = match which {
+        RotationType::Rot0 => rotation::<L, 2>(xyz) ^ rotation::<L, 13>(xyz) ^ rotation::<L, 22>(xyz),
+        RotationType::Rot1 => rotation::<L, 6>(xyz) ^ rotation::<L, 11>(xyz) ^ rotation::<L, 25>(xyz),
+    };
+    rotated.try_into().map_err(|_| ()).unwrap()
+}
+
+fn create_rotation_table<L: Limbs>(which: RotationType) -> Table {
+    create_synthesis_table::<L>(|x, y, z| rotate_word::<L>(x, y, z, which))
 }
 
 pub fn create_rot0_table<L: Limbs>() -> Table {
-    create_rotation_table::<L, 2, 13, 22>()
+    create_rotation_table::<L>(RotationType::Rot0)
 }
 
 pub fn create_rot1_table<L: Limbs>() -> Table {
-    create_rotation_table::<L, 6, 11, 25>()
+    create_rotation_table::<L>(RotationType::Rot1)
 }
 
 pub fn create_maj_table<L: Limbs>() -> Table {
@@ -136,16 +204,16 @@ pub fn create_decomposition_table<L: Limbs, const K: u8>() -> Table {
     let mut table = vec![];
     for a in 0u64..(1 << K) {
         let a_mod = a % (1 << (L::full_word_len()));
-        let x = a_mod >> (L::SECOND_LIMB_LEN + L::SECOND_LIMB_LEN);
+        let x = a_mod >> (L::SECOND_LIMB_LEN + L::THIRD_LIMB_LEN);
 
-        let mut y = a_mod >> L::SECOND_LIMB_LEN;
+        let mut y = a_mod >> L::THIRD_LIMB_LEN;
         for i in 0..L::FIRST_LIMB_LEN {
             y &= !(1 << (i + L::SECOND_LIMB_LEN));
         }
 
         let mut z = a_mod;
         for i in 0..(L::FIRST_LIMB_LEN + L::SECOND_LIMB_LEN) {
-            z &= !(1 << (i + L::SECOND_LIMB_LEN));
+            z &= !(1 << (i + L::THIRD_LIMB_LEN));
         }
 
         table.push((a, x, y, z));
@@ -153,21 +221,40 @@ pub fn create_decomposition_table<L: Limbs, const K: u8>() -> Table {
     table
 }
 
+/// Split `table` into its four columns, mapping every entry into the field
+pub fn decompose_table<F: FieldExt>(table: Table) -> (Vec<F>, Vec<F>, Vec<F>, Vec<F>) {
+    let mut columns = (
+        Vec::with_capacity(table.len()),
+        Vec::with_capacity(table.len()),
+        Vec::with_capacity(table.len()),
+        Vec::with_capacity(table.len()),
+    );
+    for (a, b, c, d) in table {
+        columns.0.push(F::from(a));
+        columns.1.push(F::from(b));
+        columns.2.push(F::from(c));
+        columns.3.push(F::from(d));
+    }
+    columns
+}
+
+/// All valid carries of an addition whose carry is at most `max_carry`
+pub fn create_carry_table(max_carry: u64) -> Vec<u64> {
+    (0..=max_carry).collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::tables::limbs::Limbs;
-    use crate::tables::{create_ch_table, create_decomposition_table, create_maj_table, create_rot0_table, create_rot1_table};
-
-    struct TestLimbs;
-    impl Limbs for TestLimbs {
-        type FullWord = u8;
-        const FIRST_LIMB_LEN: u8 = 4;
-        const SECOND_LIMB_LEN: u8 = 2;
-    }
+    use crate::tables::{
+        combine, create_carry_table, create_ch_table, create_decomposition_table, create_maj_table, create_rot0_table,
+        create_rot1_table, create_synthesis_table, create_synthesis_table_f, decompose_table, rotate_word, rotation,
+        BalancedLimbs, Bits, Limbs, LongLimbs, RotationType, ShortLimbs, TinyLimbs,
+    };
+    use halo2_proofs::halo2curves::bn256::Fr;
 
     #[test]
     fn rot0_works() {
-        let table = create_rot0_table::<TestLimbs>();
+        let table = create_rot0_table::<TinyLimbs>();
         assert_eq!(table.len(), 256);
         assert!(table.contains(&(0, 0, 0, 0)));
         assert!(table.contains(&(0b0000_1100, 0b000000_01, 0b000000_00, 0b0000_0100)));
@@ -176,16 +263,93 @@ mod tests {
 
     #[test]
     fn rot1_works() {
-        let table = create_rot1_table::<TestLimbs>();
+        let table = create_rot1_table::<TinyLimbs>();
         assert_eq!(table.len(), 256);
         assert!(table.contains(&(0, 0, 0, 0)));
         assert!(table.contains(&(0b0000_1100, 0b000000_01, 0b000000_00, 0b1110_1001)));
         assert!(table.contains(&(0b0000_1010, 0b000000_01, 0b000000_10, 0b0001_1101)));
     }
 
+    fn assert_bits_roundtrip<B: Bits + PartialEq + std::fmt::Debug>(values: &[B]) {
+        for &value in values {
+            let bits = value.to_bits();
+            assert_eq!(bits.len(), B::BITS_LEN);
+            assert!(bits.iter().all(|&bit| bit <= 1));
+            assert_eq!(B::from_bits(bits), value);
+        }
+    }
+
+    #[test]
+    fn bits_roundtrip() {
+        assert_bits_roundtrip::<u8>(&[0, 1, 0x81, 0xa5, u8::MAX]);
+        assert_bits_roundtrip::<u16>(&[0, 1, 0x8001, 0xbeef, u16::MAX]);
+        assert_bits_roundtrip::<u32>(&[0, 1, 0x8000_0001, 0xdead_beef, u32::MAX]);
+    }
+
+    #[test]
+    fn rotation_works_for_long_limbs() {
+        for word in [0u32, 1, 0x8000_0001, 0xdead_beef, u32::MAX] {
+            assert_eq!(rotation::<LongLimbs, 2>(word), word.rotate_right(2));
+            assert_eq!(rotation::<LongLimbs, 13>(word), word.rotate_right(13));
+            assert_eq!(rotation::<LongLimbs, 22>(word), word.rotate_right(22));
+            assert_eq!(rotation::<LongLimbs, 25>(word), word.rotate_right(25));
+        }
+    }
+
+    #[test]
+    fn rotation_works_for_short_limbs() {
+        for word in [0u16, 1, 0x8001, 0xbeef, u16::MAX] {
+            assert_eq!(rotation::<ShortLimbs, 6>(word), word.rotate_right(6));
+            assert_eq!(rotation::<ShortLimbs, 11>(word), word.rotate_right(11));
+        }
+    }
+
+    /// Splits a 32-bit word into the limbs of `L`, the inverse of `combine`
+    fn split<L: Limbs>(word: u32) -> (u64, u64, u64) {
+        let word = word as u64;
+        (
+            word >> (L::SECOND_LIMB_LEN + L::THIRD_LIMB_LEN),
+            (word >> L::THIRD_LIMB_LEN) % L::second_limb_rg().end,
+            word % L::third_limb_rg().end,
+        )
+    }
+
+    fn assert_rotate_word_matches_reference<L: Limbs>() {
+        for word in [0u32, 1, 0x8000_0001, 0xdead_beef, 0x6a09_e667, u32::MAX] {
+            let (x, y, z) = split::<L>(word);
+            let reference = sha_reference::Word::<32>::from(word);
+            assert_eq!(
+                rotate_word::<L>(x, y, z, RotationType::Rot0),
+                u32::from(reference.rot_0()) as u64
+            );
+            assert_eq!(
+                rotate_word::<L>(x, y, z, RotationType::Rot1),
+                u32::from(reference.rot_1()) as u64
+            );
+        }
+    }
+
+    #[test]
+    fn rotate_word_matches_reference() {
+        assert_rotate_word_matches_reference::<LongLimbs>();
+        assert_rotate_word_matches_reference::<BalancedLimbs>();
+    }
+
+    #[test]
+    fn combine_fills_full_word() {
+        let x = LongLimbs::first_limb_rg().end - 1;
+        let y = LongLimbs::second_limb_rg().end - 1;
+        assert_eq!(combine::<LongLimbs>(x, y, y), u32::MAX);
+        assert_eq!(combine::<LongLimbs>(1, 0, 0), 1 << 20);
+
+        let x = ShortLimbs::first_limb_rg().end - 1;
+        let y = ShortLimbs::second_limb_rg().end - 1;
+        assert_eq!(combine::<ShortLimbs>(x, y, y), u16::MAX);
+    }
+
     #[test]
     fn maj_works() {
-        let table = create_maj_table::<TestLimbs>();
+        let table = create_maj_table::<TinyLimbs>();
         assert_eq!(table.len(), 256);
         assert!(table.contains(&(0, 0, 0, 0)));
         assert!(table.contains(&(0b0000_1100, 0b000000_01, 0b000000_00, 0b0000_0000)));
@@ -194,7 +358,7 @@ mod tests {
 
     #[test]
     fn ch_works() {
-        let table = create_ch_table::<TestLimbs>();
+        let table = create_ch_table::<TinyLimbs>();
         assert_eq!(table.len(), 256);
         assert!(table.contains(&(0, 0, 0, 0)));
         assert!(table.contains(&(0b0000_1100, 0b000000_01, 0b000000_00, 0b0000_0000)));
@@ -203,10 +367,64 @@ mod tests {
 
     #[test]
     fn decomposition_works(){
-        let table = create_decomposition_table::<TestLimbs, 10>();
+        let table = create_decomposition_table::<TinyLimbs, 10>();
         assert_eq!(table.len(), 1024);
         assert!(table.contains(&(0, 0, 0, 0)));
         assert!(table.contains(&(0b10_1010_1010, 0b0000_1010, 0b000000_10, 0b000000_10)));
         assert!(table.contains(&(0b11_1011_0110, 0b0000_1011, 0b000000_01, 0b000000_10)));
     }
+
+    #[test]
+    fn decompose_table_works() {
+        let table = create_maj_table::<TinyLimbs>();
+        let (a, b, c, d) = decompose_table::<Fr>(table.clone());
+        assert_eq!(a.len(), table.len());
+        assert_eq!(b.len(), table.len());
+        assert_eq!(c.len(), table.len());
+        assert_eq!(d.len(), table.len());
+        for (i, &(x, y, z, maj)) in table.iter().enumerate() {
+            assert_eq!(a[i], Fr::from(x));
+            assert_eq!(b[i], Fr::from(y));
+            assert_eq!(c[i], Fr::from(z));
+            assert_eq!(d[i], Fr::from(maj));
+        }
+    }
+
+    #[test]
+    fn synthesis_table_f_matches_tuple_table() {
+        let xor = |x: u64, y: u64, z: u64| x ^ y ^ z;
+        let expected = decompose_table::<Fr>(create_synthesis_table::<TinyLimbs>(xor));
+        let columns = create_synthesis_table_f::<Fr, TinyLimbs>(xor);
+        assert_eq!(columns, expected);
+    }
+
+    #[test]
+    fn balanced_decomposition_recomposes() {
+        assert_eq!(BalancedLimbs::full_word_len(), 32);
+
+        let table = create_decomposition_table::<BalancedLimbs, 16>();
+        assert_eq!(table.len(), 1 << 16);
+        for &(a, x, y, z) in &table {
+            assert!(BalancedLimbs::first_limb_rg().contains(&x));
+            assert!(BalancedLimbs::second_limb_rg().contains(&y));
+            assert!(BalancedLimbs::third_limb_rg().contains(&z));
+            assert_eq!(combine::<BalancedLimbs>(x, y, z) as u64, a);
+        }
+
+        let x = BalancedLimbs::first_limb_rg().end - 1;
+        let y = BalancedLimbs::second_limb_rg().end - 1;
+        let z = BalancedLimbs::third_limb_rg().end - 1;
+        assert_eq!(combine::<BalancedLimbs>(x, y, z), u32::MAX);
+        assert_eq!(combine::<BalancedLimbs>(1, 0, 0), 1 << 21);
+        assert_eq!(combine::<BalancedLimbs>(0, 1, 0), 1 << 10);
+    }
+
+    #[test]
+    fn carry_works() {
+        let table = create_carry_table(4);
+        assert_eq!(table.len(), 5);
+        assert!(table.contains(&0));
+        assert!(table.contains(&4));
+        assert!(!table.contains(&5));
+    }
 }